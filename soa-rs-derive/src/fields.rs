@@ -1,14 +1,19 @@
 use crate::{
+    parse_align,
     zst::{zst_struct, ZstKind},
-    SoaAttrs, SoaDerive,
+    SoaAttr, SoaAttrs, SoaDerive, SoaDeriveMask,
 };
 use proc_macro2::TokenStream;
 use quote::{format_ident, quote, ToTokens, TokenStreamExt};
-use syn::{punctuated::Punctuated, token::Comma, Field, Ident, Index, LitInt, Visibility};
+use syn::{
+    parse_quote, punctuated::Punctuated, token::Comma, Field, GenericParam, Generics, Ident, Index,
+    LitStr, Visibility,
+};
 
 pub fn fields_struct(
     ident: Ident,
     vis: Visibility,
+    generics: Generics,
     fields: Punctuated<Field, Comma>,
     kind: FieldKind,
     soa_attrs: SoaAttrs,
@@ -21,12 +26,20 @@ pub fn fields_struct(
                 slices: derive_slices,
                 slices_mut: derive_slices_mut,
                 array: derive_array,
+                columns: derive_columns,
+                hash_ref,
             },
         include_array,
+        include_stats,
+        include_pod,
+        drop_forward,
+        align: default_align,
     } = soa_attrs;
 
-    let fields_len = fields.len();
-    let (vis_all, (ty_all, (ident_all, attrs_all))): (Vec<_>, (Vec<_>, (Vec<_>, Vec<_>))) = fields
+    let (field_vis_all, (field_ty_all, (field_ident_all, attrs_all))): (
+        Vec<_>,
+        (Vec<_>, (Vec<_>, Vec<_>)),
+    ) = fields
         .into_iter()
         .enumerate()
         .map(|(i, field)| {
@@ -43,77 +56,358 @@ pub fn fields_struct(
         })
         .unzip();
 
-    let align_all: Result<Vec<_>, syn::Error> = attrs_all
-        .into_iter()
-        .map(|attrs| {
-            for attr in attrs {
-                if attr.path().is_ident("align") {
-                    let align_literal: LitInt = attr.parse_args()?;
-                    let align: usize = align_literal.base10_parse()?;
-                    if !align.is_power_of_two() {
-                        return Err(syn::Error::new_spanned(
-                            align_literal,
-                            "align should be a power of two",
-                        ));
+    let mut field_align_all = Vec::with_capacity(attrs_all.len());
+    let mut field_skip_all = Vec::with_capacity(attrs_all.len());
+    let mut field_flatten_all = Vec::with_capacity(attrs_all.len());
+    let mut field_soa_attr_all = Vec::with_capacity(attrs_all.len());
+    let mut field_getter_all = Vec::with_capacity(attrs_all.len());
+    let mut field_phantom_all = Vec::with_capacity(attrs_all.len());
+    for (attrs, ty) in attrs_all.into_iter().zip(&field_ty_all) {
+        let mut align = None;
+        let mut skip = false;
+        let mut flatten = None;
+        let mut soa_attrs = vec![];
+        let mut getter = None;
+        for attr in attrs {
+            if attr.path().is_ident("align") {
+                align = Some(parse_align(&attr.parse_args()?)?);
+            } else if attr.path().is_ident("soa_skip") {
+                skip = true;
+            } else if attr.path().is_ident("soa_flatten") {
+                flatten = Some(attr);
+            } else if attr.path().is_ident("soa_attr") {
+                soa_attrs.push(SoaAttr::parse(&attr)?);
+            } else if attr.path().is_ident("soa") {
+                attr.parse_nested_meta(|meta| {
+                    if meta.path.is_ident("getter") {
+                        let name: LitStr = meta.value()?.parse()?;
+                        let ident = name.parse::<Ident>().map_err(|_| {
+                            syn::Error::new_spanned(&name, "getter should be a valid identifier")
+                        })?;
+                        getter = Some(ident);
+                        Ok(())
+                    } else {
+                        Err(meta.error("expected `getter`"))
                     }
-                    return Ok(Some(align));
-                }
+                })?;
             }
-            Ok(None)
-        })
-        .collect();
+        }
+        if let Some(attr) = &flatten {
+            if skip {
+                return Err(syn::Error::new_spanned(
+                    attr,
+                    "soa_flatten cannot be combined with soa_skip",
+                ));
+            }
+            if include_array {
+                return Err(syn::Error::new_spanned(
+                    attr,
+                    "soa_flatten is not supported together with soa_array",
+                ));
+            }
+            if include_stats {
+                return Err(syn::Error::new_spanned(
+                    attr,
+                    "soa_flatten is not supported together with soa_stats",
+                ));
+            }
+            if include_pod {
+                return Err(syn::Error::new_spanned(
+                    attr,
+                    "soa_flatten is not supported together with soa_pod",
+                ));
+            }
+        }
+        let phantom = !skip && flatten.is_none() && align.is_none() && is_phantom(ty);
+        // The struct-level alignment only applies to fields that are allocated
+        if align.is_none() && !skip && !phantom {
+            align.clone_from(&default_align);
+        }
+        field_phantom_all.push(phantom);
+        field_align_all.push(align);
+        field_skip_all.push(skip);
+        field_flatten_all.push(flatten.is_some());
+        if let (Some(getter), true) = (&getter, skip) {
+            return Err(syn::Error::new_spanned(
+                getter,
+                "soa_skip fields do not have getters to rename",
+            ));
+        }
+        field_soa_attr_all.push(soa_attrs);
+        field_getter_all.push(getter);
+    }
 
-    let align_all = align_all?;
+    // Fields that get their own column. Skipped fields are grouped into a
+    // single additional column after these. Flattened fields are columns
+    // holding the raw type of the nested struct, which in turn holds one array
+    // per field of that struct. PhantomData columns are zero-sized, so they are
+    // left out of the allocation and their pointers are always dangling.
+    let mut vis_all = vec![];
+    let mut ty_all = vec![];
+    let mut ident_all = vec![];
+    let mut align_all = vec![];
+    let mut flatten_all = vec![];
+    let mut phantom_all = vec![];
+    let mut soa_attr_all = vec![];
+    let mut getter_all = vec![];
+    let mut skipped_vis_all = vec![];
+    let mut skipped_ty_all = vec![];
+    let mut skipped_ident_all = vec![];
+    for (i, &skip) in field_skip_all.iter().enumerate() {
+        if skip {
+            skipped_vis_all.push(field_vis_all[i].clone());
+            skipped_ty_all.push(field_ty_all[i].clone());
+            skipped_ident_all.push(field_ident_all[i].clone());
+        } else {
+            vis_all.push(field_vis_all[i].clone());
+            ty_all.push(field_ty_all[i].clone());
+            ident_all.push(field_ident_all[i].clone());
+            align_all.push(field_align_all[i].clone());
+            flatten_all.push(field_flatten_all[i]);
+            phantom_all.push(field_phantom_all[i]);
+            soa_attr_all.push(field_soa_attr_all[i].clone());
+            getter_all.push(field_getter_all[i].clone());
+        }
+    }
+
+    let slice_vis_all = vis_all.clone();
+    let slice_ty_all = ty_all.clone();
+    let slice_ident_all = ident_all.clone();
+    let slice_flatten_all = flatten_all.clone();
+    let slice_soa_attr_all = soa_attr_all.clone();
+
+    let skipped = format_ident!("{ident}SoaSkipped");
+    let skipped_column = FieldIdent::Named(format_ident!("soa_skipped"));
+    let has_skipped = !skipped_ident_all.is_empty();
+    let has_generics = !generics.params.is_empty();
+    if has_skipped {
+        if has_generics {
+            return Err(syn::Error::new_spanned(
+                generics,
+                "soa_skip is not supported for generic structs",
+            ));
+        }
+        if kind == FieldKind::Unnamed {
+            return Err(syn::Error::new_spanned(
+                ident,
+                "soa_skip is only supported for structs with named fields",
+            ));
+        }
+        if include_array {
+            return Err(syn::Error::new_spanned(
+                ident,
+                "soa_skip is not supported together with soa_array",
+            ));
+        }
+        if include_pod {
+            return Err(syn::Error::new_spanned(
+                ident,
+                "soa_skip is not supported together with soa_pod",
+            ));
+        }
+        vis_all.push(vis.clone());
+        ty_all.push(syn::parse_quote!(#skipped));
+        ident_all.push(skipped_column.clone());
+        align_all.push(None);
+        flatten_all.push(false);
+        phantom_all.push(false);
+        soa_attr_all.push(vec![]);
+    }
 
-    let ident_rev: Vec<_> = ident_all.iter().cloned().rev().collect();
+    // At least one column has to be allocated to give the raw type a pointer
+    // to the allocation.
+    if phantom_all.iter().all(|&phantom| phantom) {
+        phantom_all.fill(false);
+        field_phantom_all.fill(false);
+    }
 
-    let (_vis_head, ident_head, ty_head) = match (
-        vis_all.first().cloned(),
-        ty_all.first().cloned(),
-        ident_all.first().cloned(),
-    ) {
-        (Some(vis), Some(ty), Some(ident)) => (vis, ident, ty),
-        _ => {
-            let zst_kind = match kind {
-                FieldKind::Named => ZstKind::Empty,
-                FieldKind::Unnamed => ZstKind::EmptyTuple,
-            };
-            return Ok(zst_struct(ident, vis, zst_kind));
+    // The array type is meant for `const` items, which are copied wherever
+    // they are used, so changes made through interior mutability would be
+    // lost.
+    let interior_mut_ty = field_ty_all.iter().find(|ty| has_interior_mutability(ty));
+    if let (true, Some(ty)) = (include_array, interior_mut_ty) {
+        return Err(syn::Error::new_spanned(
+            ty,
+            "soa_array is not supported for fields with interior mutability, \
+            since changes to a `const` array would not persist",
+        ));
+    }
+
+    if ident_all.is_empty() {
+        if has_generics {
+            return Err(syn::Error::new_spanned(
+                generics,
+                "generic parameters are not supported for structs without fields",
+            ));
         }
-    };
+        let zst_kind = match kind {
+            FieldKind::Named => ZstKind::Empty,
+            FieldKind::Unnamed => ZstKind::EmptyTuple,
+        };
+        return Ok(zst_struct(ident, vis, zst_kind, drop_forward));
+    }
 
-    let _vis_tail: Vec<_> = vis_all.iter().skip(1).cloned().collect();
-    let ty_tail: Vec<_> = ty_all.iter().skip(1).cloned().collect();
-    let ident_tail: Vec<_> = ident_all.iter().skip(1).cloned().collect();
+    // The columns that are placed in the allocation. The first one starts at
+    // the allocation pointer and the rest are placed at the computed offsets.
+    let layout_all: Vec<_> = (0..ident_all.len()).filter(|&i| !phantom_all[i]).collect();
+    let ident_head = &ident_all[layout_all[0]];
+    let flatten_head = flatten_all[layout_all[0]];
 
     let deref = format_ident!("{ident}Deref");
     let item_ref = format_ident!("{ident}Ref");
     let item_ref_mut = format_ident!("{ident}RefMut");
     let slices = format_ident!("{ident}Slices");
     let slices_mut = format_ident!("{ident}SlicesMut");
+    let slices_uninit = format_ident!("{ident}SlicesUninit");
     let array = format_ident!("{ident}Array");
+    let columns = format_ident!("{ident}Columns");
+    let field_stats = format_ident!("{ident}FieldStats");
     let raw = format_ident!("{ident}SoaRaw");
+    let inline = format_ident!("{ident}SoaInline");
+
+    // Generated types carry the generics of the original struct, plus a
+    // lifetime for the reference types and a length for the array types.
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let where_preds: Vec<_> = where_clause
+        .iter()
+        .flat_map(|clause| &clause.predicates)
+        .collect();
+    let item_ty = quote! { #ident #ty_generics };
+    let generics_a = generics_with(&generics, parse_quote!('a));
+    let (impl_generics_a, ty_generics_a, _) = generics_a.split_for_impl();
+    let generics_n = generics_with(&generics, parse_quote!(const N: usize));
+    let (impl_generics_n, ty_generics_n, _) = generics_n.split_for_impl();
 
     let mut out = TokenStream::new();
 
-    let (slice_getters_ref, slice_getters_mut): (Vec<_>, Vec<_>) = ident_all
+    let (slice_getters_ref, slice_getters_mut): (Vec<_>, Vec<_>) = slice_ident_all
         .iter()
-        .map(|ident| match ident {
-            FieldIdent::Named(named) => (named.clone(), format_ident!("{named}_mut")),
-            FieldIdent::Unnamed(unnamed) => {
+        .zip(&getter_all)
+        .map(|(ident, getter)| match (ident, getter) {
+            (_, Some(getter)) => (getter.clone(), format_ident!("{getter}_mut")),
+            (FieldIdent::Named(named), None) => (named.clone(), format_ident!("{named}_mut")),
+            (FieldIdent::Unnamed(unnamed), None) => {
                 (format_ident!("f{unnamed}"), format_ident!("f{unnamed}_mut"))
             }
         })
         .unzip();
 
+    // Getters come with variants named by adding a suffix, and the deref type
+    // has a few methods of its own, so the getter for one field can clash with
+    // a method generated for another, as with fields `data` and `data_rev`. The
+    // fixed names come first so that the error points at the field.
+    let has_flatten = slice_flatten_all.contains(&true);
+    let mut deref_methods: Vec<Ident> = ["columns_debug", "field_ptrs"]
+        .into_iter()
+        .chain((!has_flatten).then_some("zip_mut"))
+        .chain(include_stats.then_some("debug_field_stats"))
+        .chain(cfg!(feature = "bytemuck").then_some("for_each_field_bytes"))
+        .chain((kind == FieldKind::Unnamed).then_some("as_field_slices"))
+        .chain((kind == FieldKind::Unnamed).then_some("as_field_slices_mut"))
+        .map(|name| format_ident!("{name}"))
+        .collect();
+    for (getter, &flatten) in slice_getters_ref.iter().zip(&slice_flatten_all) {
+        let mut suffixes = vec!["", "_mut"];
+        if !flatten {
+            suffixes.extend(["_rev", "_ptr", "_ptr_mut"]);
+            if cfg!(feature = "bytemuck") {
+                suffixes.extend(["_bytes", "_bytes_mut"]);
+            }
+        }
+        for suffix in suffixes {
+            let method = format_ident!("{getter}{suffix}", span = getter.span());
+            if deref_methods.contains(&method) {
+                return Err(syn::Error::new_spanned(
+                    &method,
+                    format!(
+                        "`{method}` is already defined, \
+                        rename the getter with `#[soa(getter = \"...\")]`"
+                    ),
+                ));
+            }
+            deref_methods.push(method);
+        }
+    }
+
+    let mut slice_getter_bodies = Vec::with_capacity(slice_ident_all.len());
+    let mut slice_ref_ty_all = Vec::with_capacity(slice_ident_all.len());
+    let mut slice_mut_ty_all = Vec::with_capacity(slice_ident_all.len());
+    for (i, column) in slice_ident_all.iter().enumerate() {
+        let ty = &slice_ty_all[i];
+        let (ref_ty, mut_ty, body_ref, body_mut) = if slice_flatten_all[i] {
+            (
+                quote! { <#ty as ::soa_rs::Soars>::Slices<'_> },
+                quote! { <#ty as ::soa_rs::Soars>::SlicesMut<'_> },
+                quote! {
+                    unsafe { ::soa_rs::SoaRaw::slices(self.0.raw().#column, self.0.len()) }
+                },
+                quote! {
+                    unsafe { ::soa_rs::SoaRaw::slices_mut(self.0.raw().#column, self.0.len()) }
+                },
+            )
+        } else {
+            (
+                quote! { &[#ty] },
+                quote! { &mut [#ty] },
+                quote! {
+                    let ptr = self.0.raw().#column.as_ptr();
+                    let len = self.0.len();
+                    unsafe {
+                        ::std::slice::from_raw_parts(ptr, len)
+                    }
+                },
+                quote! {
+                    let ptr = self.0.raw().#column.as_ptr();
+                    let len = self.0.len();
+                    unsafe {
+                        ::std::slice::from_raw_parts_mut(ptr, len)
+                    }
+                },
+            )
+        };
+
+        let vis = &slice_vis_all[i];
+        let getter_ref = &slice_getters_ref[i];
+        let getter_mut = &slice_getters_mut[i];
+        slice_getter_bodies.push(quote! {
+            #vis fn #getter_ref(&self) -> #ref_ty {
+                #body_ref
+            }
+
+            #vis fn #getter_mut(&mut self) -> #mut_ty {
+                #body_mut
+            }
+        });
+        if !slice_flatten_all[i] {
+            let getter_rev = format_ident!("{getter_ref}_rev");
+            let getter_ptr = format_ident!("{getter_ref}_ptr");
+            let getter_ptr_mut = format_ident!("{getter_ref}_ptr_mut");
+            slice_getter_bodies.push(quote! {
+                #vis fn #getter_rev(&self) -> ::std::iter::Rev<::std::slice::Iter<'_, #ty>> {
+                    self.#getter_ref().iter().rev()
+                }
+
+                #vis fn #getter_ptr(&self) -> ::std::ptr::NonNull<#ty> {
+                    self.0.raw().#column
+                }
+
+                #vis fn #getter_ptr_mut(&mut self) -> ::std::ptr::NonNull<#ty> {
+                    self.0.raw().#column
+                }
+            });
+        }
+        slice_ref_ty_all.push(ref_ty);
+        slice_mut_ty_all.push(mut_ty);
+    }
+
     out.append_all(quote! {
         #[automatically_derived]
         #[repr(transparent)]
-        #vis struct #deref(::soa_rs::Slice<#ident>);
+        #vis struct #deref #generics (::soa_rs::Slice<#item_ty>) #where_clause;
 
         #[automatically_derived]
-        impl ::soa_rs::SoaDeref for #deref {
-            type Item = #ident;
+        impl #impl_generics ::soa_rs::SoaDeref for #deref #ty_generics #where_clause {
+            type Item = #item_ty;
 
             fn from_slice(slice: &::soa_rs::Slice<Self::Item>) -> &Self {
                 unsafe { ::std::mem::transmute(slice) }
@@ -125,108 +419,479 @@ pub fn fields_struct(
         }
 
         #[automatically_derived]
-        impl #deref {
-            #(
-            #vis_all fn #slice_getters_ref(&self) -> &[#ty_all] {
-                let ptr = self.0.raw().#ident_all.as_ptr();
-                let len = self.0.len();
-                unsafe {
-                    ::std::slice::from_raw_parts(ptr, len)
+        impl #impl_generics #deref #ty_generics #where_clause {
+            #(#slice_getter_bodies)*
+        }
+    });
+
+    if kind == FieldKind::Unnamed {
+        out.append_all(quote! {
+            #[automatically_derived]
+            impl #impl_generics #deref #ty_generics #where_clause {
+                #vis fn as_field_slices(&self) -> (#(#slice_ref_ty_all,)*) {
+                    let slices = unsafe { ::soa_rs::SoaRaw::slices(self.0.raw(), self.0.len()) };
+                    (#(slices.#slice_ident_all,)*)
+                }
+
+                #vis fn as_field_slices_mut(&mut self) -> (#(#slice_mut_ty_all,)*) {
+                    let slices = unsafe {
+                        ::soa_rs::SoaRaw::slices_mut(self.0.raw(), self.0.len())
+                    };
+                    (#(slices.#slice_ident_all,)*)
                 }
             }
+        });
+    }
 
-            #vis_all fn #slice_getters_mut(&mut self) -> &mut [#ty_all] {
-                let ptr = self.0.raw().#ident_all.as_ptr();
-                let len = self.0.len();
-                unsafe {
-                    ::std::slice::from_raw_parts_mut(ptr, len)
+    // Zipped from the field slices rather than going through the raw type so
+    // that each step is a plain slice iterator the compiler can vectorize.
+    // Flattened columns have no slice to iterate over.
+    let (zip_ident_all, zip_ty_all): (Vec<_>, Vec<_>) = slice_ident_all
+        .iter()
+        .zip(&slice_ty_all)
+        .zip(&phantom_all)
+        .filter(|(_, &phantom)| !phantom)
+        .map(|((ident, ty), _)| (ident, ty))
+        .unzip();
+    let zip_binding_all: Vec<_> = (0..zip_ident_all.len())
+        .map(|i| format_ident!("__soa_{i}"))
+        .collect();
+    let zip = zip_ident_all
+        .iter()
+        .zip(&zip_binding_all)
+        .map(|(ident, binding)| (quote! { slices.#ident.iter_mut() }, quote! { #binding }))
+        .reduce(|(iter, pat), (next_iter, next_pat)| {
+            (
+                quote! { #iter.zip(#next_iter) },
+                quote! { (#pat, #next_pat) },
+            )
+        });
+    if let (false, Some((zip_iter, zip_pat))) = (slice_flatten_all.contains(&true), zip) {
+        out.append_all(quote! {
+            #[automatically_derived]
+            impl #impl_generics #deref #ty_generics #where_clause {
+                #vis fn zip_mut(&mut self) -> impl ::std::iter::Iterator<Item = (#(&mut #zip_ty_all,)*)> + '_ {
+                    let slices = unsafe {
+                        ::soa_rs::SoaRaw::slices_mut(self.0.raw(), self.0.len())
+                    };
+                    #zip_iter.map(|#zip_pat| (#(#zip_binding_all,)*))
                 }
             }
-            )*
+        });
+    }
+
+    let (debug_name_all, (debug_getter_all, debug_bound_all)): (Vec<_>, (Vec<_>, Vec<_>)) =
+        slice_ident_all
+            .iter()
+            .zip(&slice_getters_ref)
+            .zip(slice_ty_all.iter().zip(&slice_flatten_all))
+            .zip(&phantom_all)
+            .filter(|(_, &phantom)| !phantom)
+            .map(|(((ident, getter), (ty, &flatten)), _)| {
+                let bound = if flatten {
+                    quote! { <#ty as ::soa_rs::Soars>::Slices<'__soa>: ::std::fmt::Debug }
+                } else {
+                    quote! { #ty: ::std::fmt::Debug }
+                };
+                (ident.to_string(), (getter, bound))
+            })
+            .unzip();
+    let debug_body = match kind {
+        FieldKind::Named => quote! {
+            f.debug_struct(stringify!(#ident))
+                #(.field(#debug_name_all, &self.#debug_getter_all()))*
+                .finish()
+        },
+        FieldKind::Unnamed => quote! {
+            f.debug_tuple(stringify!(#ident))
+                #(.field(&self.#debug_getter_all()))*
+                .finish()
+        },
+    };
+    out.append_all(quote! {
+        #[automatically_derived]
+        impl #impl_generics #deref #ty_generics #where_clause {
+            #vis fn columns_debug(&self) -> impl ::std::fmt::Debug + '_
+            where
+                #(for<'__soa> #debug_bound_all,)*
+            {
+                ::soa_rs::ColumnsDebug(move |f: &mut ::std::fmt::Formatter<'_>| {
+                    #debug_body
+                })
+            }
         }
     });
 
-    let define = |type_mapper: &dyn Fn(&syn::Type) -> TokenStream| {
-        let ty_mapped = ty_all.iter().map(type_mapper);
-        match kind {
-            FieldKind::Named => quote! {
-                { #(#[automatically_derived] #vis_all #ident_all: #ty_mapped),* }
-            },
-            FieldKind::Unnamed => quote! {
-                ( #(#[automatically_derived] #vis_all #ty_mapped),* );
-            },
-        }
+    // The target selects which field-level soa_attr attributes apply to the
+    // type being defined. Fields marked as phantom are wrapped in PhantomData
+    // so that they carry the same lifetimes and variance without storing
+    // anything.
+    type Target = fn(&SoaDeriveMask) -> bool;
+    let define_with =
+        |vis_all: &[Visibility],
+         ident_all: &[FieldIdent],
+         ty_all: &[syn::Type],
+         flatten_all: &[bool],
+         phantom_all: &[bool],
+         soa_attr_all: &[Vec<SoaAttr>],
+         target: Option<Target>,
+         type_mapper: &dyn Fn(&syn::Type, bool) -> TokenStream| {
+            let ty_mapped = ty_all.iter().zip(flatten_all).zip(phantom_all).map(
+                |((ty, &flatten), &phantom)| {
+                    let mapped = type_mapper(ty, flatten);
+                    if phantom {
+                        quote! { ::std::marker::PhantomData<#mapped> }
+                    } else {
+                        mapped
+                    }
+                },
+            );
+            let attrs_all = soa_attr_all.iter().map(|attrs| match target {
+                Some(target) => SoaAttr::select(attrs, target),
+                None => TokenStream::new(),
+            });
+            match kind {
+                FieldKind::Named => quote! {
+                    #where_clause {
+                        #(#[automatically_derived] #attrs_all #vis_all #ident_all: #ty_mapped),*
+                    }
+                },
+                FieldKind::Unnamed => quote! {
+                    ( #(#[automatically_derived] #attrs_all #vis_all #ty_mapped),* ) #where_clause;
+                },
+            }
+        };
+    let no_phantom = vec![false; ident_all.len()];
+    let define = |target: Option<Target>, type_mapper: &dyn Fn(&syn::Type, bool) -> TokenStream| {
+        define_with(
+            &vis_all,
+            &ident_all,
+            &ty_all,
+            &flatten_all,
+            &no_phantom,
+            &soa_attr_all,
+            target,
+            type_mapper,
+        )
+    };
+    let define_fields = |target: Target, type_mapper: &dyn Fn(&syn::Type, bool) -> TokenStream| {
+        define_with(
+            &field_vis_all,
+            &field_ident_all,
+            &field_ty_all,
+            &field_flatten_all,
+            &field_phantom_all,
+            &field_soa_attr_all,
+            Some(target),
+            type_mapper,
+        )
     };
+    let define_slices =
+        |target: Option<Target>, type_mapper: &dyn Fn(&syn::Type, bool) -> TokenStream| {
+            define_with(
+                &slice_vis_all,
+                &slice_ident_all,
+                &slice_ty_all,
+                &slice_flatten_all,
+                &no_phantom[..slice_ident_all.len()],
+                &slice_soa_attr_all,
+                target,
+                type_mapper,
+            )
+        };
+
+    if has_skipped {
+        out.append_all(quote! {
+            #[automatically_derived]
+            #[doc(hidden)]
+            #vis struct #skipped {
+                #(#skipped_vis_all #skipped_ident_all: #skipped_ty_all,)*
+            }
+        });
+    }
 
-    let item_ref_def = define(&|ty| quote! { &'a #ty });
+    // Rebuilt field by field rather than copied since nesting the associated
+    // types of flattened fields makes the reference types invariant.
+    let ref_as_ref_all: Vec<_> = field_ident_all
+        .iter()
+        .zip(&field_flatten_all)
+        .zip(&field_phantom_all)
+        .map(|((field, &flatten), &phantom)| {
+            if phantom {
+                quote! { ::std::marker::PhantomData }
+            } else if flatten {
+                quote! { ::soa_rs::AsSoaRef::as_soa_ref(&self.#field) }
+            } else {
+                quote! { self.#field }
+            }
+        })
+        .collect();
+    let item_ref_def = define_fields(|mask| mask.r#ref, &|ty, flatten| {
+        if flatten {
+            quote! { <#ty as ::soa_rs::Soars>::Ref<'a> }
+        } else {
+            quote! { &'a #ty }
+        }
+    });
     out.append_all(quote! {
         #derive_ref
         #[automatically_derived]
-        #vis struct #item_ref<'a> #item_ref_def
+        #vis struct #item_ref #generics_a #item_ref_def
 
         #[automatically_derived]
-        impl<'a> ::soa_rs::AsSoaRef for #item_ref<'a> {
-            type Item = #ident;
+        impl #impl_generics_a ::soa_rs::AsSoaRef for #item_ref #ty_generics_a #where_clause {
+            type Item = #item_ty;
 
             fn as_soa_ref(&self) -> <Self::Item as Soars>::Ref<'_> {
-                *self
+                #item_ref {
+                    #(
+                        #field_ident_all: #ref_as_ref_all,
+                    )*
+                }
             }
         }
     });
 
-    let item_ref_mut_def = define(&|ty| quote! { &'a mut #ty });
+    // Stands in for a derived `Hash` and hashes like one on the struct itself,
+    // but only requires the fields to be `Hash` rather than the type
+    // parameters.
+    if hash_ref {
+        let hash_bound_all: Vec<_> = field_ty_all
+            .iter()
+            .zip(&field_flatten_all)
+            .map(|(ty, &flatten)| {
+                if flatten {
+                    quote! { for<'__soa> <#ty as ::soa_rs::Soars>::Ref<'__soa>: ::std::hash::Hash }
+                } else {
+                    quote! { for<'__soa> #ty: ::std::hash::Hash }
+                }
+            })
+            .collect();
+        out.append_all(quote! {
+            #[automatically_derived]
+            impl #impl_generics_a ::std::hash::Hash for #item_ref #ty_generics_a
+            where
+                #(#where_preds,)*
+                #(#hash_bound_all,)*
+            {
+                fn hash<__H: ::std::hash::Hasher>(&self, state: &mut __H) {
+                    #(
+                        ::std::hash::Hash::hash(&self.#field_ident_all, state);
+                    )*
+                }
+            }
+        });
+    }
+
+    let item_ref_mut_def = define_fields(|mask| mask.ref_mut, &|ty, flatten| {
+        if flatten {
+            quote! { <#ty as ::soa_rs::Soars>::RefMut<'a> }
+        } else {
+            quote! { &'a mut #ty }
+        }
+    });
     out.append_all(quote! {
         #derive_ref_mut
         #[automatically_derived]
-        #vis struct #item_ref_mut<'a> #item_ref_mut_def
+        #vis struct #item_ref_mut #generics_a #item_ref_mut_def
 
         #[automatically_derived]
-        impl<'a> ::soa_rs::AsSoaRef for #item_ref_mut<'a> {
-            type Item = #ident;
+        impl #impl_generics_a ::soa_rs::AsSoaRef for #item_ref_mut #ty_generics_a #where_clause {
+            type Item = #item_ty;
 
             fn as_soa_ref(&self) -> <Self::Item as Soars>::Ref<'_> {
                 #item_ref {
                     #(
-                        #ident_all: self.#ident_all,
+                        #field_ident_all: #ref_as_ref_all,
                     )*
                 }
             }
         }
     });
 
-    let slices_def = define(&|ty| quote! { &'a [#ty] });
+    let slices_def = define_slices(Some(|mask| mask.slice), &|ty, flatten| {
+        if flatten {
+            quote! { <#ty as ::soa_rs::Soars>::Slices<'a> }
+        } else {
+            quote! { &'a [#ty] }
+        }
+    });
     out.append_all(quote! {
         #derive_slices
         #[automatically_derived]
-        #vis struct #slices<'a> #slices_def
+        #vis struct #slices #generics_a #slices_def
     });
 
-    let slices_mut_def = define(&|ty| quote! { &'a mut [#ty] });
+    if has_generics {
+        for copy_ty in [&item_ref, &slices] {
+            out.append_all(quote! {
+                #[automatically_derived]
+                impl #impl_generics_a ::std::clone::Clone for #copy_ty #ty_generics_a #where_clause {
+                    fn clone(&self) -> Self {
+                        *self
+                    }
+                }
+
+                #[automatically_derived]
+                impl #impl_generics_a ::std::marker::Copy for #copy_ty #ty_generics_a #where_clause {}
+            });
+        }
+    }
+
+    let slices_mut_def = define_slices(Some(|mask| mask.slice_mut), &|ty, flatten| {
+        if flatten {
+            quote! { <#ty as ::soa_rs::Soars>::SlicesMut<'a> }
+        } else {
+            quote! { &'a mut [#ty] }
+        }
+    });
     out.append_all(quote! {
         #derive_slices_mut
         #[automatically_derived]
-        #vis struct #slices_mut<'a> #slices_mut_def
+        #vis struct #slices_mut #generics_a #slices_mut_def
+    });
+
+    // Unlike the other slice types, this includes the skipped column since
+    // every column must be initialized before the length is increased.
+    let slices_uninit_def = define(None, &|ty, flatten| {
+        if flatten {
+            quote! { <#ty as ::soa_rs::Soars>::SlicesUninit<'a> }
+        } else {
+            quote! { &'a mut [::std::mem::MaybeUninit<#ty>] }
+        }
+    });
+    out.append_all(quote! {
+        #[automatically_derived]
+        #vis struct #slices_uninit #generics_a #slices_uninit_def
+    });
+
+    let columns_def = define_fields(|mask| mask.columns, &|ty, flatten| {
+        if flatten {
+            quote! { <#ty as ::soa_rs::SoaColumns>::Columns }
+        } else {
+            quote! { ::std::vec::Vec<#ty> }
+        }
+    });
+    // PhantomData fields have no column to iterate over and are filled in
+    // directly.
+    let (column_field_all, (column_ty_all, column_flatten_all)): (Vec<_>, (Vec<_>, Vec<_>)) =
+        field_ident_all
+            .iter()
+            .zip(field_ty_all.iter().zip(&field_flatten_all))
+            .zip(&field_phantom_all)
+            .filter(|(_, &phantom)| !phantom)
+            .map(|((field, (ty, &flatten)), _)| (field, (ty, flatten)))
+            .unzip();
+    let phantom_field_all: Vec<_> = field_ident_all
+        .iter()
+        .zip(&field_phantom_all)
+        .filter(|(_, &phantom)| phantom)
+        .map(|(field, _)| field)
+        .collect();
+    let column_iter_all = column_field_all
+        .iter()
+        .zip(&column_ty_all)
+        .zip(&column_flatten_all)
+        .map(|((field, ty), &flatten)| {
+            if flatten {
+                quote! {
+                    ::std::iter::IntoIterator::into_iter(
+                        <#ty as ::soa_rs::SoaColumns>::from_columns(columns.#field)?
+                    )
+                }
+            } else {
+                quote! { ::std::iter::IntoIterator::into_iter(columns.#field) }
+            }
+        });
+    let column_new_all = column_ty_all
+        .iter()
+        .zip(&column_flatten_all)
+        .map(|(ty, &flatten)| {
+            if flatten {
+                quote! { ::soa_rs::Soa::<#ty>::with_capacity(len) }
+            } else {
+                quote! { ::std::vec::Vec::<#ty>::with_capacity(len) }
+            }
+        });
+    let column_finish_all = column_field_all
+        .iter()
+        .zip(&column_ty_all)
+        .zip(&column_flatten_all)
+        .enumerate()
+        .map(|(i, ((field, ty), &flatten))| {
+            let index = Index::from(i);
+            if flatten {
+                quote! { #field: <#ty as ::soa_rs::SoaColumns>::into_columns(columns.#index) }
+            } else {
+                quote! { #field: columns.#index }
+            }
+        });
+    let column_indices: Vec<_> = (0..column_field_all.len()).map(Index::from).collect();
+    let column_bindings: Vec<_> = (0..column_field_all.len())
+        .map(|i| format_ident!("__soa_{i}"))
+        .collect();
+    out.append_all(quote! {
+        #derive_columns
+        #[automatically_derived]
+        #vis struct #columns #generics #columns_def
+
+        #[automatically_derived]
+        impl #impl_generics ::soa_rs::SoaColumns for #item_ty #where_clause {
+            type Columns = #columns #ty_generics;
+
+            fn from_columns(
+                columns: #columns #ty_generics,
+            ) -> ::std::result::Result<::soa_rs::Soa<Self>, ::soa_rs::ColumnLengthError> {
+                let mut columns = (#(#column_iter_all,)*);
+                let len = ::std::iter::ExactSizeIterator::len(&columns.0);
+                if #(::std::iter::ExactSizeIterator::len(&columns.#column_indices) != len)||* {
+                    return Err(::soa_rs::ColumnLengthError);
+                }
+
+                let mut out = ::soa_rs::Soa::<Self>::with_capacity(len);
+                while let (#(Some(#column_bindings),)*) = (#(columns.#column_indices.next(),)*) {
+                    out.push(#ident {
+                        #(#column_field_all: #column_bindings,)*
+                        #(#phantom_field_all: ::std::marker::PhantomData,)*
+                    });
+                }
+                Ok(out)
+            }
+
+            fn into_columns(soa: ::soa_rs::Soa<Self>) -> #columns #ty_generics {
+                let len = soa.len();
+                let mut columns = (#(#column_new_all,)*);
+                for #ident { #(#column_field_all: #column_bindings,)* .. } in soa {
+                    #(columns.#column_indices.push(#column_bindings);)*
+                }
+                #columns {
+                    #(#column_finish_all,)*
+                    #(#phantom_field_all: ::std::marker::PhantomData,)*
+                }
+            }
+        }
     });
 
     if include_array {
-        let array_def = define(&|ty| quote! { [#ty; N] });
-        let uninit_def = define(&|ty| quote! { [::std::mem::MaybeUninit<#ty>; K] });
+        let array_def = define(Some(|mask| mask.array), &|ty, _| quote! { [#ty; N] });
+        let uninit_def = define(None, &|ty, _| quote! { [::std::mem::MaybeUninit<#ty>; K] });
+        let generics_k = generics_with(&generics, parse_quote!(const K: usize));
         out.append_all(quote! {
             #derive_array
             #[automatically_derived]
-            #vis struct #array<const N: usize> #array_def
+            #vis struct #array #generics_n #array_def
 
             #[automatically_derived]
-            impl<const N: usize> #array<N> {
-                #vis const fn from_array(array: [#ident; N]) -> Self {
+            impl #impl_generics_n #array #ty_generics_n #where_clause {
+                #vis const fn from_array(array: [#item_ty; N]) -> Self {
                     let array = ::std::mem::ManuallyDrop::new(array);
-                    let array = ::std::ptr::from_ref::<::std::mem::ManuallyDrop<[#ident; N]>>(&array);
-                    let array = array.cast::<[#ident; N]>();
+                    let array = ::std::ptr::from_ref::<::std::mem::ManuallyDrop<[#item_ty; N]>>(&array);
+                    let array = array.cast::<[#item_ty; N]>();
                     let array = unsafe { &*array };
 
-                    struct Uninit<const K: usize> #uninit_def;
+                    struct Uninit #generics_k #uninit_def
 
-                    let mut uninit: Uninit<N> = Uninit {
+                    let mut uninit: Uninit #ty_generics_n = Uninit {
                         #(
                         // https://doc.rust-lang.org/std/mem/union.MaybeUninit.html#initializing-an-array-element-by-element
                         //
@@ -261,8 +926,8 @@ pub fn fields_struct(
             }
 
             #[automatically_derived]
-            impl<const N: usize> ::soa_rs::AsSlice for #array<N> {
-                type Item = #ident;
+            impl #impl_generics_n ::soa_rs::AsSlice for #array #ty_generics_n #where_clause {
+                type Item = #item_ty;
 
                 fn as_slice(&self) -> ::soa_rs::SliceRef<'_, Self::Item> {
                     let raw = #raw {
@@ -279,7 +944,7 @@ pub fn fields_struct(
             }
 
             #[automatically_derived]
-            impl<const N: usize> ::soa_rs::AsMutSlice for #array<N> {
+            impl #impl_generics_n ::soa_rs::AsMutSlice for #array #ty_generics_n #where_clause {
                 fn as_mut_slice(&mut self) -> ::soa_rs::SliceMut<'_, Self::Item> {
                     let raw = #raw {
                         #(
@@ -296,9 +961,354 @@ pub fn fields_struct(
         });
     }
 
-    let indices = std::iter::repeat(()).enumerate().map(|(i, ())| i);
-    let offsets_len = fields_len - 1;
-    let raw_body = define(&|ty| quote! { ::std::ptr::NonNull<#ty> });
+    if include_stats {
+        let field_stats_def = define_slices(None, &|ty, _| quote! { ::soa_rs::FieldStats<#ty> });
+        out.append_all(quote! {
+            #[derive(Debug, Clone, Copy, PartialEq)]
+            #[automatically_derived]
+            #vis struct #field_stats #generics #field_stats_def
+
+            #[automatically_derived]
+            impl #impl_generics #deref #ty_generics #where_clause {
+                #vis fn debug_field_stats(&self) -> #field_stats #ty_generics
+                where
+                    #(#slice_ty_all: ::soa_rs::Numeric,)*
+                {
+                    #field_stats {
+                        #(
+                            #slice_ident_all: ::soa_rs::FieldStats::from_slice(self.#slice_getters_ref()),
+                        )*
+                    }
+                }
+            }
+        });
+    }
+
+    let (bytes_ty_all, (bytes_ident_all, bytes_getters_all)): (Vec<_>, (Vec<_>, Vec<_>)) =
+        slice_ty_all
+            .iter()
+            .zip(&slice_ident_all)
+            .zip(&slice_getters_ref)
+            .zip(&slice_flatten_all)
+            .filter(|(_, &flatten)| !flatten)
+            .map(|(((ty, ident), getter), _)| (ty, (ident, getter)))
+            .unzip();
+    if cfg!(feature = "bytemuck") {
+        out.append_all(quote! {
+            #[automatically_derived]
+            impl #impl_generics #deref #ty_generics #where_clause {
+                #vis fn for_each_field_bytes<F>(&self, mut f: F)
+                where
+                    F: FnMut(&'static str, &[u8]),
+                    #(for<'__soa> #bytes_ty_all: ::soa_rs::bytemuck::Pod,)*
+                {
+                    #(
+                        f(
+                            stringify!(#bytes_ident_all),
+                            ::soa_rs::bytemuck::cast_slice(self.#bytes_getters_all()),
+                        );
+                    )*
+                }
+            }
+        });
+
+        let (bytes_vis_all, (bytes_getters_mut_all, (bytes_name_all, bytes_name_mut_all))): (
+            Vec<_>,
+            (Vec<_>, (Vec<_>, Vec<_>)),
+        ) = slice_vis_all
+            .iter()
+            .zip(&slice_getters_ref)
+            .zip(&slice_getters_mut)
+            .zip(&slice_flatten_all)
+            .filter(|(_, &flatten)| !flatten)
+            .map(|(((vis, getter), getter_mut), _)| {
+                (
+                    vis,
+                    (
+                        getter_mut,
+                        (
+                            format_ident!("{getter}_bytes"),
+                            format_ident!("{getter}_bytes_mut"),
+                        ),
+                    ),
+                )
+            })
+            .unzip();
+        out.append_all(quote! {
+            #[automatically_derived]
+            impl #impl_generics #deref #ty_generics #where_clause {
+                #(
+                #bytes_vis_all fn #bytes_name_all(&self) -> &[u8]
+                where
+                    for<'__soa> #bytes_ty_all: ::soa_rs::bytemuck::Pod,
+                {
+                    ::soa_rs::bytemuck::cast_slice(self.#bytes_getters_all())
+                }
+
+                #bytes_vis_all fn #bytes_name_mut_all(&mut self) -> &mut [u8]
+                where
+                    for<'__soa> #bytes_ty_all: ::soa_rs::bytemuck::Pod,
+                {
+                    ::soa_rs::bytemuck::cast_slice_mut(self.#bytes_getters_mut_all())
+                }
+                )*
+            }
+        });
+    }
+
+    if include_pod {
+        out.append_all(quote! {
+            #[automatically_derived]
+            unsafe impl #impl_generics ::soa_rs::SoaPod for #item_ty
+            where
+                #(#where_preds,)*
+                #(#ty_all: ::soa_rs::bytemuck::Pod,)*
+            {
+                fn pod_columns(raw: #raw #ty_generics) -> ::std::vec::Vec<::soa_rs::PodColumn> {
+                    ::std::vec![
+                        #(::soa_rs::PodColumn::new(stringify!(#ident_all), raw.#ident_all),)*
+                    ]
+                }
+            }
+        });
+    }
+
+    let inline_def = define(None, &|ty, flatten| {
+        if flatten {
+            quote! { <#ty as ::soa_rs::Soars>::Inline<N> }
+        } else {
+            quote! { [::std::mem::MaybeUninit<#ty>; N] }
+        }
+    });
+    let (inline_uninit_all, (inline_raw_all, inline_raw_mut_all)): (Vec<_>, (Vec<_>, Vec<_>)) =
+        ident_all
+            .iter()
+            .zip(&flatten_all)
+            .map(|(column, &flatten)| {
+                if flatten {
+                    (
+                        quote! { ::soa_rs::SoaInline::uninit() },
+                        (
+                            quote! { ::soa_rs::SoaInline::raw(&self.#column) },
+                            quote! { ::soa_rs::SoaInline::raw_mut(&mut self.#column) },
+                        ),
+                    )
+                } else {
+                    (
+                        // https://doc.rust-lang.org/std/mem/union.MaybeUninit.html#initializing-an-array-element-by-element
+                        quote! { unsafe { ::std::mem::MaybeUninit::uninit().assume_init() } },
+                        (
+                            quote! {{
+                                let ptr = self.#column.as_ptr().cast_mut().cast();
+                                unsafe { ::std::ptr::NonNull::new_unchecked(ptr) }
+                            }},
+                            quote! {{
+                                let ptr = self.#column.as_mut_ptr().cast();
+                                unsafe { ::std::ptr::NonNull::new_unchecked(ptr) }
+                            }},
+                        ),
+                    )
+                }
+            })
+            .unzip();
+    out.append_all(quote! {
+        #[automatically_derived]
+        #[doc(hidden)]
+        #vis struct #inline #generics_n #inline_def
+
+        #[automatically_derived]
+        unsafe impl #impl_generics_n ::soa_rs::SoaInline for #inline #ty_generics_n #where_clause {
+            type Item = #item_ty;
+
+            #[inline]
+            fn uninit() -> Self {
+                Self {
+                    #(#ident_all: #inline_uninit_all,)*
+                }
+            }
+
+            #[inline]
+            fn raw(&self) -> #raw #ty_generics {
+                #raw {
+                    #(#ident_all: #inline_raw_all,)*
+                }
+            }
+
+            #[inline]
+            fn raw_mut(&mut self) -> #raw #ty_generics {
+                #raw {
+                    #(#ident_all: #inline_raw_mut_all,)*
+                }
+            }
+        }
+    });
+
+    let set_all = ident_all
+        .iter()
+        .zip(&flatten_all)
+        .map(|(column, &flatten)| {
+            if flatten {
+                quote! {
+                    ::soa_rs::SoaRaw::set(self.#column, element.#column);
+                }
+            } else if has_skipped && *column == skipped_column {
+                quote! {
+                    self.#column.as_ptr().write(#skipped {
+                        #(#skipped_ident_all: element.#skipped_ident_all,)*
+                    });
+                }
+            } else {
+                quote! {
+                    self.#column.as_ptr().write(element.#column);
+                }
+            }
+        });
+    let get_skipped = has_skipped.then(|| {
+        quote! {
+            let #skipped_column = self.#skipped_column.as_ptr().read();
+        }
+    });
+    let get_all = field_ident_all
+        .iter()
+        .zip(field_skip_all.iter().zip(&field_flatten_all))
+        .zip(&field_phantom_all)
+        .map(|((field, (&skip, &flatten)), &phantom)| {
+            if phantom {
+                quote! { ::std::marker::PhantomData }
+            } else if skip {
+                quote! { #skipped_column.#field }
+            } else if flatten {
+                quote! { ::soa_rs::SoaRaw::get(self.#field) }
+            } else {
+                quote! { self.#field.as_ptr().read() }
+            }
+        });
+    let get_ref_all = field_ident_all
+        .iter()
+        .zip(field_skip_all.iter().zip(&field_flatten_all))
+        .zip(&field_phantom_all)
+        .map(|((field, (&skip, &flatten)), &phantom)| {
+            if phantom {
+                quote! { ::std::marker::PhantomData }
+            } else if skip {
+                quote! { &(*self.#skipped_column.as_ptr()).#field }
+            } else if flatten {
+                quote! { ::soa_rs::SoaRaw::get_ref(self.#field) }
+            } else {
+                quote! { self.#field.as_ptr().as_ref().unwrap_unchecked() }
+            }
+        });
+    let get_mut_all = field_ident_all
+        .iter()
+        .zip(field_skip_all.iter().zip(&field_flatten_all))
+        .zip(&field_phantom_all)
+        .map(|((field, (&skip, &flatten)), &phantom)| {
+            if phantom {
+                quote! { ::std::marker::PhantomData }
+            } else if skip {
+                quote! { &mut (*self.#skipped_column.as_ptr()).#field }
+            } else if flatten {
+                quote! { ::soa_rs::SoaRaw::get_mut(self.#field) }
+            } else {
+                quote! { self.#field.as_ptr().as_mut().unwrap_unchecked() }
+            }
+        });
+
+    let offsets_len = layout_all.len() - 1;
+    let raw_body = define(None, &|ty, flatten| {
+        if flatten {
+            quote! { <#ty as ::soa_rs::Soars>::Raw }
+        } else {
+            quote! { ::std::ptr::NonNull<#ty> }
+        }
+    });
+
+    // Per-column expressions for the operations of the raw type. Flattened
+    // columns defer to the raw type of the nested struct.
+    let column_expr = |plain: &dyn Fn(&FieldIdent) -> TokenStream,
+                       flattened: &dyn Fn(&FieldIdent) -> TokenStream| {
+        ident_all
+            .iter()
+            .zip(&flatten_all)
+            .map(|(column, &flatten)| {
+                if flatten {
+                    flattened(column)
+                } else {
+                    plain(column)
+                }
+            })
+            .collect::<Vec<_>>()
+    };
+    let with_offsets_all = column_expr(
+        &|_| quote! { ::std::ptr::NonNull::new_unchecked(ptr.cast()) },
+        &|_| quote! { ::soa_rs::SoaRaw::from_parts(ptr, cap) },
+    );
+    let with_offsets_all = ident_all.iter().enumerate().map(|(i, column)| {
+        let with_offsets = &with_offsets_all[i];
+        match layout_all.iter().position(|&j| j == i) {
+            None => quote! { #column: ::std::ptr::NonNull::dangling() },
+            Some(0) => quote! { #column: #with_offsets },
+            Some(k) => {
+                let offset = k - 1;
+                quote! {
+                    #column: {
+                        let ptr = ptr.add(offsets[#offset]);
+                        #with_offsets
+                    }
+                }
+            }
+        }
+    });
+    let dangling_all = column_expr(
+        &|_| quote! { ::std::ptr::NonNull::dangling() },
+        &|_| quote! { ::soa_rs::SoaRaw::dangling() },
+    );
+    let copy_to_all = column_expr(
+        &|column| quote! { ::std::ptr::copy(self.#column.as_ptr(), dst.#column.as_ptr(), count); },
+        &|column| quote! { ::soa_rs::SoaRaw::copy_to(self.#column, dst.#column, count); },
+    );
+    let copy_to_reversed_all: Vec<_> = column_expr(
+        &|column| quote! { ::std::ptr::copy(self.#column.as_ptr(), dst.#column.as_ptr(), count); },
+        &|column| quote! { ::soa_rs::SoaRaw::copy_to_reversed(self.#column, dst.#column, count); },
+    )
+    .into_iter()
+    .rev()
+    .collect();
+    let offset_all = column_expr(
+        &|column| {
+            quote! {
+                ::std::ptr::NonNull::new_unchecked(self.#column.as_ptr().add(count))
+            }
+        },
+        &|column| quote! { ::soa_rs::SoaRaw::offset(self.#column, count) },
+    );
+    let slices_all = column_expr(
+        &|column| {
+            quote! {
+                unsafe { ::std::slice::from_raw_parts(self.#column.as_ptr(), len) }
+            }
+        },
+        &|column| quote! { ::soa_rs::SoaRaw::slices(self.#column, len) },
+    );
+    let slices_mut_all = column_expr(
+        &|column| {
+            quote! {
+                unsafe { ::std::slice::from_raw_parts_mut(self.#column.as_ptr(), len) }
+            }
+        },
+        &|column| quote! { ::soa_rs::SoaRaw::slices_mut(self.#column, len) },
+    );
+    let slices_uninit_all = column_expr(
+        &|column| {
+            quote! {
+                unsafe { ::std::slice::from_raw_parts_mut(self.#column.as_ptr().cast(), len) }
+            }
+        },
+        &|column| quote! { ::soa_rs::SoaRaw::slices_uninit(self.#column, len) },
+    );
+    let slices_len = slice_ident_all.len();
+    let slices_all = &slices_all[..slices_len];
+    let slices_mut_all = &slices_mut_all[..slices_len];
 
     let layout_and_offsets_body = |checked: bool| {
         let check = if checked {
@@ -311,8 +1321,23 @@ pub fn fields_struct(
             }
         };
 
-        let mut raise_align = align_all.iter().map(|align| {
-            align.as_ref().map(|align| {
+        let mut array_layout = layout_all.iter().map(|&i| {
+            let ty = &ty_all[i];
+            if flatten_all[i] {
+                quote! {
+                    <<#ty as ::soa_rs::Soars>::Raw as ::soa_rs::SoaRaw>::layout(cap)#check
+                }
+            } else {
+                quote! {
+                    ::std::alloc::Layout::array::<#ty>(cap)#check
+                }
+            }
+        });
+        let array_layout_head = array_layout.next();
+        let array_layout_tail: Vec<_> = array_layout.collect();
+
+        let mut raise_align = layout_all.iter().map(|&i| {
+            align_all[i].as_ref().map(|align| {
                 quote! {
                     let array = array.align_to(#align)#check;
                 }
@@ -322,14 +1347,14 @@ pub fn fields_struct(
         let raise_align_head = raise_align.next().flatten();
         let raise_align_tail: Vec<_> = raise_align.collect();
 
-        let indices = indices.clone();
+        let indices = 0..offsets_len;
         quote! {
-            let array = ::std::alloc::Layout::array::<#ty_head>(cap)#check;
+            let array = #array_layout_head;
             #raise_align_head
             let layout = array;
             let mut offsets = [0usize; #offsets_len];
             #(
-                let array = ::std::alloc::Layout::array::<#ty_tail>(cap)#check;
+                let array = #array_layout_tail;
                 #raise_align_tail
                 let (layout, offset) = layout.extend(array)#check;
                 offsets[#indices] = offset;
@@ -337,26 +1362,113 @@ pub fn fields_struct(
         }
     };
 
+    let into_parts_head = if flatten_head {
+        quote! { ::soa_rs::SoaRaw::into_parts(self.#ident_head) }
+    } else {
+        quote! { self.#ident_head.as_ptr().cast() }
+    };
+    let as_soa_ref_all = field_ident_all
+        .iter()
+        .zip(&field_flatten_all)
+        .zip(&field_phantom_all)
+        .map(|((field, &flatten), &phantom)| {
+            if phantom {
+                quote! { ::std::marker::PhantomData }
+            } else if flatten {
+                quote! { ::soa_rs::AsSoaRef::as_soa_ref(&self.#field) }
+            } else {
+                quote! { &self.#field }
+            }
+        });
+
+    let column_offset_all = std::iter::once(quote! { 0 }).chain((0..offsets_len).map(|i| {
+        quote! { offsets[#i] }
+    }));
+    let column_name_all = layout_all.iter().map(|&i| &ident_all[i]);
+    let column_size_all = layout_all.iter().map(|&i| {
+        let ty = &ty_all[i];
+        if flatten_all[i] {
+            quote! {
+                <<#ty as ::soa_rs::Soars>::Raw as ::soa_rs::SoaRaw>::layout(capacity)?.size()
+            }
+        } else {
+            quote! {
+                ::std::mem::size_of::<#ty>() * capacity
+            }
+        }
+    });
+
+    let layout_len = layout_all.len();
+    let column_align_all = layout_all.iter().map(|&i| {
+        let ty = &ty_all[i];
+        let align = if flatten_all[i] {
+            quote! {
+                <<#ty as ::soa_rs::Soars>::Raw as ::soa_rs::SoaRaw>::layout(0).unwrap().align()
+            }
+        } else {
+            quote! { ::std::mem::align_of::<#ty>() }
+        };
+        match &align_all[i] {
+            Some(raised) => quote! { #align.max(#raised) },
+            None => align,
+        }
+    });
+    let column_ptr_all = layout_all.iter().map(|&i| {
+        let column = &ident_all[i];
+        if flatten_all[i] {
+            quote! {
+                unsafe {
+                    ::std::ptr::NonNull::new_unchecked(::soa_rs::SoaRaw::into_parts(raw.#column))
+                }
+            }
+        } else {
+            quote! { raw.#column.cast() }
+        }
+    });
+
     let layout_and_offsets_checked_body = layout_and_offsets_body(true);
     let layout_and_offsets_unchecked_body = layout_and_offsets_body(false);
 
     out.append_all(quote! {
         #[automatically_derived]
-        #[derive(Copy, Clone)]
-        #vis struct #raw #raw_body
+        #vis struct #raw #generics #raw_body
 
         #[automatically_derived]
-        unsafe impl ::soa_rs::Soars for #ident {
-            type Raw = #raw;
-            type Deref = #deref;
-            type Ref<'a> = #item_ref<'a> where Self: 'a;
-            type RefMut<'a> = #item_ref_mut<'a> where Self: 'a;
-            type Slices<'a> = #slices<'a> where Self: 'a;
-            type SlicesMut<'a> = #slices_mut<'a> where Self: 'a;
+        impl #impl_generics #deref #ty_generics #where_clause {
+            #vis fn field_ptrs(&self) -> [::std::ptr::NonNull<u8>; #layout_len] {
+                let raw = self.0.raw();
+                [#(#column_ptr_all),*]
+            }
         }
 
         #[automatically_derived]
-        impl #raw {
+        impl #impl_generics ::std::clone::Clone for #raw #ty_generics #where_clause {
+            fn clone(&self) -> Self {
+                *self
+            }
+        }
+
+        #[automatically_derived]
+        impl #impl_generics ::std::marker::Copy for #raw #ty_generics #where_clause {}
+
+        #[automatically_derived]
+        unsafe impl #impl_generics ::soa_rs::Soars for #item_ty #where_clause {
+            type Raw = #raw #ty_generics;
+            type Deref = #deref #ty_generics;
+            type Ref<'a> = #item_ref #ty_generics_a where Self: 'a;
+            type RefMut<'a> = #item_ref_mut #ty_generics_a where Self: 'a;
+            type Slices<'a> = #slices #ty_generics_a where Self: 'a;
+            type SlicesMut<'a> = #slices_mut #ty_generics_a where Self: 'a;
+            type SlicesUninit<'a> = #slices_uninit #ty_generics_a where Self: 'a;
+            type Inline<const N: usize> = #inline #ty_generics_n;
+        }
+
+        #[automatically_derived]
+        impl #impl_generics #raw #ty_generics #where_clause {
+            #vis fn field_alignments() -> [usize; #layout_len] {
+                [#(#column_align_all),*]
+            }
+
             #[inline]
             fn layout_and_offsets(cap: usize)
                 -> Result<(::std::alloc::Layout, [usize; #offsets_len]), ::std::alloc::LayoutError>
@@ -374,39 +1486,52 @@ pub fn fields_struct(
             }
 
             #[inline]
-            unsafe fn with_offsets(ptr: *mut u8, offsets: [usize; #offsets_len]) -> Self {
+            #[allow(unused_variables)]
+            unsafe fn with_offsets(ptr: *mut u8, offsets: [usize; #offsets_len], cap: usize) -> Self {
                 Self {
-                    #ident_head: ::std::ptr::NonNull::new_unchecked(ptr.cast()),
-                    #(
-                    #ident_tail: ::std::ptr::NonNull::new_unchecked(
-                        ptr.add(offsets[#indices]).cast(),
-                    )
-                    ),*
+                    #(#with_offsets_all,)*
                 }
             }
         }
 
         #[automatically_derived]
-        unsafe impl ::soa_rs::SoaRaw for #raw {
-            type Item = #ident;
+        unsafe impl #impl_generics ::soa_rs::SoaRaw for #raw #ty_generics #where_clause {
+            type Item = #item_ty;
+
+            const DROP_FORWARD: bool = #drop_forward;
 
             #[inline]
             fn dangling() -> Self {
                 Self {
-                    #(#ident_all: ::std::ptr::NonNull::dangling(),)*
+                    #(#ident_all: #dangling_all,)*
                 }
             }
 
+            #[inline]
+            fn layout(capacity: usize) -> Result<::std::alloc::Layout, ::std::alloc::LayoutError> {
+                Self::layout_and_offsets(capacity).map(|(layout, _)| layout)
+            }
+
+            fn capacity_report(capacity: usize)
+                -> Result<::soa_rs::CapacityReport, ::std::alloc::LayoutError>
+            {
+                let (layout, offsets) = Self::layout_and_offsets(capacity)?;
+                Ok(::soa_rs::CapacityReport::new(
+                    layout.size(),
+                    [#((stringify!(#column_name_all), #column_offset_all, #column_size_all)),*],
+                ))
+            }
+
             #[inline]
             unsafe fn from_parts(ptr: *mut u8, capacity: usize) -> Self {
                 // SAFETY: This should have come from a previous allocation
                 let (_, offsets) = Self::layout_and_offsets_unchecked(capacity);
-                Self::with_offsets(ptr, offsets)
+                Self::with_offsets(ptr, offsets, capacity)
             }
 
             #[inline]
             fn into_parts(self) -> *mut u8 {
-                self.#ident_head.as_ptr().cast()
+                #into_parts_head
             }
 
             #[inline]
@@ -419,7 +1544,20 @@ pub fn fields_struct(
                     ::std::alloc::handle_alloc_error(new_layout);
                 }
 
-                Self::with_offsets(ptr, new_offsets)
+                Self::with_offsets(ptr, new_offsets, capacity)
+            }
+
+            #[inline]
+            unsafe fn try_alloc(capacity: usize) -> Result<Self, ::soa_rs::TryReserveError> {
+                let (new_layout, new_offsets) = Self::layout_and_offsets(capacity)
+                    .map_err(|_| ::soa_rs::TryReserveError::CapacityOverflow)?;
+
+                let ptr = ::std::alloc::alloc(new_layout);
+                if ptr.is_null() {
+                    return Err(::soa_rs::TryReserveError::AllocError { layout: new_layout });
+                }
+
+                Ok(Self::with_offsets(ptr, new_offsets, capacity))
             }
 
             #[inline]
@@ -435,25 +1573,54 @@ pub fn fields_struct(
                     .expect("capacity overflow");
 
                 // Grow allocation first
-                let ptr = self.#ident_head.as_ptr().cast();
+                let ptr = ::soa_rs::SoaRaw::into_parts(*self);
                 let ptr = ::std::alloc::realloc(ptr, old_layout, new_layout.size());
                 if ptr.is_null() {
                     ::std::alloc::handle_alloc_error(new_layout);
                 }
 
                 // Pointer may have moved, can't reuse self
-                let old = Self::with_offsets(ptr, old_offsets);
-                let new = Self::with_offsets(ptr, new_offsets);
+                let old = Self::with_offsets(ptr, old_offsets, old_capacity);
+                let new = Self::with_offsets(ptr, new_offsets, new_capacity);
 
                 // Copy do destination in reverse order to avoid
                 // overwriting data
-                #(
-                    ::std::ptr::copy(old.#ident_rev.as_ptr(), new.#ident_rev.as_ptr(), length);
-                )*
+                ::soa_rs::SoaRaw::copy_to_reversed(old, new, length);
 
                 new
             }
 
+            #[inline]
+            unsafe fn try_realloc_grow(
+                &mut self,
+                old_capacity: usize,
+                new_capacity: usize,
+                length: usize,
+            ) -> Result<Self, ::soa_rs::TryReserveError> {
+                // SAFETY: We already constructed this layout for a previous allocation
+                let (old_layout, old_offsets) = Self::layout_and_offsets_unchecked(old_capacity);
+                let (new_layout, new_offsets) = Self::layout_and_offsets(new_capacity)
+                    .map_err(|_| ::soa_rs::TryReserveError::CapacityOverflow)?;
+
+                // Grow allocation first. On failure, the old allocation is
+                // left as it was.
+                let ptr = ::soa_rs::SoaRaw::into_parts(*self);
+                let ptr = ::std::alloc::realloc(ptr, old_layout, new_layout.size());
+                if ptr.is_null() {
+                    return Err(::soa_rs::TryReserveError::AllocError { layout: new_layout });
+                }
+
+                // Pointer may have moved, can't reuse self
+                let old = Self::with_offsets(ptr, old_offsets, old_capacity);
+                let new = Self::with_offsets(ptr, new_offsets, new_capacity);
+
+                // Copy do destination in reverse order to avoid
+                // overwriting data
+                ::soa_rs::SoaRaw::copy_to_reversed(old, new, length);
+
+                Ok(new)
+            }
+
             #[inline]
             unsafe fn realloc_shrink(
                 &mut self,
@@ -469,11 +1636,9 @@ pub fn fields_struct(
                 // Move data before reallocating as some data
                 // may be past the end of the new allocation.
                 // Copy from front to back to avoid overwriting data.
-                let ptr = self.#ident_head.as_ptr().cast();
-                let dst = Self::with_offsets(ptr, new_offsets);
-                #(
-                    ::std::ptr::copy(self.#ident_all.as_ptr(), dst.#ident_all.as_ptr(), length);
-                )*
+                let ptr = ::soa_rs::SoaRaw::into_parts(*self);
+                let dst = Self::with_offsets(ptr, new_offsets, new_capacity);
+                ::soa_rs::SoaRaw::copy_to(*self, dst, length);
 
                 let ptr = ::std::alloc::realloc(ptr, old_layout, new_layout.size());
                 if ptr.is_null() {
@@ -481,100 +1646,262 @@ pub fn fields_struct(
                 }
 
                 // Pointer may have moved, can't reuse dst
-                Self::with_offsets(ptr, new_offsets)
+                Self::with_offsets(ptr, new_offsets, new_capacity)
             }
 
             #[inline]
             unsafe fn dealloc(self, old_capacity: usize) {
                 // SAFETY: We already constructed this layout for a previous allocation
                 let (layout, _) = Self::layout_and_offsets_unchecked(old_capacity);
-                ::std::alloc::dealloc(self.#ident_head.as_ptr().cast(), layout);
+                ::std::alloc::dealloc(::soa_rs::SoaRaw::into_parts(self), layout);
             }
 
             #[inline]
             unsafe fn copy_to(self, dst: Self, count: usize) {
-                #(
-                    ::std::ptr::copy(self.#ident_all.as_ptr(), dst.#ident_all.as_ptr(), count);
-                )*
+                #(#copy_to_all)*
             }
 
             #[inline]
-            unsafe fn set(self, element: #ident) {
-                #(self.#ident_all.as_ptr().write(element.#ident_all);)*
+            unsafe fn copy_to_reversed(self, dst: Self, count: usize) {
+                #(#copy_to_reversed_all)*
             }
 
             #[inline]
-            unsafe fn get(self) -> #ident {
+            unsafe fn set(self, element: #item_ty) {
+                #(#set_all)*
+            }
+
+            #[inline]
+            unsafe fn get(self) -> #item_ty {
+                #get_skipped
                 #ident {
-                    #(#ident_all: self.#ident_all.as_ptr().read(),)*
+                    #(#field_ident_all: #get_all,)*
                 }
             }
 
             #[inline]
-            unsafe fn get_ref<'a>(self) -> #item_ref<'a> {
+            unsafe fn get_ref<'a>(self) -> <Self::Item as ::soa_rs::Soars>::Ref<'a> {
                 #item_ref {
-                    #(#ident_all: self.#ident_all.as_ptr().as_ref().unwrap_unchecked(),)*
+                    #(#field_ident_all: #get_ref_all,)*
                 }
             }
 
             #[inline]
-            unsafe fn get_mut<'a>(self) -> #item_ref_mut<'a> {
+            unsafe fn get_mut<'a>(self) -> <Self::Item as ::soa_rs::Soars>::RefMut<'a> {
                 #item_ref_mut {
-                    #(#ident_all: self.#ident_all.as_ptr().as_mut().unwrap_unchecked(),)*
+                    #(#field_ident_all: #get_mut_all,)*
                 }
             }
 
             #[inline]
             unsafe fn offset(self, count: usize) -> Self {
                 Self {
-                    #(
-                    #ident_all: ::std::ptr::NonNull::new_unchecked(
-                        self.#ident_all.as_ptr().add(count)
-                    ),
-                    )*
+                    #(#ident_all: #offset_all,)*
                 }
             }
 
             #[inline]
-            unsafe fn slices<'a>(self, len: usize) -> #slices<'a> {
+            unsafe fn slices<'a>(self, len: usize) -> <Self::Item as ::soa_rs::Soars>::Slices<'a> {
                 #slices {
-                    #(
-                        #ident_all: unsafe {
-                            ::std::slice::from_raw_parts(self.#ident_all.as_ptr(), len)
-                        },
-                    )*
+                    #(#slice_ident_all: #slices_all,)*
                 }
             }
 
             #[inline]
-            unsafe fn slices_mut<'a>(self, len: usize) -> #slices_mut<'a> {
+            unsafe fn slices_mut<'a>(self, len: usize)
+                -> <Self::Item as ::soa_rs::Soars>::SlicesMut<'a>
+            {
                 #slices_mut {
-                    #(
-                        #ident_all: unsafe {
-                            ::std::slice::from_raw_parts_mut(self.#ident_all.as_ptr(), len)
-                        },
-                    )*
+                    #(#slice_ident_all: #slices_mut_all,)*
+                }
+            }
+
+            #[inline]
+            unsafe fn slices_uninit<'a>(self, len: usize)
+                -> <Self::Item as ::soa_rs::Soars>::SlicesUninit<'a>
+            {
+                #slices_uninit {
+                    #(#ident_all: #slices_uninit_all,)*
                 }
             }
         }
 
         #[automatically_derived]
-        impl ::soa_rs::AsSoaRef for #ident {
-            type Item = #ident;
+        impl #impl_generics ::soa_rs::AsSoaRef for #item_ty #where_clause {
+            type Item = #item_ty;
 
             fn as_soa_ref(&self) -> <Self::Item as ::soa_rs::Soars>::Ref<'_> {
                 #item_ref {
                     #(
-                        #ident_all: &self.#ident_all,
+                        #field_ident_all: #as_soa_ref_all,
                     )*
                 }
             }
         }
     });
 
+    let (from_soa_ref_bound_all, from_soa_ref_all): (Vec<_>, Vec<_>) = field_ident_all
+        .iter()
+        .zip(&field_ty_all)
+        .zip(&field_flatten_all)
+        .zip(&field_phantom_all)
+        .map(|(((field, ty), &flatten), &phantom)| {
+            if phantom {
+                (
+                    quote! { #ty: ::std::clone::Clone },
+                    quote! { ::std::marker::PhantomData },
+                )
+            } else if flatten {
+                (
+                    quote! { #ty: ::soa_rs::FromSoaRef },
+                    quote! { <#ty as ::soa_rs::FromSoaRef>::from_soa_ref(item.#field) },
+                )
+            } else {
+                (
+                    quote! { #ty: ::std::clone::Clone },
+                    quote! { ::std::clone::Clone::clone(item.#field) },
+                )
+            }
+        })
+        .unzip();
+    out.append_all(quote! {
+        #[automatically_derived]
+        impl #impl_generics ::soa_rs::FromSoaRef for #item_ty
+        where
+            #(#where_preds,)*
+            #(for<'__soa> #from_soa_ref_bound_all,)*
+        {
+            fn from_soa_ref(item: <Self as ::soa_rs::Soars>::Ref<'_>) -> Self {
+                Self {
+                    #(
+                        #field_ident_all: #from_soa_ref_all,
+                    )*
+                }
+            }
+        }
+
+        #[automatically_derived]
+        impl #impl_generics_a ::std::iter::Extend<#item_ref #ty_generics_a> for ::soa_rs::Soa<#item_ty>
+        where
+            #(#where_preds,)*
+            #(for<'__soa> #from_soa_ref_bound_all,)*
+        {
+            fn extend<I: IntoIterator<Item = #item_ref #ty_generics_a>>(&mut self, iter: I) {
+                self.extend(
+                    iter.into_iter()
+                        .map(|item| <#item_ty as ::soa_rs::FromSoaRef>::from_soa_ref(item)),
+                );
+            }
+        }
+
+        #[automatically_derived]
+        impl #impl_generics_a ::std::iter::Extend<#item_ref_mut #ty_generics_a> for ::soa_rs::Soa<#item_ty>
+        where
+            #(#where_preds,)*
+            #(for<'__soa> #from_soa_ref_bound_all,)*
+        {
+            fn extend<I: IntoIterator<Item = #item_ref_mut #ty_generics_a>>(&mut self, iter: I) {
+                self.extend(iter.into_iter().map(|item| {
+                    <#item_ty as ::soa_rs::FromSoaRef>::from_soa_ref(
+                        ::soa_rs::AsSoaRef::as_soa_ref(&item),
+                    )
+                }));
+            }
+        }
+
+        #[automatically_derived]
+        impl #impl_generics_a ::std::iter::FromIterator<#item_ref #ty_generics_a> for ::soa_rs::Soa<#item_ty>
+        where
+            #(#where_preds,)*
+            #(for<'__soa> #from_soa_ref_bound_all,)*
+        {
+            fn from_iter<I: IntoIterator<Item = #item_ref #ty_generics_a>>(iter: I) -> Self {
+                let mut out = Self::new();
+                out.extend(iter);
+                out
+            }
+        }
+
+        #[automatically_derived]
+        impl #impl_generics_a ::std::iter::FromIterator<#item_ref_mut #ty_generics_a> for ::soa_rs::Soa<#item_ty>
+        where
+            #(#where_preds,)*
+            #(for<'__soa> #from_soa_ref_bound_all,)*
+        {
+            fn from_iter<I: IntoIterator<Item = #item_ref_mut #ty_generics_a>>(iter: I) -> Self {
+                let mut out = Self::new();
+                out.extend(iter);
+                out
+            }
+        }
+    });
+
     Ok(out)
 }
 
+/// Adds a parameter to the generics of the original struct. Lifetimes go first
+/// and other parameters go last.
+fn generics_with(generics: &Generics, param: GenericParam) -> Generics {
+    let mut out = generics.clone();
+    match param {
+        GenericParam::Lifetime(_) => out.params.insert(0, param),
+        _ => out.params.push(param),
+    }
+    out
+}
+
+/// Whether the type is spelled as `PhantomData`, possibly with a path prefix.
+fn is_phantom(ty: &syn::Type) -> bool {
+    match ty {
+        syn::Type::Path(path) => path
+            .path
+            .segments
+            .last()
+            .is_some_and(|segment| segment.ident == "PhantomData"),
+        _ => false,
+    }
+}
+
+/// Whether the type is one of the standard library's interior mutability
+/// types, or an array, tuple, or `Option` of one. This only looks at the type
+/// as written, so aliases and other types that contain a `Cell` are missed.
+fn has_interior_mutability(ty: &syn::Type) -> bool {
+    const CELLS: &[&str] = &[
+        "Cell",
+        "RefCell",
+        "UnsafeCell",
+        "SyncUnsafeCell",
+        "OnceCell",
+        "LazyCell",
+        "Mutex",
+        "RwLock",
+        "OnceLock",
+        "LazyLock",
+    ];
+    const WRAPPERS: &[&str] = &["Option", "ManuallyDrop", "MaybeUninit", "Wrapping"];
+
+    match ty {
+        syn::Type::Array(array) => has_interior_mutability(&array.elem),
+        syn::Type::Tuple(tuple) => tuple.elems.iter().any(has_interior_mutability),
+        syn::Type::Paren(paren) => has_interior_mutability(&paren.elem),
+        syn::Type::Group(group) => has_interior_mutability(&group.elem),
+        syn::Type::Path(path) => path.path.segments.last().is_some_and(|segment| {
+            let ident = segment.ident.to_string();
+            if CELLS.contains(&ident.as_str()) || ident.starts_with("Atomic") {
+                return true;
+            }
+            let syn::PathArguments::AngleBracketed(arguments) = &segment.arguments else {
+                return false;
+            };
+            WRAPPERS.contains(&ident.as_str())
+                && arguments.args.iter().any(|argument| {
+                    matches!(argument, syn::GenericArgument::Type(ty) if has_interior_mutability(ty))
+                })
+        }),
+        _ => false,
+    }
+}
+
 #[derive(Clone, PartialEq, Eq)]
 enum FieldIdent {
     Named(Ident),
@@ -11,10 +11,26 @@ use std::{
     error::Error,
     fmt::{self, Display, Formatter},
 };
-use syn::{parse_macro_input, Attribute, Data, DeriveInput, Fields};
+use syn::{
+    parse_macro_input, punctuated::Punctuated, Attribute, Data, DeriveInput, Expr, Fields, LitInt,
+    LitStr, Meta, Token,
+};
 use zst::{zst_struct, ZstKind};
 
-#[proc_macro_derive(Soars, attributes(align, soa_derive, soa_array))]
+#[proc_macro_derive(
+    Soars,
+    attributes(
+        soa,
+        align,
+        soa_derive,
+        soa_attr,
+        soa_array,
+        soa_stats,
+        soa_pod,
+        soa_skip,
+        soa_flatten
+    )
+)]
 pub fn soa(input: TokenStream) -> TokenStream {
     let input: DeriveInput = parse_macro_input!(input);
     let span = input.ident.span();
@@ -36,15 +52,16 @@ fn soa_inner(input: DeriveInput) -> Result<TokenStream2, SoarsError> {
         vis,
         data,
         attrs,
-        generics: _,
+        generics,
     } = input;
 
-    let attrs = SoaAttrs::new(attrs)?;
+    let attrs = SoaAttrs::new(attrs, !generics.params.is_empty())?;
     match data {
         Data::Struct(strukt) => match strukt.fields {
             Fields::Named(fields) => Ok(fields_struct(
                 ident,
                 vis,
+                generics,
                 fields.named,
                 FieldKind::Named,
                 attrs,
@@ -52,11 +69,21 @@ fn soa_inner(input: DeriveInput) -> Result<TokenStream2, SoarsError> {
             Fields::Unnamed(fields) => Ok(fields_struct(
                 ident,
                 vis,
+                generics,
                 fields.unnamed,
                 FieldKind::Unnamed,
                 attrs,
             )?),
-            Fields::Unit => Ok(zst_struct(ident, vis, ZstKind::Unit)),
+            Fields::Unit => {
+                if !generics.params.is_empty() {
+                    return Err(syn::Error::new_spanned(
+                        generics,
+                        "generic parameters are not supported for structs without fields",
+                    )
+                    .into());
+                }
+                Ok(zst_struct(ident, vis, ZstKind::Unit, attrs.drop_forward))
+            }
         },
         Data::Enum(_) | Data::Union(_) => Err(SoarsError::NotAStruct),
     }
@@ -78,30 +105,101 @@ impl From<syn::Error> for SoarsError {
 struct SoaAttrs {
     pub derive: SoaDerive,
     pub include_array: bool,
+    pub include_stats: bool,
+    pub include_pod: bool,
+    pub drop_forward: bool,
+    /// The alignment for fields without their own `align` attribute.
+    pub align: Option<TokenStream2>,
 }
 
 impl SoaAttrs {
-    pub fn new(attributes: Vec<Attribute>) -> Result<Self, syn::Error> {
+    pub fn new(attributes: Vec<Attribute>, generic: bool) -> Result<Self, syn::Error> {
         let mut derive_parse = SoaDeriveParse::new();
         let mut include_array = false;
+        let mut include_stats = false;
+        let mut include_pod = false;
+        let mut drop_forward = false;
+        let mut align = None;
         for attr in attributes {
             let path = attr.path();
             if path.is_ident("soa_derive") {
                 derive_parse.append(attr)?;
+            } else if path.is_ident("soa_attr") {
+                derive_parse.attrs.push(SoaAttr::parse(&attr)?);
             } else if path.is_ident("soa_array") {
                 include_array = true;
+            } else if path.is_ident("soa_stats") {
+                include_stats = true;
+            } else if path.is_ident("soa_pod") {
+                if !cfg!(feature = "bytemuck") {
+                    return Err(syn::Error::new_spanned(
+                        attr,
+                        "soa_pod requires the bytemuck feature",
+                    ));
+                }
+                include_pod = true;
+            } else if path.is_ident("soa") {
+                attr.parse_nested_meta(|meta| {
+                    if meta.path.is_ident("array") {
+                        include_array = true;
+                        Ok(())
+                    } else if meta.path.is_ident("align") {
+                        align = Some(parse_align(&meta.value()?.parse()?)?);
+                        Ok(())
+                    } else if meta.path.is_ident("drop_order") {
+                        let order: LitStr = meta.value()?.parse()?;
+                        drop_forward = match order.value().as_str() {
+                            "forward" => true,
+                            "reverse" => false,
+                            _ => {
+                                return Err(syn::Error::new_spanned(
+                                    order,
+                                    "expected \"forward\" or \"reverse\"",
+                                ))
+                            }
+                        };
+                        Ok(())
+                    } else {
+                        Err(meta.error("expected `array`, `align`, or `drop_order`"))
+                    }
+                })?;
             } else {
                 return Err(syn::Error::new_spanned(attr, "Unknown SOA attribute"));
             }
         }
 
         Ok(Self {
-            derive: derive_parse.into_derive(),
+            derive: derive_parse.into_derive(generic),
             include_array,
+            include_stats,
+            include_pod,
+            drop_forward,
+            align,
         })
     }
 }
 
+/// Parses the argument of an `align` attribute, which is either a power of two
+/// or `simd`.
+fn parse_align(align_arg: &Expr) -> Result<TokenStream2, syn::Error> {
+    if let Expr::Path(path) = align_arg {
+        if path.path.is_ident("simd") {
+            return Ok(quote! { ::soa_rs::SIMD_ALIGN });
+        }
+    }
+    let align_literal: LitInt = syn::parse2(quote! { #align_arg }).map_err(|_| {
+        syn::Error::new_spanned(align_arg, "align should be a power of two or `simd`")
+    })?;
+    let align_value: usize = align_literal.base10_parse()?;
+    if !align_value.is_power_of_two() {
+        return Err(syn::Error::new_spanned(
+            align_literal,
+            "align should be a power of two",
+        ));
+    }
+    Ok(quote! { #align_value })
+}
+
 #[derive(Debug, Clone, Default)]
 struct SoaDeriveParse {
     r#ref: Vec<syn::Path>,
@@ -109,6 +207,8 @@ struct SoaDeriveParse {
     slices: Vec<syn::Path>,
     slices_mut: Vec<syn::Path>,
     array: Vec<syn::Path>,
+    columns: Vec<syn::Path>,
+    attrs: Vec<SoaAttr>,
 }
 
 impl SoaDeriveParse {
@@ -119,33 +219,66 @@ impl SoaDeriveParse {
             slices: copy_clone(),
             slices_mut: vec![],
             array: vec![],
+            columns: vec![],
+            attrs: vec![],
         }
     }
 
-    fn into_derive(self) -> SoaDerive {
+    /// Generic types get manual `Copy` and `Clone` implementations for `Ref`
+    /// and `Slices` since the derived ones would require the type parameters
+    /// to be `Copy` as well. `Hash` for `Ref` is also implemented manually so
+    /// that only its fields need to be `Hash`, so it is removed from the
+    /// derive list, whether or not the path is qualified.
+    fn into_derive(self, generic: bool) -> SoaDerive {
         let Self {
-            r#ref: reff,
+            r#ref: mut reff,
             ref_mut,
-            slices,
+            mut slices,
             slices_mut,
             array,
+            columns,
+            attrs,
         } = self;
+        if generic {
+            let is_copy_clone = |path: &syn::Path| path.is_ident("Copy") || path.is_ident("Clone");
+            reff.retain(|path| !is_copy_clone(path));
+            slices.retain(|path| !is_copy_clone(path));
+        }
+        let is_hash = |path: &syn::Path| path.segments.last().is_some_and(|s| s.ident == "Hash");
+        let hash_ref = reff.iter().any(is_hash);
+        reff.retain(|path| !is_hash(path));
+        let attrs_ref = SoaAttr::select(&attrs, |mask| mask.r#ref);
+        let attrs_ref_mut = SoaAttr::select(&attrs, |mask| mask.ref_mut);
+        let attrs_slices = SoaAttr::select(&attrs, |mask| mask.slice);
+        let attrs_slices_mut = SoaAttr::select(&attrs, |mask| mask.slice_mut);
+        let attrs_array = SoaAttr::select(&attrs, |mask| mask.array);
+        let attrs_columns = SoaAttr::select(&attrs, |mask| mask.columns);
         SoaDerive {
             r#ref: quote! {
                 #[derive(#(#reff),*)]
+                #attrs_ref
             },
             ref_mut: quote! {
                 #[derive(#(#ref_mut),*)]
+                #attrs_ref_mut
             },
             slices: quote! {
                 #[derive(#(#slices),*)]
+                #attrs_slices
             },
             slices_mut: quote! {
                 #[derive(#(#slices_mut),*)]
+                #attrs_slices_mut
             },
             array: quote! {
                 #[derive(#(#array),*)]
+                #attrs_array
             },
+            columns: quote! {
+                #[derive(#(#columns),*)]
+                #attrs_columns
+            },
+            hash_ref,
         }
     }
 
@@ -179,7 +312,8 @@ impl SoaDeriveParse {
             .chain(mask.ref_mut.then_some(&mut self.ref_mut))
             .chain(mask.slice.then_some(&mut self.slices))
             .chain(mask.slice_mut.then_some(&mut self.slices_mut))
-            .chain(mask.array.then_some(&mut self.array));
+            .chain(mask.array.then_some(&mut self.array))
+            .chain(mask.columns.then_some(&mut self.columns));
 
         for set in to_extend {
             set.extend(collected.iter().cloned());
@@ -189,6 +323,45 @@ impl SoaDeriveParse {
     }
 }
 
+/// Attributes to forward to one of the generated types, as in
+/// `#[soa_attr(Ref, serde(rename_all = "camelCase"))]`. This is accepted on the
+/// struct and on individual fields.
+#[derive(Debug, Clone)]
+struct SoaAttr {
+    pub mask: SoaDeriveMask,
+    pub metas: Vec<Meta>,
+}
+
+impl SoaAttr {
+    pub fn parse(attr: &Attribute) -> Result<Self, syn::Error> {
+        attr.parse_args_with(|input: syn::parse::ParseStream| {
+            let target: syn::Path = input.parse()?;
+            let mut mask = SoaDeriveMask::splat(false);
+            mask.set_by_path(&target, true).map_err(|_| {
+                syn::Error::new_spanned(&target, format!("unknown soa_attr target {target:?}"))
+            })?;
+            input.parse::<Token![,]>()?;
+            let metas = Punctuated::<Meta, Token![,]>::parse_terminated(input)?;
+            Ok(Self {
+                mask,
+                metas: metas.into_iter().collect(),
+            })
+        })
+    }
+
+    /// Collects the attributes that apply to the generated type selected by
+    /// `target`.
+    pub fn select(attrs: &[Self], target: impl Fn(&SoaDeriveMask) -> bool) -> TokenStream2 {
+        let metas = attrs
+            .iter()
+            .filter(|attr| target(&attr.mask))
+            .flat_map(|attr| &attr.metas);
+        quote! {
+            #(#[#metas])*
+        }
+    }
+}
+
 fn copy_clone() -> Vec<syn::Path> {
     vec![str_to_path("Copy"), str_to_path("Clone")]
 }
@@ -207,6 +380,8 @@ struct SoaDerive {
     pub slices: TokenStream2,
     pub slices_mut: TokenStream2,
     pub array: TokenStream2,
+    pub columns: TokenStream2,
+    pub hash_ref: bool,
 }
 
 #[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug)]
@@ -216,11 +391,17 @@ struct SoaDeriveMask {
     pub slice: bool,
     pub slice_mut: bool,
     pub array: bool,
+    pub columns: bool,
 }
 
 impl SoaDeriveMask {
     pub const fn new() -> Self {
-        Self::splat(true)
+        // Columns are opt-in since Vec may not support everything that
+        // references do
+        Self {
+            columns: false,
+            ..Self::splat(true)
+        }
     }
 
     pub const fn splat(value: bool) -> Self {
@@ -230,6 +411,7 @@ impl SoaDeriveMask {
             slice: value,
             slice_mut: value,
             array: value,
+            columns: value,
         }
     }
 
@@ -244,6 +426,8 @@ impl SoaDeriveMask {
             self.slice_mut = value;
         } else if path.is_ident("Array") {
             self.array = value;
+        } else if path.is_ident("Columns") {
+            self.columns = value;
         } else {
             return Err(SetByPathError);
         }
@@ -2,7 +2,7 @@ use proc_macro2::{Ident, TokenStream};
 use quote::{format_ident, quote};
 use syn::Visibility;
 
-pub fn zst_struct(ident: Ident, vis: Visibility, kind: ZstKind) -> TokenStream {
+pub fn zst_struct(ident: Ident, vis: Visibility, kind: ZstKind, drop_forward: bool) -> TokenStream {
     let raw = format_ident!("{ident}SoaRaw");
     let deref = format_ident!("{ident}Deref");
     let array = format_ident!("{ident}Array");
@@ -21,6 +21,24 @@ pub fn zst_struct(ident: Ident, vis: Visibility, kind: ZstKind) -> TokenStream {
             type RefMut<'a> = #ident;
             type Slices<'a> = #ident;
             type SlicesMut<'a> = #ident;
+            type SlicesUninit<'a> = #ident;
+            type Inline<const N: usize> = #raw;
+        }
+
+        #[automatically_derived]
+        impl ::soa_rs::FromSoaRef for #ident {
+            fn from_soa_ref(item: Self) -> Self { item }
+        }
+
+        #[automatically_derived]
+        unsafe impl ::soa_rs::SoaInline for #raw {
+            type Item = #ident;
+
+            fn uninit() -> Self { Self }
+
+            fn raw(&self) -> Self { Self }
+
+            fn raw_mut(&mut self) -> Self { Self }
         }
 
         #[automatically_derived]
@@ -81,9 +99,22 @@ pub fn zst_struct(ident: Ident, vis: Visibility, kind: ZstKind) -> TokenStream {
         unsafe impl ::soa_rs::SoaRaw for #raw {
             type Item = #ident;
 
+            const DROP_FORWARD: bool = #drop_forward;
+
             #[inline]
             fn dangling() -> Self { Self }
 
+            #[inline]
+            fn layout(capacity: usize) -> Result<::std::alloc::Layout, ::std::alloc::LayoutError> {
+                Ok(::std::alloc::Layout::new::<()>())
+            }
+
+            fn capacity_report(capacity: usize)
+                -> Result<::soa_rs::CapacityReport, ::std::alloc::LayoutError>
+            {
+                Ok(::soa_rs::CapacityReport::new(0, []))
+            }
+
             #[inline]
             unsafe fn from_parts(ptr: *mut u8, capacity: usize) -> Self { Self }
 
@@ -101,6 +132,19 @@ pub fn zst_struct(ident: Ident, vis: Visibility, kind: ZstKind) -> TokenStream {
                 length: usize,
             ) -> Self { Self }
 
+            #[inline]
+            unsafe fn try_alloc(capacity: usize) -> Result<Self, ::soa_rs::TryReserveError> {
+                Ok(Self)
+            }
+
+            #[inline]
+            unsafe fn try_realloc_grow(
+                &mut self,
+                old_capacity: usize,
+                new_capacity: usize,
+                length: usize,
+            ) -> Result<Self, ::soa_rs::TryReserveError> { Ok(Self) }
+
             #[inline]
             unsafe fn realloc_shrink(
                 &mut self,
@@ -115,6 +159,9 @@ pub fn zst_struct(ident: Ident, vis: Visibility, kind: ZstKind) -> TokenStream {
             #[inline]
             unsafe fn copy_to(self, dst: Self, count: usize) { }
 
+            #[inline]
+            unsafe fn copy_to_reversed(self, dst: Self, count: usize) { }
+
             #[inline]
             unsafe fn set(self, element: #ident) { }
 
@@ -139,6 +186,11 @@ pub fn zst_struct(ident: Ident, vis: Visibility, kind: ZstKind) -> TokenStream {
             unsafe fn slices_mut<'a>(self, len: usize) -> <#ident as Soars>::SlicesMut<'a> {
                 #ident #unit_construct
             }
+
+            #[inline]
+            unsafe fn slices_uninit<'a>(self, len: usize) -> <#ident as Soars>::SlicesUninit<'a> {
+                #ident #unit_construct
+            }
         }
     }
 }
@@ -1,4 +1,4 @@
-use criterion::{criterion_group, criterion_main, Criterion};
+use criterion::{criterion_group, criterion_main, BatchSize, Criterion};
 use rand::{rngs::StdRng, RngCore, SeedableRng};
 use soa_rs::{Soa, Soars};
 
@@ -25,12 +25,8 @@ impl Rng {
 
 #[derive(Soars, Debug, Clone, Copy, PartialEq, PartialOrd)]
 #[soa_derive(Debug, PartialEq, PartialOrd)]
-struct Vec4(
-    #[align(64)] f32,
-    #[align(64)] f32,
-    #[align(64)] f32,
-    #[align(64)] f32,
-);
+#[soa(align = 64)]
+struct Vec4(f32, f32, f32, f32);
 
 impl Vec4 {
     fn new_rng(rng: &mut Rng) -> Self {
@@ -105,5 +101,137 @@ fn criterion_benchmark(c: &mut Criterion) {
     });
 }
 
-criterion_group!(benches, criterion_benchmark);
+fn sort_benchmark(c: &mut Criterion) {
+    let mut rng = Rng::new(42);
+    let soa: Soa<_> = rng.collect_vec4(1 << 14);
+    let vec: Vec<_> = soa.iter().map(|v| Vec4(*v.0, *v.1, *v.2, *v.3)).collect();
+
+    c.bench_function("sort-soa-permute", |b| {
+        b.iter_batched_ref(
+            || soa.clone(),
+            |soa| soa.sort_by(|a, b| a.0.total_cmp(b.0)),
+            BatchSize::LargeInput,
+        )
+    });
+
+    c.bench_function("sort-soa-swap", |b| {
+        b.iter_batched_ref(
+            || soa.clone(),
+            |soa| {
+                // Heapsort in terms of Slice::swap, for comparison with the
+                // permutation approach used by Slice::sort_by
+                let less = |soa: &Soa<Vec4>, i: usize, j: usize| soa.idx(i).0 < soa.idx(j).0;
+                let sift_down = |soa: &mut Soa<Vec4>, mut node: usize, end: usize| loop {
+                    let mut child = 2 * node + 1;
+                    if child >= end {
+                        break;
+                    }
+                    if child + 1 < end && less(soa, child, child + 1) {
+                        child += 1;
+                    }
+                    if !less(soa, node, child) {
+                        break;
+                    }
+                    soa.swap(node, child);
+                    node = child;
+                };
+                let len = soa.len();
+                for i in (0..len / 2).rev() {
+                    sift_down(soa, i, len);
+                }
+                for end in (1..len).rev() {
+                    soa.swap(0, end);
+                    sift_down(soa, 0, end);
+                }
+            },
+            BatchSize::LargeInput,
+        )
+    });
+
+    c.bench_function("sort-vec", |b| {
+        b.iter_batched_ref(
+            || vec.clone(),
+            |vec| vec.sort_by(|a, b| a.0.total_cmp(&b.0)),
+            BatchSize::LargeInput,
+        )
+    });
+}
+
+fn rfold_benchmark(c: &mut Criterion) {
+    let mut rng = Rng::new(42);
+    let soa: Soa<_> = rng.collect_vec4(1 << 16);
+
+    c.bench_function("rfold-soa", |b| {
+        b.iter(|| soa.iter().rfold(0., |acc, v| acc * 0.5 + v.0))
+    });
+
+    c.bench_function("rfold-soa-next-back", |b| {
+        b.iter(|| {
+            // The default rfold implementation, for comparison
+            let mut iter = soa.iter();
+            let mut acc = 0.;
+            while let Some(v) = iter.next_back() {
+                acc = acc * 0.5 + v.0;
+            }
+            acc
+        })
+    });
+}
+
+fn zip_mut_benchmark(c: &mut Criterion) {
+    let mut rng = Rng::new(42);
+    let soa: Soa<_> = rng.collect_vec4(1 << 16);
+
+    c.bench_function("iter-mut-soa", |b| {
+        b.iter_batched_ref(
+            || soa.clone(),
+            |soa| {
+                for v in soa.iter_mut() {
+                    let dot = *v.0 * *v.0 + *v.1 * *v.1 + *v.2 * *v.2 + *v.3 * *v.3;
+                    *v.0 *= dot;
+                    *v.1 *= dot;
+                    *v.2 *= dot;
+                    *v.3 *= dot;
+                }
+            },
+            BatchSize::LargeInput,
+        )
+    });
+
+    c.bench_function("zip-mut-soa", |b| {
+        b.iter_batched_ref(
+            || soa.clone(),
+            |soa| {
+                for (x, y, z, w) in soa.zip_mut() {
+                    let dot = *x * *x + *y * *y + *z * *z + *w * *w;
+                    *x *= dot;
+                    *y *= dot;
+                    *z *= dot;
+                    *w *= dot;
+                }
+            },
+            BatchSize::LargeInput,
+        )
+    });
+}
+
+fn from_slice_benchmark(c: &mut Criterion) {
+    let mut rng = Rng::new(42);
+    let vec: Vec<_> = rng.collect_vec4(1 << 16);
+
+    c.bench_function("from-slice-soa", |b| b.iter(|| Soa::from(vec.as_slice())));
+
+    c.bench_function("from-slice-copy-soa", |b| {
+        b.iter(|| Soa::from_slice_copy(&vec))
+    });
+}
+
+criterion_group!(
+    benches,
+    criterion_benchmark,
+    sort_benchmark,
+    rfold_benchmark,
+    zip_mut_benchmark,
+    from_slice_benchmark
+);
 criterion_main!(benches);
@@ -1,9 +1,44 @@
 #![cfg(test)]
 #![allow(clippy::disallowed_names)]
 
-use std::sync::Mutex;
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    cell::{Cell, RefCell},
+    cmp::Ordering,
+    marker::PhantomData,
+    rc::Rc,
+    sync::Mutex,
+};
+
+use soa_rs::{
+    soa, soa_columns, AsMutSlice, AsSlice, AsSoaRef, ColumnLengthError, Columnar,
+    DeserializeColumnar, SmallSoa, Soa, SoaColumns, Soars, TryReserveError, SIMD_ALIGN,
+};
+
+thread_local! {
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+/// Counts allocations made by the current thread
+struct CountingAllocator;
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let _ = ALLOCATIONS.try_with(|count| count.set(count.get() + 1));
+        System.alloc(layout)
+    }
 
-use soa_rs::{soa, AsMutSlice, AsSlice, AsSoaRef, Soa, Soars};
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+fn allocations() -> usize {
+    ALLOCATIONS.with(Cell::get)
+}
 
 #[derive(Soars, Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[soa_derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
@@ -26,6 +61,118 @@ impl Drop for SingleDrop {
     }
 }
 
+#[derive(Debug)]
+struct DropCounter(Rc<Cell<usize>>);
+
+impl Drop for DropCounter {
+    fn drop(&mut self) {
+        self.0.set(self.0.get() + 1);
+    }
+}
+
+#[derive(Soars, Debug, Clone, Copy, PartialEq)]
+#[soa_derive(Debug, PartialEq)]
+#[soa_pod]
+struct Particle {
+    id: u8,
+    pos: [f32; 3],
+    #[align(16)]
+    mass: f64,
+}
+
+thread_local! {
+    static COMPARISONS: Cell<usize> = const { Cell::new(0) };
+}
+
+fn comparisons() -> usize {
+    COMPARISONS.with(Cell::get)
+}
+
+/// Counts calls to [`PartialEq::eq`] made by the current thread
+#[derive(Debug)]
+struct EqCounter(u8);
+
+impl PartialEq for EqCounter {
+    fn eq(&self, other: &Self) -> bool {
+        COMPARISONS.with(|count| count.set(count.get() + 1));
+        self.0 == other.0
+    }
+}
+
+#[derive(Soars, Debug, PartialEq)]
+#[soa_derive(Debug, PartialEq)]
+struct Compared(EqCounter);
+
+thread_local! {
+    static DROP_LOG: RefCell<Vec<u8>> = const { RefCell::new(vec![]) };
+}
+
+fn take_drop_log() -> Vec<u8> {
+    DROP_LOG.with(|log| log.take())
+}
+
+/// Records its value in the current thread's drop log when dropped
+#[derive(Debug)]
+struct DropLogger(u8);
+
+impl Drop for DropLogger {
+    fn drop(&mut self) {
+        DROP_LOG.with(|log| log.borrow_mut().push(self.0));
+    }
+}
+
+#[derive(Soars, Debug)]
+#[soa_derive(Debug)]
+struct DropsReverse(DropLogger);
+
+#[derive(Soars, Debug)]
+#[soa_derive(Debug)]
+#[soa(drop_order = "forward")]
+struct DropsForward(DropLogger);
+
+#[derive(Soars, Debug)]
+#[soa_derive(Debug)]
+struct Counted {
+    n: u8,
+    drops: DropCounter,
+}
+
+#[derive(Soars, Debug, Clone, PartialEq)]
+#[soa_derive(Debug, PartialEq)]
+struct Skipped {
+    foo: u64,
+    #[soa_skip]
+    name: String,
+    #[align(64)]
+    bar: u8,
+    #[soa_skip]
+    #[align(64)]
+    baz: SingleDrop,
+}
+
+#[derive(Soars, Debug, Clone, Copy, PartialEq)]
+#[soa_derive(Debug, PartialEq)]
+struct Vector3 {
+    x: f32,
+    y: f32,
+    z: f32,
+}
+
+#[derive(Soars, Debug, Clone, Copy, PartialEq)]
+#[soa_derive(Debug, PartialEq)]
+struct Velocity(#[soa_flatten] Vector3);
+
+#[derive(Soars, Debug, Clone, Copy, PartialEq)]
+#[soa_derive(Debug, PartialEq)]
+struct Body {
+    #[soa_flatten]
+    pos: Vector3,
+    #[soa_flatten]
+    vel: Velocity,
+    #[align(32)]
+    mass: f32,
+}
+
 #[derive(Soars, Debug, Clone, PartialEq, Eq, Hash)]
 #[soa_array]
 #[soa_derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
@@ -216,7 +363,7 @@ pub fn from_iter() {
 #[test]
 pub fn iter() {
     let soa: Soa<_> = ABCDE.into();
-    for (borrowed, owned) in soa.iter().zip(ABCDE.into_iter()) {
+    for (borrowed, owned) in soa.iter().zip(ABCDE) {
         assert_eq!(borrowed.foo, &owned.foo);
         assert_eq!(borrowed.bar, &owned.bar);
         assert_eq!(borrowed.baz, &owned.baz);
@@ -230,12 +377,49 @@ pub fn iter_mut() {
         *el.foo += 1;
         *el.bar += 2;
     }
-    for (borrowed, owned) in soa.iter().zip(ABCDE.into_iter()) {
+    for (borrowed, owned) in soa.iter().zip(ABCDE) {
         assert_eq!(borrowed.foo, &(owned.foo + 1));
         assert_eq!(borrowed.bar, &(owned.bar + 2));
     }
 }
 
+#[test]
+fn iter_mut_double_ended() {
+    let mut soa: Soa<El> = ABCDE.into();
+    let mut iter = soa.iter_mut();
+    let mut refs = vec![];
+    while let Some(front) = iter.next() {
+        refs.push(front);
+        assert_eq!(iter.as_slice().len(), iter.len());
+        if let Some(back) = iter.next_back() {
+            refs.push(back);
+            assert_eq!(iter.as_mut_slice().len(), iter.len());
+        }
+    }
+    assert!(iter.as_slice().is_empty());
+    assert_eq!(
+        refs.iter().map(|el| *el.foo).collect::<Vec<_>>(),
+        [0, 16, 4, 12, 8]
+    );
+
+    // Every reference is still live here, so none of them may alias
+    for (i, el) in refs.into_iter().enumerate() {
+        *el.foo = i as u64;
+        *el.bar += 100;
+    }
+    assert_eq!(soa.foo(), [0, 2, 4, 3, 1]);
+    assert_eq!(soa.bar(), [101, 105, 109, 113, 117]);
+
+    let mut iter = soa.iter_mut();
+    *iter.next_back().unwrap().bar = 0;
+    assert_eq!(iter.as_slice().bar(), [101, 105, 109, 113]);
+    *iter.nth_back(1).unwrap().bar = 1;
+    assert_eq!(iter.as_slice().bar(), [101, 105]);
+    *iter.next().unwrap().bar = 2;
+    assert_eq!(iter.into_slice().bar(), [105]);
+    assert_eq!(soa.bar(), [2, 105, 1, 113, 0]);
+}
+
 #[test]
 pub fn from_impls() {
     let expected: Soa<_> = ABCDE.into_iter().collect();
@@ -264,7 +448,7 @@ pub fn clone() {
 
 #[test]
 pub fn clone_from() {
-    let mut dst: Soa<_> = std::iter::repeat(Tuple(100, 100, 100)).take(7).collect();
+    let mut dst: Soa<_> = std::iter::repeat_n(Tuple(100, 100, 100), 7).collect();
     let src: Soa<_> = [Tuple(1, 2, 3), Tuple(4, 5, 6), Tuple(7, 8, 9)].into();
     dst.clone_from(&src);
     assert_eq!(dst, src);
@@ -430,6 +614,56 @@ pub fn align_attribute() {
     assert_eq!(soa, aligns_array.as_slice());
 }
 
+#[test]
+fn struct_align_attribute() {
+    #[derive(Debug, Clone, Copy, PartialEq, Soars)]
+    #[soa_derive(Debug, PartialEq)]
+    #[soa(align = 64)]
+    struct StructAlignment {
+        a: f32,
+        b: f32,
+        c: f32,
+        d: f32,
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq, Soars)]
+    #[soa_derive(Debug, PartialEq)]
+    #[soa(align = 64)]
+    struct Overridden(#[align(8)] u8, u8);
+
+    for cap in [1, 3, 17] {
+        let (layout, offsets) = StructAlignmentSoaRaw::layout_and_offsets(cap).unwrap();
+        assert_eq!(
+            (layout, offsets),
+            AlignmentSoaRaw::layout_and_offsets(cap).unwrap()
+        );
+        assert_eq!(layout.align(), 64);
+        assert!(offsets.iter().all(|offset| offset % 64 == 0));
+    }
+
+    let soa: Soa<_> = (0..3)
+        .map(|i| StructAlignment {
+            a: i as f32,
+            b: 0.0,
+            c: 0.0,
+            d: 0.0,
+        })
+        .collect();
+    for ptr in [
+        soa.a().as_ptr(),
+        soa.b().as_ptr(),
+        soa.c().as_ptr(),
+        soa.d().as_ptr(),
+    ] {
+        assert_eq!(ptr as usize % 64, 0);
+    }
+    assert_eq!(soa.a(), [0.0, 1.0, 2.0]);
+
+    let (layout, offsets) = OverriddenSoaRaw::layout_and_offsets(3).unwrap();
+    assert_eq!(layout.align(), 64);
+    assert_eq!(offsets, [64]);
+}
+
 #[test]
 pub fn iterator_slice_methods() {
     let mut soa = Soa::from(ABCDE);
@@ -516,6 +750,79 @@ fn iterator_nth_back() {
     }
 }
 
+#[test]
+fn iterator_nth_owned() {
+    struct Logged(u8, Rc<RefCell<Vec<u8>>>);
+
+    impl Drop for Logged {
+        fn drop(&mut self) {
+            self.1.borrow_mut().push(self.0);
+            if self.0 == 1 {
+                panic!("dropped 1");
+            }
+        }
+    }
+
+    #[derive(Soars)]
+    struct Item {
+        logged: Logged,
+    }
+
+    let log = Rc::new(RefCell::new(vec![]));
+    let items = |range: std::ops::Range<u8>| -> Soa<Item> {
+        range
+            .map(|n| Item {
+                logged: Logged(n, log.clone()),
+            })
+            .collect()
+    };
+
+    // A panicking destructor must not cause the skipped elements to be
+    // dropped again when the iterator is dropped
+    let mut iter = items(0..5).into_iter();
+    let nth = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        iter.nth(3).map(|item| item.logged.0)
+    }));
+    assert!(nth.is_err());
+    assert_eq!(log.take(), [0, 1, 2, 3]);
+    drop(iter);
+    assert_eq!(log.take(), [4]);
+
+    // Skipping past the end drops the remaining elements
+    let soa = items(5..10);
+    assert_eq!(soa.idx(0).logged.0, 5);
+    let mut iter = soa.into_iter();
+    assert!(iter.nth(7).is_none());
+    assert_eq!(log.take(), [5, 6, 7, 8, 9]);
+    drop(iter);
+    assert!(log.take().is_empty());
+}
+
+#[test]
+fn iter_nth() {
+    let soa: Soa<El> = ABCDE.into();
+    let mut iter = soa.iter();
+    assert_eq!(iter.nth(1), Some(B.as_soa_ref()));
+    assert_eq!(iter.nth_back(1), Some(D.as_soa_ref()));
+    assert_eq!(iter.next(), Some(C.as_soa_ref()));
+    assert_eq!(iter.nth(5), None);
+
+    let drops = Rc::new(Cell::new(0));
+    let soa: Soa<Counted> = (0..5)
+        .map(|n| Counted {
+            n,
+            drops: DropCounter(drops.clone()),
+        })
+        .collect();
+    let mut iter = soa.into_iter();
+    assert_eq!(iter.nth(1).map(|el| el.n), Some(1));
+    assert_eq!(drops.get(), 2);
+    assert_eq!(iter.nth_back(1).map(|el| el.n), Some(3));
+    assert_eq!(drops.get(), 4);
+    drop(iter);
+    assert_eq!(drops.get(), 5);
+}
+
 #[test]
 fn iterator_next_back() {
     let soa: Soa<_> = ABCDE.into();
@@ -550,121 +857,2943 @@ fn chunks_exact() {
 }
 
 #[test]
-fn array_eq() {
-    let array = ABCDE_SOA;
-    assert_eq!(array.as_slice(), array);
+fn enumerate_from() {
+    let soa: Soa<_> = ABCDE.into();
+    let base = 1;
+    let sub = soa.idx(base..4);
+    let mut count = 0;
+    for (i, el) in sub.enumerate_from(base) {
+        assert_eq!(el, soa.idx(i));
+        count += 1;
+    }
+    assert_eq!(count, 3);
 }
 
 #[test]
-fn array_slice_mut() {
-    let mut array = ABCDE_SOA;
-    let mut slice = array.as_mut_slice();
-    for item in slice.iter_mut() {
-        *item.foo += 10;
-        *item.bar += 10;
+fn binary_search() {
+    let empty = Soa::<El>::new();
+    assert_eq!(empty.binary_search_by_key(&0, |el| *el.foo), Err(0));
+
+    let single = soa![C];
+    assert_eq!(single.binary_search_by_key(&8, |el| *el.foo), Ok(0));
+    assert_eq!(single.binary_search_by_key(&7, |el| *el.foo), Err(0));
+    assert_eq!(single.binary_search_by_key(&9, |el| *el.foo), Err(1));
+
+    let soa: Soa<_> = ABCDE.into();
+    for (i, el) in ABCDE.iter().enumerate() {
+        assert_eq!(soa.binary_search_by_key(&el.foo, |el| *el.foo), Ok(i));
     }
-    let expected = ElArray::from_array(ABCDE.map(|el| El {
-        foo: el.foo + 10,
-        bar: el.bar + 10,
-        baz: el.baz,
-    }));
-    assert_eq!(slice, expected.as_slice());
+    assert_eq!(soa.binary_search_by_key(&5, |el| *el.foo), Err(2));
+    assert_eq!(soa.binary_search_by_key(&100, |el| *el.foo), Err(5));
+
+    let dupes = soa![A, B, B, B, C];
+    let found = dupes.binary_search_by_key(&B.foo, |el| *el.foo).unwrap();
+    assert!((1..4).contains(&found));
 }
 
 #[test]
-fn slices() {
-    let soa = Soa::from(ABCDE);
-    let slices = soa.slices();
-    assert_eq!(slices.foo, soa.foo());
-    assert_eq!(slices.bar, soa.bar());
-    assert_eq!(slices.baz, soa.baz());
+fn debug_field_stats() {
+    #[derive(Soars)]
+    #[soa_stats]
+    struct Sample {
+        count: u32,
+        weight: f64,
+    }
+
+    let counts = [4, 1, 7, 2];
+    let weights = [0.5, -2.0, 3.25, 1.0];
+    let soa: Soa<_> = counts
+        .into_iter()
+        .zip(weights)
+        .map(|(count, weight)| Sample { count, weight })
+        .collect();
+
+    assert_eq!(*soa.idx(2).count, 7);
+    assert_eq!(*soa.idx(2).weight, 3.25);
+
+    let stats = soa.debug_field_stats();
+    assert_eq!(stats.count.min, Some(1));
+    assert_eq!(stats.count.max, Some(7));
+    assert_eq!(stats.count.sum, 14.0);
+    assert_eq!(stats.count.mean, Some(3.5));
+    assert_eq!(stats.weight.min, Some(-2.0));
+    assert_eq!(stats.weight.max, Some(3.25));
+    assert_eq!(stats.weight.sum, 2.75);
+    assert_eq!(stats.weight.mean, Some(2.75 / 4.0));
+
+    let empty = Soa::<Sample>::new().debug_field_stats();
+    assert_eq!(empty.count.min, None);
+    assert_eq!(empty.weight.mean, None);
 }
 
 #[test]
-fn slices_mut() {
-    let mut soa = Soa::from(ABCDE);
-    let slices = soa.slices_mut();
-    for foo in slices.foo {
-        *foo += 10;
-    }
-    for bar in slices.bar {
-        *bar += 10;
+fn par_chunks_exact_mut() {
+    use rayon::prelude::*;
+
+    let original: Soa<_> = (0..1003).map(|i| Tuple(0, 0, i)).collect();
+
+    let mut sequential = original.clone();
+    for x in &mut sequential.f2_mut()[..1000] {
+        *x *= 3;
     }
 
-    let expected = ABCDE.map(|el| el.foo + 10);
-    assert_eq!(soa.foo(), expected);
+    let mut parallel = original.clone();
+    parallel.par_chunks_exact_mut(8).for_each(|mut chunk| {
+        for x in chunk.f2_mut() {
+            *x *= 3;
+        }
+    });
+    assert_eq!(parallel, sequential);
+
+    let chunks = parallel.par_chunks_exact(8);
+    assert_eq!(chunks.len(), 125);
+    assert_eq!(chunks.remainder(), &original.idx(1000..));
+    let sum: u32 = chunks.map(|chunk| chunk.f2().iter().sum::<u32>()).sum();
+    assert_eq!(sum, (0..1000).map(|i| i * 3).sum::<u32>());
+}
 
-    let expected = ABCDE.map(|el| el.bar + 10);
-    assert_eq!(soa.bar(), expected);
+#[test]
+fn par_iter() {
+    use rayon::prelude::*;
+
+    let mut soa: Soa<_> = (0..1003).map(|i| Tuple(i as u8, 0, i)).collect();
+    let sequential: u32 = soa.f2().iter().sum();
+    let parallel: u32 = soa.par_iter().map(|el| *el.2).sum();
+    assert_eq!(parallel, sequential);
+    assert_eq!(soa.par_iter().len(), 1003);
+
+    soa.par_iter_mut().for_each(|el| *el.2 *= 3);
+    let expected: Vec<u32> = (0..1003).map(|i| i * 3).collect();
+    assert_eq!(soa.f2(), expected.as_slice());
+
+    let rev: Vec<u32> = soa.idx(..4).par_iter().rev().map(|el| *el.2).collect();
+    assert_eq!(rev, [9, 6, 3, 0]);
 }
 
 #[test]
-fn array_with_box() {
-    #[derive(Soars)]
-    #[soa_array]
-    #[soa_derive(PartialEq)]
-    struct Example {
-        foo: Box<u8>,
+fn sort_by() {
+    use std::cmp::Reverse;
+
+    let order = [3, 0, 4, 1, 2, 0, 3];
+    let mut vec: Vec<_> = order.iter().map(|&i| ABCDE[i].clone()).collect();
+    let mut soa: Soa<_> = vec.iter().cloned().collect();
+    vec.sort_by_key(|el| Reverse(el.bar));
+    soa.sort_by(|a, b| b.bar.cmp(a.bar));
+    assert_eq!(soa, Soa::from(vec.as_slice()));
+
+    // Stable with respect to elements that compare equal
+    let mut soa = soa![
+        Tuple(1, 0, 0),
+        Tuple(0, 1, 0),
+        Tuple(1, 2, 0),
+        Tuple(0, 3, 0)
+    ];
+    soa.sort_by_key(|el| *el.0);
+    assert_eq!(
+        soa,
+        soa![
+            Tuple(0, 1, 0),
+            Tuple(0, 3, 0),
+            Tuple(1, 0, 0),
+            Tuple(1, 2, 0)
+        ]
+    );
+
+    let mut empty = Soa::<El>::new();
+    empty.sort_by_key(|el| *el.foo);
+    assert!(empty.is_empty());
+}
+
+#[test]
+fn rotate() {
+    for mid in 0..=ABCDE.len() {
+        let mut vec = ABCDE.to_vec();
+        let mut soa = Soa::from(ABCDE);
+        vec.rotate_left(mid);
+        soa.rotate_left(mid);
+        assert_eq!(soa, Soa::from(vec.as_slice()));
+
+        let mut vec = ABCDE.to_vec();
+        let mut soa = Soa::from(ABCDE);
+        vec.rotate_right(mid);
+        soa.rotate_right(mid);
+        assert_eq!(soa, Soa::from(vec.as_slice()));
     }
-    let foo = Box::new(42_u8);
-    let x = ExampleArray::from_array([Example { foo }]);
-    let s = x.as_slice();
-    let _ = s.get(0).unwrap().foo;
+
+    let mut soa = Soa::from(ABCDE);
+    soa.rotate_left(ABCDE.len());
+    assert_eq!(soa, Soa::from(ABCDE));
+    soa.rotate_right(ABCDE.len());
+    assert_eq!(soa, Soa::from(ABCDE));
 }
 
-fn assert_send<T: Send>(_t: T) {}
-fn assert_sync<T: Sync>(_t: T) {}
+#[test]
+fn reverse() {
+    let mut soa = Soa::from(ABCDE);
+    soa.reverse();
+    assert_eq!(soa, Soa::from([E, D, C, B, A].as_slice()));
+    soa.reverse();
+    assert_eq!(soa, Soa::from(ABCDE));
+
+    soa.idx_mut(1..3).reverse();
+    assert_eq!(soa, Soa::from([A, C, B, D, E].as_slice()));
+
+    let mut empty = Soa::<El>::new();
+    empty.reverse();
+    assert!(empty.is_empty());
+
+    let mut units = soa![Unit, Unit, Unit];
+    units.reverse();
+    assert_eq!(units.len(), 3);
+}
 
 #[test]
-fn send_sync() {
-    assert_send(soa![A]);
-    assert_sync(soa![A]);
+#[should_panic]
+fn rotate_out_of_bounds() {
+    let mut soa = Soa::from(ABCDE);
+    soa.rotate_left(6);
 }
 
 #[test]
-#[allow(unused)]
-fn field_type_without_partial_eq() {
-    // See https://github.com/tim-harding/soa-rs/issues/10
+fn small_soa_inline() {
+    let before = allocations();
+    let mut small = SmallSoa::<El, 3>::new();
+    small.extend([A, B, C]);
+    assert!(!small.spilled());
+    assert_eq!(small.capacity(), 3);
+    assert_eq!(allocations(), before);
+    assert_eq!(small, soa![A, B, C]);
+    small.as_mut_slice().foo_mut()[1] = 100;
+    assert_eq!(small.as_slice().foo(), [0, 100, 8]);
+    assert_eq!(small.pop(), Some(C));
+    assert_eq!(small.len(), 2);
+}
 
-    struct TypeWithoutPartialEq {
-        foo: u8,
-    }
+#[test]
+fn small_soa_spill() {
+    let mut small: SmallSoa<El, 2> = ABCDE.into_iter().collect();
+    assert!(small.spilled());
+    assert!(small.capacity() >= 5);
+    assert_eq!(small, ABCDE_SOA);
+    small.clear();
+    assert!(small.is_empty());
+    assert!(small.spilled());
+}
 
-    #[derive(Soars)]
-    struct Example {
-        a: TypeWithoutPartialEq,
-    }
+#[test]
+fn small_soa_deref() {
+    let mut small = SmallSoa::<El, 3>::new();
+    small.extend([A, B]);
+    small.foo_mut()[0] = 10;
+    assert_eq!(small.foo(), [10, 4]);
+
+    // Mutating through the container must not leave the slice holding
+    // pointers from an earlier shared borrow
+    small.push(C);
+    assert_eq!(small.foo(), [10, 4, 8]);
+    small.as_mut_slice().foo_mut()[2] = 9;
+    assert_eq!(small.foo(), [10, 4, 9]);
+    assert_eq!(small.pop(), Some(El { foo: 9, ..C }));
+    assert_eq!(small.foo(), [10, 4]);
+
+    // Moving the container must not leave the slice pointing at the old
+    // inline storage
+    let mut moved = Box::new(small);
+    assert_eq!(moved.bar(), [1, 5]);
+    moved.bar_mut()[1] = 20;
+    let moved = *moved;
+    assert_eq!(moved.idx(1).bar, &20);
+    assert_eq!(moved.len(), 2);
+
+    let mut spilled: SmallSoa<El, 2> = ABCDE.into_iter().collect();
+    spilled.swap(0, 4);
+    assert_eq!(spilled.foo(), [16, 4, 8, 12, 0]);
 }
 
 #[test]
-fn serde() {
-    #[derive(Soars, serde::Deserialize)]
-    #[soa_derive(Debug, PartialEq)]
-    #[soa_derive(include(Ref), serde::Serialize)]
-    struct Test {
-        n: i32,
-        s: String,
-    }
+fn small_soa_drops() {
+    let drops = Rc::new(Cell::new(0));
+    let counted = |n| Counted {
+        n,
+        drops: DropCounter(drops.clone()),
+    };
 
-    let original = soa![
-        Test {
-            n: 10,
-            s: "Hello".to_string()
-        },
-        Test {
-            n: 20,
-            s: "Serde".to_string()
+    let mut small = SmallSoa::<Counted, 2>::new();
+    small.push(counted(0));
+    small.push(counted(1));
+    drop(small);
+    assert_eq!(drops.get(), 2);
+
+    drops.set(0);
+    let mut small = SmallSoa::<Counted, 2>::new();
+    small.extend((0..3).map(counted));
+    assert!(small.spilled());
+    assert_eq!(drops.get(), 0);
+    assert_eq!(small.iter().map(|el| *el.n).collect::<Vec<_>>(), [0, 1, 2]);
+    assert!(small.iter().all(|el| Rc::ptr_eq(&el.drops.0, &drops)));
+    drop(small.pop());
+    assert_eq!(drops.get(), 1);
+    drop(small);
+    assert_eq!(drops.get(), 3);
+}
+
+#[test]
+fn dedup() {
+    let mut soa: Soa<El> = [A, A, B, C, C, C, A, D, D].into();
+    soa.dedup();
+    assert_eq!(soa, soa![A, B, C, A, D]);
+
+    let mut soa: Soa<El> = [B, B, B, B].into();
+    soa.dedup();
+    assert_eq!(soa, soa![B]);
+
+    let mut soa = Soa::<El>::new();
+    soa.dedup();
+    assert_eq!(soa, soa![]);
+}
+
+#[test]
+fn dedup_by_key() {
+    let mut soa: Soa<El> = ABCDE.into();
+    soa.dedup_by_key(|el| el.foo / 8);
+    assert_eq!(soa, soa![A, C, E]);
+
+    let mut soa: Soa<El> = ABCDE.into();
+    soa.dedup_by_key(|_| ());
+    assert_eq!(soa, soa![A]);
+}
+
+#[test]
+fn dedup_by() {
+    let mut soa = soa![
+        A,
+        El { bar: 2, ..A },
+        B,
+        El { bar: 6, ..B },
+        El { bar: 7, ..B },
+        C,
+    ];
+    assert_eq!(soa.dedup_by(|a, b| a.foo == b.foo), 3);
+    assert_eq!(soa, soa![A, B, C]);
+    assert_eq!(soa.dedup_by(|a, b| a.foo == b.foo), 0);
+
+    let mut soa = Soa::<El>::new();
+    assert_eq!(soa.dedup_by(|_, _| true), 0);
+}
+
+#[test]
+fn dedup_by_key_panic() {
+    let mut soa: Soa<El> = ABCDE.into();
+    let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        soa.dedup_by_key(|el| {
+            if *el.foo == 12 {
+                panic!("key function panicked");
+            }
+            el.foo / 8
+        })
+    }));
+    assert!(result.is_err());
+    assert_eq!(soa, soa![A, C, D, E]);
+}
+
+#[test]
+fn find_mut() {
+    let mut soa: Soa<El> = ABCDE.into();
+    let found = soa.find_mut(|el| *el.foo > 6).unwrap();
+    *found.bar = 100;
+    assert_eq!(soa.bar(), [1, 5, 100, 13, 17]);
+    assert!(soa.find_mut(|el| *el.foo > 16).is_none());
+}
+
+#[test]
+fn map_in_place() {
+    let soa: Soa<El> = ABCDE.into();
+    let before = allocations();
+    let soa = soa.into_iter().map_in_place(|mut el| {
+        el.foo += 1;
+        el
+    });
+    assert_eq!(allocations(), before);
+    assert_eq!(soa.foo(), [1, 5, 9, 13, 17]);
+    assert_eq!(soa.bar(), [1, 5, 9, 13, 17]);
+
+    let mut iter = soa.into_iter();
+    iter.next();
+    iter.next_back();
+    let soa = iter.map_in_place(|el| el);
+    assert_eq!(allocations(), before);
+    assert_eq!(soa.foo(), [5, 9, 13]);
+    assert!(soa.capacity() >= 5);
+}
+
+#[test]
+fn map_in_place_panic() {
+    let drops = Rc::new(Cell::new(0));
+    let soa: Soa<Counted> = (0..5)
+        .map(|n| Counted {
+            n,
+            drops: DropCounter(drops.clone()),
+        })
+        .collect();
+    let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        soa.into_iter().map_in_place(|el| {
+            if el.n == 2 {
+                panic!("map function panicked");
+            }
+            el
+        })
+    }));
+    assert!(result.is_err());
+    assert_eq!(drops.get(), 5);
+}
+
+#[test]
+fn extend_from_refs() {
+    let src: Soa<El> = ABCDE.into();
+    let mut dst: Soa<El> = soa![A];
+    dst.extend(src.iter().skip(3));
+    assert_eq!(dst, soa![A, D, E]);
+
+    let collected: Soa<El> = src.iter().collect();
+    assert_eq!(collected, src);
+
+    let mut src = src;
+    let collected: Soa<El> = src.iter_mut().take(2).collect();
+    assert_eq!(collected, soa![A, B]);
+}
+
+#[test]
+fn position() {
+    let soa: Soa<El> = [A, B, C, B, E].into();
+    assert_eq!(soa.position(|el| *el.foo == 0), Some(0));
+    assert_eq!(soa.position(|el| *el.foo == 4), Some(1));
+    assert_eq!(soa.position(|el| *el.foo == 16), Some(4));
+    assert_eq!(soa.position(|el| *el.foo == 12), None);
+    assert_eq!(soa.rposition(|el| *el.foo == 0), Some(0));
+    assert_eq!(soa.rposition(|el| *el.foo == 4), Some(3));
+    assert_eq!(soa.rposition(|el| *el.foo == 16), Some(4));
+    assert_eq!(soa.rposition(|el| *el.foo == 12), None);
+}
+
+#[test]
+fn group_by_key() {
+    let soa: Soa<Tuple> = (0..10u8)
+        .map(|i| Tuple(i % 3, i.into(), i.into()))
+        .collect();
+    let groups = soa.group_by_key(|el| *el.0);
+    assert_eq!(groups.len(), 3);
+    assert_eq!(groups[&0].f1(), [0, 3, 6, 9]);
+    assert_eq!(groups[&1].f1(), [1, 4, 7]);
+    assert_eq!(groups[&2].f2(), [2, 5, 8]);
+    assert_eq!(groups.values().map(|group| group.len()).sum::<usize>(), 10);
+    assert!(groups
+        .iter()
+        .all(|(key, group)| group.f0().iter().all(|k| k == key)));
+}
+
+#[test]
+fn ordering_matches_vec() {
+    let cases: [&[(u8, u16, u32)]; 7] = [
+        &[],
+        &[(1, 2, 3)],
+        &[(1, 2, 3), (4, 5, 6)],
+        &[(1, 2, 3), (4, 5, 7)],
+        &[(1, 2, 3), (4, 5, 6), (0, 0, 0)],
+        &[(0, 9, 9)],
+        &[(1, 2, 3), (3, 5, 6)],
+    ];
+    for a in cases {
+        let soa_a: Soa<Tuple> = a.iter().map(|&(x, y, z)| Tuple(x, y, z)).collect();
+        for b in cases {
+            let soa_b: Soa<Tuple> = b.iter().map(|&(x, y, z)| Tuple(x, y, z)).collect();
+            assert_eq!(soa_a.cmp(&soa_b), a.cmp(b), "{a:?} cmp {b:?}");
+            assert_eq!(soa_a.partial_cmp(&soa_b), a.partial_cmp(b));
+            assert_eq!(soa_a == soa_b, a == b);
+            assert_eq!(
+                soa_a.cmp_by(&soa_b, |x, y| x.2.cmp(y.2)),
+                a.iter().map(|t| t.2).cmp(b.iter().map(|t| t.2)),
+            );
+            assert_eq!(
+                soa_a.eq_by(&soa_b, |x, y| x.0 == y.0),
+                a.iter().map(|t| t.0).eq(b.iter().map(|t| t.0)),
+            );
         }
+    }
+}
+
+#[test]
+fn windows() {
+    let soa: Soa<El> = ABCDE.into();
+    let foos: Vec<_> = soa.windows(3).map(|window| window.foo().to_vec()).collect();
+    assert_eq!(foos, [[0, 4, 8], [4, 8, 12], [8, 12, 16]]);
+    let bars: Vec<_> = soa.windows(2).map(|window| window.bar().to_vec()).collect();
+    assert_eq!(bars, [[1, 5], [5, 9], [9, 13], [13, 17]]);
+    assert_eq!(soa.windows(5).count(), 1);
+    assert_eq!(soa.windows(6).count(), 0);
+}
+
+#[test]
+#[should_panic]
+fn windows_zero() {
+    let soa: Soa<El> = ABCDE.into();
+    soa.windows(0);
+}
+
+#[test]
+fn for_each_field_bytes() {
+    let soa: Soa<Tuple> = [Tuple(1, 2, 3), Tuple(4, 5, 6)].into();
+    let mut columns = vec![];
+    soa.for_each_field_bytes(|name, bytes| columns.push((name, bytes.to_vec())));
+    let expected = vec![
+        ("0", vec![1, 4]),
+        (
+            "1",
+            [2u16, 5].iter().flat_map(|x| x.to_ne_bytes()).collect(),
+        ),
+        (
+            "2",
+            [3u32, 6].iter().flat_map(|x| x.to_ne_bytes()).collect(),
+        ),
     ];
+    assert_eq!(columns, expected);
+}
 
-    let serial = serde_json::to_string(&original).unwrap();
-    let deserial: Soa<Test> = serde_json::from_str(&serial).unwrap();
-    assert_eq!(original, deserial);
+#[test]
+fn chunks() {
+    let soa: Soa<El> = ABCDE.into();
+    let foos: Vec<_> = soa.chunks(2).map(|chunk| chunk.foo().to_vec()).collect();
+    assert_eq!(foos, [vec![0, 4], vec![8, 12], vec![16]]);
+    assert_eq!(soa.chunks(2).size_hint(), (3, Some(3)));
+    let foos: Vec<_> = soa.chunks(5).map(|chunk| chunk.foo().to_vec()).collect();
+    assert_eq!(foos, [vec![0, 4, 8, 12, 16]]);
+    assert_eq!(soa.chunks(8).count(), 1);
+    assert_eq!(Soa::<El>::new().chunks(2).count(), 0);
 }
 
 #[test]
-fn mutex() {
+fn chunks_mut() {
+    let mut soa: Soa<El> = [A, B, C, D, E, A].into();
+    for (i, mut chunk) in soa.chunks_mut(3).enumerate() {
+        for bar in chunk.bar_mut() {
+            *bar = i as u8;
+        }
+    }
+    assert_eq!(soa.bar(), [0, 0, 0, 1, 1, 1]);
+    let lens: Vec<_> = soa.chunks_mut(4).map(|chunk| chunk.len()).collect();
+    assert_eq!(lens, [4, 2]);
+}
+
+#[test]
+#[should_panic]
+fn chunks_zero() {
+    let soa: Soa<El> = ABCDE.into();
+    soa.chunks(0);
+}
+
+#[test]
+fn swap_remove_range() {
+    let mut soa: Soa<El> = [A, B, C, D, E, A, B].into();
+    let removed = soa.swap_remove_range(1..3);
+    assert_eq!(removed, soa![B, C]);
+    assert_eq!(soa, soa![A, A, B, D, E]);
+
+    let mut soa: Soa<El> = ABCDE.into();
+    let removed = soa.swap_remove_range(1..=3);
+    assert_eq!(removed, soa![B, C, D]);
+    assert_eq!(soa, soa![A, E]);
+
+    let mut soa: Soa<El> = ABCDE.into();
+    let removed = soa.swap_remove_range(3..);
+    assert_eq!(removed, soa![D, E]);
+    assert_eq!(soa, soa![A, B, C]);
+
+    let mut soa: Soa<El> = ABCDE.into();
+    let removed = soa.swap_remove_range(2..2);
+    assert!(removed.is_empty());
+    assert_eq!(soa.len(), 5);
+}
+
+#[test]
+#[should_panic]
+fn swap_remove_range_out_of_bounds() {
+    let mut soa: Soa<El> = ABCDE.into();
+    soa.swap_remove_range(3..6);
+}
+
+#[test]
+#[should_panic(expected = "index out of bounds")]
+fn swap_remove_range_overflow() {
+    let mut soa: Soa<El> = ABCDE.into();
+    soa.swap_remove_range(..=usize::MAX);
+}
+
+#[test]
+fn chunks_exact_enumerate_offsets() {
+    let soa: Soa<Tuple> = (0..11u8).map(|i| Tuple(i, 0, 0)).collect();
+    let offsets: Vec<_> = soa
+        .chunks_exact(3)
+        .enumerate_offsets()
+        .map(|(offset, chunk)| {
+            assert_eq!(chunk.f0()[0] as usize, offset);
+            offset
+        })
+        .collect();
+    assert_eq!(offsets, [0, 3, 6]);
+
+    let mut iter = soa.chunks_exact(4);
+    iter.next();
+    let offsets: Vec<_> = iter.enumerate_offsets().map(|(offset, _)| offset).collect();
+    assert_eq!(offsets, [4]);
+}
+
+#[test]
+fn split_first_last() {
+    let mut soa: Soa<El> = ABCDE.into();
+    let (first, rest) = soa.split_first().unwrap();
+    assert_eq!(first, A.as_soa_ref());
+    assert_eq!(rest, soa![B, C, D, E]);
+    let (last, rest) = soa.split_last().unwrap();
+    assert_eq!(last, E.as_soa_ref());
+    assert_eq!(rest, soa![A, B, C, D]);
+
+    let (first, mut rest) = soa.split_first_mut().unwrap();
+    *first.foo = 100;
+    rest.foo_mut()[0] = 200;
+    let (last, rest) = soa.split_last_mut().unwrap();
+    *last.bar = 100;
+    assert_eq!(rest.len(), 4);
+    assert_eq!(soa.foo(), [100, 200, 8, 12, 16]);
+    assert_eq!(soa.bar(), [1, 5, 9, 13, 100]);
+
+    let mut soa: Soa<El> = soa![A];
+    let (first, rest) = soa.split_first().unwrap();
+    assert_eq!(first, A.as_soa_ref());
+    assert!(rest.is_empty());
+    soa.clear();
+    assert!(soa.split_first().is_none());
+    assert!(soa.split_last_mut().is_none());
+}
+
+#[test]
+fn content_hash() {
+    use std::hash::{DefaultHasher, Hasher};
+
+    let content_hash = |soa: &Soa<El>| {
+        let mut hasher = DefaultHasher::new();
+        soa.content_hash(&mut hasher);
+        hasher.finish()
+    };
+
+    let mut a = Soa::<El>::with_capacity(5);
+    a.extend(ABCDE);
+    let mut b = Soa::<El>::with_capacity(64);
+    b.extend([A, B]);
+    b.extend([C, D, E]);
+    let c: Soa<El> = ABCDE.into_iter().rev().collect();
+    assert_ne!(a.capacity(), b.capacity());
+    assert_eq!(content_hash(&a), content_hash(&b));
+    assert_ne!(content_hash(&a), content_hash(&c));
+}
+
+#[test]
+fn soa_skip() {
+    let skipped = |i: u8| Skipped {
+        foo: i.into(),
+        name: i.to_string(),
+        bar: i,
+        baz: SingleDrop::DEFAULT,
+    };
+    let elements: Vec<_> = (0..10).map(skipped).collect();
+    let mut soa: Soa<Skipped> = elements.iter().cloned().collect();
+    assert_eq!(soa.foo(), (0..10).collect::<Vec<_>>());
+    assert_eq!(soa.bar(), (0..10).collect::<Vec<_>>());
+    assert_eq!(soa.bar().as_ptr() as usize % 64, 0);
+    assert!(soa.iter().zip(&elements).all(|(a, b)| a.name == &b.name));
+
+    *soa.idx_mut(3).name = "three".to_string();
+    soa.insert(1, skipped(100));
+    soa.remove(0);
+    soa.shrink_to_fit();
+    let mut expected = elements.clone();
+    expected[3].name = "three".to_string();
+    expected[0] = skipped(100);
+    assert_eq!(soa.into_iter().collect::<Vec<_>>(), expected);
+}
+
+#[test]
+fn soa_flatten() {
+    let body = |i: u8| {
+        let i = f32::from(i);
+        let vector = |offset: f32| Vector3 {
+            x: i + offset,
+            y: i + offset + 0.25,
+            z: i + offset + 0.5,
+        };
+        Body {
+            pos: vector(0.0),
+            vel: Velocity(vector(100.0)),
+            mass: -i,
+        }
+    };
+    let elements: Vec<_> = (0..10).map(body).collect();
+    let mut soa: Soa<Body> = elements.iter().copied().collect();
+    soa.insert(2, body(20));
+    soa.remove(2);
+    soa.shrink_to_fit();
+
+    let position = soa.pos();
+    let velocity = soa.vel().0;
+    let columns = [
+        position.x,
+        position.y,
+        position.z,
+        velocity.x,
+        velocity.y,
+        velocity.z,
+        soa.mass(),
+    ];
+    for (i, column) in columns.iter().enumerate() {
+        assert_eq!(column.len(), elements.len());
+        for other in &columns[i + 1..] {
+            let end = column.as_ptr_range().end;
+            assert!(end <= other.as_ptr() || column.as_ptr() >= other.as_ptr_range().end);
+        }
+    }
+    assert_eq!(soa.mass().as_ptr() as usize % 32, 0);
+    assert_eq!(
+        position.y,
+        elements.iter().map(|b| b.pos.y).collect::<Vec<_>>()
+    );
+    assert_eq!(
+        velocity.z,
+        elements.iter().map(|b| b.vel.0.z).collect::<Vec<_>>()
+    );
+
+    let item = soa.idx(3);
+    assert_eq!(*item.pos.x, 3.0);
+    assert_eq!(*item.vel.0.y, 103.25);
+    *soa.idx_mut(4).vel.0.x = 0.0;
+    soa.pos_mut().z[5] = 1.0;
+    let mut expected = elements.clone();
+    expected[4].vel.0.x = 0.0;
+    expected[5].pos.z = 1.0;
+    assert_eq!(soa.aos_iter().collect::<Vec<_>>(), expected);
+    assert_eq!(soa.into_iter().collect::<Vec<_>>(), expected);
+}
+
+#[test]
+fn aos_iter() {
+    let soa: Soa<El> = ABCDE.into();
+    let vec: Vec<El> = soa.aos_iter().collect();
+    assert_eq!(vec, ABCDE);
+    let rev: Vec<El> = soa.aos_iter().rev().collect();
+    assert_eq!(rev, [E, D, C, B, A]);
+    assert_eq!(soa.aos_iter().len(), 5);
+}
+
+#[test]
+fn simd_padding() {
+    let mut soa = Soa::<Tuple>::with_simd_padding::<8>(13);
+    assert_eq!(soa.capacity() % 8, 0);
+    assert!(soa.capacity() >= 13);
+    soa.extend((0..13).map(|i| Tuple(i, i.into(), i.into())));
+    assert_eq!(soa.len(), 13);
+    assert_eq!(soa.capacity(), 16);
+
+    let slices = soa.padded_slices_mut::<8>();
+    assert_eq!(slices.0.len(), 16);
+    assert_eq!(slices.2[13..], [0, 0, 0]);
+    slices.1.iter_mut().for_each(|x| *x += 1);
+    assert_eq!(soa.len(), 13);
+    assert_eq!(soa.f1(), (1..14).collect::<Vec<_>>());
+
+    soa.push(Tuple(13, 14, 13));
+    soa.extend([Tuple(14, 15, 14), Tuple(15, 16, 15), Tuple(16, 17, 16)]);
+    let slices = soa.padded_slices_mut::<8>();
+    assert_eq!(slices.0.len(), 24);
+    assert_eq!(soa.capacity() % 8, 0);
+    assert_eq!(soa.len(), 17);
+}
+
+#[test]
+fn get_disjoint_mut() {
+    let mut soa: Soa<El> = ABCDE.into();
+    assert!(soa.get_disjoint_mut([0, 2, 0]).is_none());
+    assert!(soa.get_disjoint_mut([1, 5]).is_none());
+
+    let [a, c, e] = soa.get_disjoint_mut([0, 2, 4]).unwrap();
+    *a.foo = 100;
+    *c.bar = 200;
+    std::mem::swap(a.foo, e.foo);
+    assert_eq!(soa.foo(), [E.foo, B.foo, C.foo, D.foo, 100]);
+    assert_eq!(soa.bar(), [A.bar, B.bar, 200, D.bar, E.bar]);
+}
+
+#[test]
+fn vec_round_trip() {
+    let vec = ABCDE.to_vec();
+    let soa = Soa::from(vec.clone());
+    assert_eq!(soa, ABCDE_SOA);
+    let round_trip: Vec<El> = soa.into();
+    assert_eq!(round_trip, vec);
+    assert_eq!(Vec::from(Soa::<El>::new()), []);
+}
+
+#[test]
+fn eq_short_circuits() {
+    let compared =
+        |n: &[u8]| -> Soa<Compared> { n.iter().map(|&n| Compared(EqCounter(n))).collect() };
+    let a = compared(&[1, 2, 3, 4, 5]);
+    let b = compared(&[1, 9, 3, 4, 5]);
+
+    let before = comparisons();
+    assert_ne!(a, b);
+    assert_eq!(comparisons() - before, 2);
+
+    let before = comparisons();
+    assert_eq!(a, compared(&[1, 2, 3, 4, 5]));
+    assert_eq!(comparisons() - before, 5);
+
+    let shorter = compared(&[1, 2, 3]);
+    let before = comparisons();
+    assert_ne!(a, shorter);
+    assert_ne!(shorter, a);
+    assert_ne!(a.idx(..4), a.idx(..));
+    assert_ne!(Soa::<Compared>::new(), a);
+    assert_eq!(comparisons() - before, 0);
+}
+
+#[test]
+fn as_field_slices() {
+    let mut soa: Soa<Tuple> = (0..5).map(|i| Tuple(i, i.into(), i.into())).collect();
+    let (a, b, c) = soa.as_field_slices();
+    assert_eq!(a, soa.f0());
+    assert_eq!(b, soa.f1());
+    assert_eq!(c, soa.f2());
+
+    let (a, b, c) = soa.as_field_slices_mut();
+    a[0] = 10;
+    b.swap(1, 2);
+    c.fill(7);
+    assert_eq!(soa.f0(), [10, 1, 2, 3, 4]);
+    assert_eq!(soa.f1(), [0, 2, 1, 3, 4]);
+    assert_eq!(soa.f2(), [7; 5]);
+
+    let mut velocity = Soa::<Velocity>::new();
+    velocity.push(Velocity(Vector3 {
+        x: 1.0,
+        y: 2.0,
+        z: 3.0,
+    }));
+    let (vector,) = velocity.as_field_slices();
+    assert_eq!(vector.y, [2.0]);
+}
+
+#[test]
+fn rfold() {
+    let mut soa: Soa<El> = ABCDE.into();
+    let manual = |mut iter: soa_rs::Iter<'_, El>| {
+        let mut acc = vec![];
+        while let Some(el) = iter.next_back() {
+            acc.push(*el.foo);
+        }
+        acc
+    };
+    let push = |mut acc: Vec<u64>, el: ElRef<'_>| {
+        acc.push(*el.foo);
+        acc
+    };
+    assert_eq!(soa.iter().rfold(vec![], push), manual(soa.iter()));
+
+    let mut iter = soa.iter();
+    iter.next();
+    iter.next_back();
+    assert_eq!(iter.rfold(vec![], push), [D.foo, C.foo, B.foo]);
+    assert_eq!(
+        Soa::<El>::new().iter().rfold(vec![], push),
+        Vec::<u64>::new()
+    );
+
+    let sum = soa
+        .iter_mut()
+        .rfold(0, |acc, el| acc * 2 + usize::from(*el.bar));
+    assert_eq!(
+        sum,
+        ABCDE
+            .iter()
+            .rev()
+            .fold(0, |acc, el| acc * 2 + usize::from(el.bar))
+    );
+    let bars: Vec<_> = soa.into_iter().rfold(vec![], |mut acc, el| {
+        acc.push(el.bar);
+        acc
+    });
+    assert_eq!(bars, [E.bar, D.bar, C.bar, B.bar, A.bar]);
+}
+
+#[test]
+fn column_bytes_round_trip() {
+    let soa: Soa<Particle> = (0..10u8)
+        .map(|i| Particle {
+            id: i,
+            pos: [i.into(), f32::from(i) * 2.0, -f32::from(i)],
+            mass: f64::from(i) / 4.0,
+        })
+        .collect();
+    let columns = soa.as_column_bytes();
+    assert_eq!(
+        columns.iter().map(|(name, _)| *name).collect::<Vec<_>>(),
+        ["id", "pos", "mass"]
+    );
+    assert_eq!(columns[0].1, (0..10).collect::<Vec<u8>>());
+    assert_eq!(columns[2].1.len(), 80);
+
+    // Copy into unaligned, reordered storage as if read from a file
+    let mut storage = vec![0u8];
+    let mut ranges = vec![];
+    for (name, bytes) in columns.iter().rev() {
+        let start = storage.len();
+        storage.extend_from_slice(bytes);
+        ranges.push((*name, start..storage.len()));
+    }
+    let owned: Vec<_> = ranges
+        .into_iter()
+        .map(|(name, range)| (name, &storage[range]))
+        .collect();
+    let rebuilt = unsafe { Soa::<Particle>::from_column_bytes(&owned) };
+    assert_eq!(rebuilt, soa);
+
+    let empty =
+        unsafe { Soa::<Particle>::from_column_bytes(&[("id", &[]), ("pos", &[]), ("mass", &[])]) };
+    assert!(empty.is_empty());
+}
+
+#[test]
+#[should_panic(expected = "column `pos` has the wrong length")]
+fn column_bytes_wrong_length() {
+    let columns = [
+        ("id", [1u8, 2].as_slice()),
+        ("pos", &[0; 12]),
+        ("mass", &[0; 16]),
+    ];
+    let _ = unsafe { Soa::<Particle>::from_column_bytes(&columns) };
+}
+
+#[test]
+fn index_of() {
+    let soa = soa![A, B, C, B.clone(), D];
+    assert_eq!(soa.index_of(C), Some(2));
+    assert_eq!(soa.index_of(B.clone()), Some(1));
+    assert_eq!(soa.index_of(soa.idx(3)), Some(1));
+    assert_eq!(soa.index_of(E), None);
+    assert_eq!(Soa::<El>::new().index_of(A), None);
+}
+
+#[test]
+fn from_sorted_by_key() {
+    let unordered = [7u8, 3, 9, 1, 3, 0, 5].map(|i| Tuple(i, u16::from(10 - i), u32::from(i) * 2));
+    let soa = Soa::from_sorted_by_key(unordered, |t| *t.1);
+    assert_eq!(soa.f1(), [1, 3, 5, 7, 7, 9, 10]);
+    assert_eq!(soa.f0(), [9, 7, 5, 3, 3, 1, 0]);
+    assert!(soa
+        .f2()
+        .iter()
+        .zip(soa.f0())
+        .all(|(&c, &a)| c == u32::from(a) * 2));
+}
+
+#[test]
+fn bytemuck_field_bytes() {
+    #[derive(Soars)]
+    #[soa_derive(Debug, PartialEq)]
+    struct Vertex {
+        position: [f32; 3],
+        color: u32,
+    }
+
+    let mut soa: Soa<Vertex> = (0..5u8)
+        .map(|i| Vertex {
+            position: [i.into(); 3],
+            color: i.into(),
+        })
+        .collect();
+    assert_eq!(
+        soa.position_bytes().len(),
+        soa.len() * size_of::<[f32; 3]>()
+    );
+    assert_eq!(soa.color_bytes().len(), soa.len() * size_of::<u32>());
+    assert_eq!(soa.color_bytes()[4..8], 1u32.to_ne_bytes());
+
+    soa.color_bytes_mut()[..4].copy_from_slice(&42u32.to_ne_bytes());
+    assert_eq!(soa.color(), [42, 1, 2, 3, 4]);
+
+    let tuple = soa![Tuple(1, 2, 3)];
+    assert_eq!(tuple.f1_bytes(), 2u16.to_ne_bytes());
+}
+
+#[test]
+fn drop_order() {
+    take_drop_log();
+    let soa = soa![
+        DropsReverse(DropLogger(0)),
+        DropsReverse(DropLogger(1)),
+        DropsReverse(DropLogger(2)),
+    ];
+    drop(soa);
+    assert_eq!(take_drop_log(), [2, 1, 0]);
+
+    let soa = soa![
+        DropsForward(DropLogger(0)),
+        DropsForward(DropLogger(1)),
+        DropsForward(DropLogger(2)),
+    ];
+    drop(soa);
+    assert_eq!(take_drop_log(), [0, 1, 2]);
+
+    let mut soa = soa![DropsForward(DropLogger(3)), DropsForward(DropLogger(4))];
+    soa.clear();
+    assert_eq!(take_drop_log(), [3, 4]);
+    assert!(soa.is_empty());
+    soa.push(DropsForward(DropLogger(5)));
+    drop(soa);
+    assert_eq!(take_drop_log(), [5]);
+}
+
+#[test]
+fn truncate_drop_order() {
+    take_drop_log();
+    let mut soa: Soa<_> = (0..4).map(|i| DropsReverse(DropLogger(i))).collect();
+    soa.truncate(1);
+    assert_eq!(take_drop_log(), [3, 2, 1]);
+    assert_eq!(soa.len(), 1);
+    drop(soa);
+    assert_eq!(take_drop_log(), [0]);
+
+    let mut soa: Soa<_> = (0..4).map(|i| DropsForward(DropLogger(i))).collect();
+    soa.truncate(1);
+    assert_eq!(take_drop_log(), [1, 2, 3]);
+    soa.truncate(0);
+    assert_eq!(take_drop_log(), [0]);
+    assert!(soa.is_empty());
+}
+
+#[test]
+fn extract_if() {
+    let mut vec: Vec<_> = (0..10).map(|i| Tuple(i, 0, i.into())).collect();
+    let mut soa: Soa<_> = vec.iter().copied().collect();
+    let extracted: Vec<_> = soa.extract_if(|el| *el.0 % 3 == 0).collect();
+    assert_eq!(
+        extracted,
+        [
+            Tuple(0, 0, 0),
+            Tuple(3, 0, 3),
+            Tuple(6, 0, 6),
+            Tuple(9, 0, 9)
+        ]
+    );
+    vec.retain(|el| el.0 % 3 != 0);
+    assert_eq!(soa, Soa::from(vec.as_slice()));
+
+    let mut soa: Soa<_> = (0..6).map(|i| Tuple(i, 0, 0)).collect();
+    let mut iter = soa.extract_if(|el| {
+        *el.1 += 1;
+        *el.0 % 2 == 1
+    });
+    assert_eq!(iter.next(), Some(Tuple(1, 1, 0)));
+    drop(iter);
+    assert_eq!(
+        soa,
+        soa![
+            Tuple(0, 1, 0),
+            Tuple(2, 0, 0),
+            Tuple(3, 0, 0),
+            Tuple(4, 0, 0),
+            Tuple(5, 0, 0)
+        ]
+    );
+
+    let mut soa = soa![
+        DropsForward(DropLogger(0)),
+        DropsForward(DropLogger(1)),
+        DropsForward(DropLogger(2)),
+    ];
+    take_drop_log();
+    let mut iter = soa.extract_if(|el| el.0 .0 == 0);
+    drop(iter.next());
+    std::mem::forget(iter);
+    assert_eq!(take_drop_log(), [0]);
+    assert!(soa.is_empty());
+    drop(soa);
+    assert!(take_drop_log().is_empty());
+}
+
+#[test]
+fn retain_indexed() {
+    let mut soa: Soa<_> = (0..8).map(|i| Tuple(i * 10, 0, 0)).collect();
+    let mut seen = vec![];
+    soa.retain_indexed(|i, el| {
+        seen.push((i, *el.0));
+        i % 3 != 1
+    });
+    assert_eq!(seen, (0..8).map(|i| (i, i as u8 * 10)).collect::<Vec<_>>());
+    assert_eq!(
+        soa,
+        soa![
+            Tuple(0, 0, 0),
+            Tuple(20, 0, 0),
+            Tuple(30, 0, 0),
+            Tuple(50, 0, 0),
+            Tuple(60, 0, 0)
+        ]
+    );
+
+    let drops = Rc::new(Cell::new(0));
+    let mut soa: Soa<_> = (0..6)
+        .map(|n| Counted {
+            n,
+            drops: DropCounter(drops.clone()),
+        })
+        .collect();
+    let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        soa.retain_indexed(|i, el| {
+            assert!(i < 4, "predicate panic");
+            *el.n != i as u8 || i % 2 == 0
+        });
+    }));
+    assert!(result.is_err());
+    assert_eq!(drops.get(), 2);
+    assert_eq!(soa.n(), [0, 2, 4, 5]);
+    drop(soa);
+    assert_eq!(drops.get(), 6);
+}
+
+#[test]
+fn clone_soa() {
+    let soa = Soa::from(ABCDE);
+    let mut copy = soa.clone_soa();
+    assert_eq!(copy, soa);
+    assert_eq!(copy.capacity(), soa.len());
+    *copy.idx_mut(0).foo = 100;
+    assert_eq!(soa.idx(0).foo, &A.foo);
+    assert_eq!(copy.foo()[1..], soa.foo()[1..]);
+    assert_eq!(Soa::<El>::new().clone_soa(), Soa::new());
+}
+
+#[test]
+fn fill_indexed() {
+    let drops = Rc::new(Cell::new(0));
+    let counted = |n| Counted {
+        n,
+        drops: DropCounter(drops.clone()),
+    };
+    let mut soa: Soa<_> = (0..5).map(counted).collect();
+    let mut indices = vec![];
+    soa.fill_indexed(|i| {
+        indices.push(i);
+        counted(i as u8 + 10)
+    });
+    assert_eq!(indices, [0, 1, 2, 3, 4]);
+    assert_eq!(soa.n(), [10, 11, 12, 13, 14]);
+    assert_eq!(drops.get(), 5);
+
+    let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        soa.fill_indexed(|i| {
+            assert!(i < 2, "fill panic");
+            counted(i as u8 + 20)
+        });
+    }));
+    assert!(result.is_err());
+    assert_eq!(soa.n(), [20, 21, 12, 13, 14]);
+    assert_eq!(drops.get(), 7);
+    drop(soa);
+    assert_eq!(drops.get(), 12);
+}
+
+#[test]
+fn from_slice_copy() {
+    let tuples: Vec<_> = (0..20).map(|i| Tuple(i, i.into(), i.into())).collect();
+    let soa = Soa::from_slice_copy(&tuples);
+    assert_eq!(soa, Soa::from(tuples.as_slice()));
+    assert_eq!(soa.capacity(), tuples.len());
+    assert_eq!(soa.iter().count(), tuples.len());
+    assert_eq!(Soa::<Tuple>::from_slice_copy(&[]), Soa::new());
+}
+
+#[test]
+fn rolling() {
+    #[derive(Soars, Debug, PartialEq)]
+    #[soa_derive(Debug, PartialEq)]
+    struct Max {
+        index: usize,
+        value: u32,
+    }
+
+    let soa: Soa<_> = [3, 1, 4, 1, 5, 9, 2, 6]
+        .into_iter()
+        .map(|i| Tuple(0, 0, i))
+        .collect();
+    let max = soa.rolling(3, |window| {
+        let (index, &value) = window
+            .f2()
+            .iter()
+            .enumerate()
+            .max_by_key(|(_, &value)| value)
+            .unwrap();
+        Max { index, value }
+    });
+    assert_eq!(max.value(), [4, 4, 5, 9, 9, 9]);
+    assert_eq!(max.index(), [2, 1, 2, 2, 1, 0]);
+
+    assert!(soa.rolling(9, |_| Max { index: 0, value: 0 }).is_empty());
+    assert_eq!(
+        soa.rolling(8, |window| Tuple(0, 0, window.len() as u32))
+            .len(),
+        1
+    );
+}
+
+#[test]
+fn insert_from_iter() {
+    let original: Soa<_> = (0..5).map(|i| Tuple(i, 0, 0)).collect();
+    let new = [Tuple(10, 1, 1), Tuple(11, 2, 2), Tuple(12, 3, 3)];
+
+    let mut expected = original.clone();
+    for (i, el) in new.iter().enumerate() {
+        expected.insert(2 + i, *el);
+    }
+
+    let mut exact = original.clone();
+    exact.insert_from_iter(2, new);
+    assert_eq!(exact, expected);
+
+    let mut inexact = original.clone();
+    inexact.insert_from_iter(2, new.into_iter().filter(|_| true));
+    assert_eq!(inexact, expected);
+
+    let mut end = original.clone();
+    end.insert_from_iter(5, new);
+    assert_eq!(end.idx(5..), Soa::from(new.as_slice()));
+    assert_eq!(end.idx(..5), original);
+}
+
+#[test]
+fn iter_cloned() {
+    #[derive(Soars, Debug, Clone, PartialEq)]
+    #[soa_derive(Debug, PartialEq)]
+    struct Named {
+        id: u8,
+        name: String,
+    }
+
+    let mut soa = soa![
+        Named {
+            id: 1,
+            name: "one".into()
+        },
+        Named {
+            id: 2,
+            name: "two".into()
+        },
+    ];
+    let mut owned: Vec<Named> = soa.iter().cloned().collect();
+    assert_eq!(
+        owned,
+        soa.iter()
+            .map(|el| Named {
+                id: *el.id,
+                name: el.name.clone()
+            })
+            .collect::<Vec<_>>()
+    );
+
+    owned[0].name.push_str("-changed");
+    soa.name_mut()[1].push_str("-changed");
+    assert_eq!(soa.name(), ["one", "two-changed"]);
+    assert_eq!(owned[0].name, "one-changed");
+    assert_eq!(owned[1].name, "two");
+
+    let rev: Vec<_> = soa.iter().cloned().rev().map(|el| el.id).collect();
+    assert_eq!(rev, [2, 1]);
+}
+
+#[test]
+fn fill() {
+    let drops = Rc::new(Cell::new(0));
+    let counted = |n| Counted {
+        n,
+        drops: DropCounter(drops.clone()),
+    };
+
+    let mut soa: Soa<_> = (0..4)
+        .map(|_| El {
+            foo: 1,
+            bar: 2,
+            baz: SingleDrop::DEFAULT,
+        })
+        .collect();
+    soa.fill(B);
+    assert_eq!(soa, Soa::from([B, B, B, B].as_slice()));
+    soa.fill_with(|| A);
+    assert_eq!(soa, Soa::from([A, A, A, A].as_slice()));
+    soa.idx_mut(..0).fill(B);
+
+    let mut soa: Soa<_> = (0..3).map(counted).collect();
+    let mut n = 10;
+    soa.fill_with(|| {
+        n += 1;
+        counted(n)
+    });
+    assert_eq!(drops.get(), 3);
+    assert_eq!(soa.n(), [11, 12, 13]);
+    drop(soa);
+    assert_eq!(drops.get(), 6);
+}
+
+#[test]
+fn capacity_report() {
+    #[derive(Soars)]
+    #[soa_derive(Debug, PartialEq)]
+    struct Mixed {
+        tiny: u8,
+        #[align(16)]
+        small: u16,
+        medium: u32,
+        #[align(64)]
+        large: u64,
+    }
+
+    let soa = Soa::<Mixed>::with_capacity(5);
+    let report = soa.capacity_report();
+    let (layout, offsets) = MixedSoaRaw::layout_and_offsets(soa.capacity()).unwrap();
+    assert_eq!(report.size, layout.size());
+
+    let names: Vec<_> = report.fields.iter().map(|field| field.name).collect();
+    assert_eq!(names, ["tiny", "small", "medium", "large"]);
+    let reported: Vec<_> = report.fields.iter().map(|field| field.offset).collect();
+    assert_eq!(reported[0], 0);
+    assert_eq!(reported[1..], offsets);
+
+    let mut end = 0;
+    for field in &report.fields {
+        assert_eq!(field.padding, field.offset - end);
+        end = field.offset + field.size;
+    }
+    assert_eq!(report.packed_size, 5 * (1 + 2 + 4 + 8));
+    assert_eq!(report.padding(), report.size - report.packed_size);
+    assert!(report.padding() > 0);
+
+    let empty = Soa::<Mixed>::new().capacity_report();
+    assert_eq!(empty.packed_size, 0);
+}
+
+impl El {
+    fn weight(&self) -> u64 {
+        self.foo + u64::from(self.bar)
+    }
+}
+
+#[test]
+fn for_each_and_try_fold() {
+    let mut soa = Soa::from(ABCDE);
+    let mut total = 0;
+    soa.for_each(|el| total += el.weight());
+    assert_eq!(total, ABCDE.iter().map(El::weight).sum::<u64>());
+    assert_eq!(soa, ABCDE);
+
+    let mut visited = vec![];
+    let result = soa.try_fold(0, |acc, el| {
+        visited.push(el.foo);
+        if el.foo < 10 {
+            Ok(acc + el.weight())
+        } else {
+            Err(el.bar)
+        }
+    });
+    assert_eq!(result, Err(13));
+    assert_eq!(visited, [0, 4, 8, 12]);
+    assert_eq!(soa.try_fold(0, |acc, el| Ok::<_, ()>(acc + el.foo)), Ok(40));
+
+    #[derive(Soars)]
+    struct Slot {
+        id: u8,
+        payload: Cell<Option<Box<u8>>>,
+    }
+
+    let mut slots: Soa<_> = (0..3)
+        .map(|id| Slot {
+            id,
+            payload: Cell::new(Some(Box::new(id))),
+        })
+        .collect();
+    let mut taken = vec![];
+    slots.for_each(|slot| {
+        if slot.id != 1 {
+            taken.push(slot.payload.take().unwrap());
+        }
+    });
+    assert_eq!(taken, [Box::new(0), Box::new(2)]);
+    let remaining: Vec<_> = slots.payload_mut().iter_mut().map(|p| p.take()).collect();
+    assert_eq!(remaining, [None, Some(Box::new(1)), None]);
+
+    let drops = Rc::new(Cell::new(0));
+    let mut soa: Soa<_> = (0..3)
+        .map(|n| Counted {
+            n,
+            drops: DropCounter(drops.clone()),
+        })
+        .collect();
+    let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        soa.for_each(|counted| assert!(counted.n < 1, "for_each panic"));
+    }));
+    assert!(result.is_err());
+    assert_eq!(drops.get(), 0);
+    assert_eq!(soa.n(), [0, 1, 2]);
+    drop(soa);
+    assert_eq!(drops.get(), 3);
+}
+
+#[test]
+fn nth_copied_and_cloned() {
+    let tuples = soa![Tuple(1, 2, 3), Tuple(4, 5, 6), Tuple(7, 8, 9)];
+    assert_eq!(tuples.nth_copied(0), Tuple(1, 2, 3));
+    assert_eq!(tuples.nth_copied(2), Tuple(7, 8, 9));
+    assert_eq!(tuples.idx(1..).nth_copied(0), Tuple(4, 5, 6));
+
+    let soa = Soa::from(ABCDE);
+    for (i, el) in ABCDE.iter().enumerate() {
+        assert_eq!(&soa.nth_cloned(i), el);
+    }
+    assert_eq!(soa.idx(3..).nth_cloned(1), E);
+    assert_eq!(soa, ABCDE);
+}
+
+#[test]
+#[should_panic(expected = "index out of bounds")]
+fn nth_copied_out_of_bounds() {
+    soa![Tuple(1, 2, 3)].nth_copied(1);
+}
+
+#[test]
+#[should_panic(expected = "index out of bounds")]
+fn nth_cloned_out_of_bounds() {
+    Soa::from(ABCDE).nth_cloned(5);
+}
+
+#[test]
+fn drain_all() {
+    let mut soa = Soa::from(ABCDE);
+    soa.reserve(10);
+    let capacity = soa.capacity();
+    assert!(soa.drain_all().eq(ABCDE));
+    assert_eq!(soa.len(), 0);
+    assert_eq!(soa.capacity(), capacity);
+
+    soa.extend(ABCDE);
+    assert_eq!(soa.capacity(), capacity);
+    let mut drain = soa.drain_all();
+    assert_eq!(drain.next(), Some(A));
+    assert_eq!(drain.next_back(), Some(E));
+    assert_eq!(*drain.as_slice(), [B, C, D]);
+    drop(drain);
+    assert!(soa.is_empty());
+
+    let drops = Rc::new(Cell::new(0));
+    let mut soa: Soa<_> = (0..4)
+        .map(|n| Counted {
+            n,
+            drops: DropCounter(drops.clone()),
+        })
+        .collect();
+    let first = soa.drain_all().next().unwrap();
+    assert_eq!(first.n, 0);
+    assert_eq!(drops.get(), 3);
+    drop(first);
+    assert_eq!(drops.get(), 4);
+    assert!(soa.is_empty());
+}
+
+#[test]
+fn stride() {
+    let soa = Soa::from(ABCDE);
+    assert!(soa.stride(2).eq([A, C, E].iter().map(|el| el.as_soa_ref())));
+    assert!(soa.stride(3).eq([A, D].iter().map(|el| el.as_soa_ref())));
+    assert!(soa.stride(1).eq(soa.iter()));
+    assert!(soa.stride(5).eq([A.as_soa_ref()]));
+    assert!(soa.stride(8).eq([A.as_soa_ref()]));
+    assert!(soa
+        .stride(2)
+        .rev()
+        .eq([E, C, A].iter().map(|el| el.as_soa_ref())));
+    assert_eq!(soa.stride(2).len(), 3);
+    assert_eq!(soa.get(1..).unwrap().stride(2).len(), 2);
+    assert_eq!(Soa::<El>::new().stride(3).next(), None);
+}
+
+#[test]
+#[should_panic(expected = "step must be nonzero")]
+fn stride_zero() {
+    let soa = Soa::from(ABCDE);
+    let _ = soa.stride(0);
+}
+
+#[test]
+fn field_ptr_getters() {
+    let mut soa = Soa::from(ABCDE);
+    let foo = unsafe { std::slice::from_raw_parts(soa.foo_ptr().as_ptr(), soa.len()) };
+    assert_eq!(foo, soa.foo());
+    let bar = unsafe { std::slice::from_raw_parts(soa.bar_ptr().as_ptr(), soa.len()) };
+    assert_eq!(bar, soa.bar());
+
+    let tail = soa.get(3..).unwrap();
+    let foo = unsafe { std::slice::from_raw_parts(tail.foo_ptr().as_ptr(), tail.len()) };
+    assert_eq!(foo, [12, 16]);
+
+    let len = soa.len();
+    let bar = soa.bar_ptr_mut();
+    unsafe { std::slice::from_raw_parts_mut(bar.as_ptr(), len) }.fill(7);
+    assert_eq!(soa.bar(), [7; 5]);
+
+    let tuples = soa![Tuple(1, 2, 3), Tuple(4, 5, 6)];
+    assert_eq!(tuples.f1_ptr().as_ptr().cast_const(), tuples.f1().as_ptr());
+}
+
+#[test]
+fn field_alignments_and_ptrs() {
+    #[derive(Soars)]
+    #[soa_derive(Debug, PartialEq)]
+    struct Mixed<T> {
+        tiny: u8,
+        #[align(16)]
+        small: u16,
+        medium: u32,
+        #[align(64)]
+        large: u64,
+        #[align(2)]
+        wide: u64,
+        marker: PhantomData<T>,
+    }
+
+    assert_eq!(MixedSoaRaw::<()>::field_alignments(), [1, 16, 4, 64, 8]);
+    let soa: Soa<_> = (0..5u8)
+        .map(|i| Mixed::<()> {
+            tiny: i,
+            small: i.into(),
+            medium: i.into(),
+            large: i.into(),
+            wide: i.into(),
+            marker: PhantomData,
+        })
+        .collect();
+    let ptrs = soa.field_ptrs();
+    for (ptr, align) in ptrs.iter().zip(MixedSoaRaw::<()>::field_alignments()) {
+        assert_eq!(ptr.as_ptr() as usize % align, 0);
+    }
+    assert_eq!(ptrs[0].as_ptr().cast_const(), soa.tiny().as_ptr());
+    assert_eq!(ptrs[1].as_ptr() as *const u16, soa.small().as_ptr());
+    assert_eq!(ptrs[3].as_ptr() as *const u64, soa.large().as_ptr());
+    let tail = soa.get(2..).unwrap();
+    assert_eq!(
+        tail.field_ptrs()[2].as_ptr() as *const u32,
+        tail.medium().as_ptr()
+    );
+
+    assert_eq!(BodySoaRaw::field_alignments(), [4, 4, 32]);
+    let bodies = Soa::<Body>::with_capacity(3);
+    let [pos, vel, mass] = bodies.field_ptrs();
+    assert_eq!(pos.as_ptr() as *const f32, bodies.pos().x.as_ptr());
+    assert_eq!(vel.as_ptr() as *const f32, bodies.vel().0.x.as_ptr());
+    assert_eq!(mass.as_ptr() as *const f32, bodies.mass().as_ptr());
+}
+
+#[test]
+fn dedup_by_approx() {
+    #[derive(Soars, Debug, PartialEq)]
+    #[soa_derive(Debug, PartialEq)]
+    struct Sample {
+        t: u32,
+        y: f64,
+    }
+
+    let ys = [
+        0.0,
+        1e-4,
+        -1e-4,
+        0.5,
+        0.5 + 1e-5,
+        1.0,
+        1.0009,
+        1.0018,
+        f64::NAN,
+        f64::NAN,
+    ];
+    let mut soa: Soa<_> = ys
+        .into_iter()
+        .enumerate()
+        .map(|(t, y)| Sample { t: t as u32, y })
+        .collect();
+    soa.dedup_by_approx(1e-3, |sample| *sample.y);
+    assert_eq!(soa.t(), [0, 3, 5, 7, 8, 9]);
+}
+
+#[test]
+fn truncate_at() {
+    let drops = Rc::new(Cell::new(0));
+    let make = || -> Soa<Counted> {
+        [1, 2, 0, 3]
+            .into_iter()
+            .map(|n| Counted {
+                n,
+                drops: DropCounter(drops.clone()),
+            })
+            .collect()
+    };
+
+    let mut soa = make();
+    soa.truncate_at(|el| *el.n == 1);
+    assert!(soa.is_empty());
+    assert_eq!(drops.get(), 4);
+
+    drops.set(0);
+    let mut soa = make();
+    soa.truncate_at(|el| *el.n == 0);
+    assert_eq!(soa.n(), [1, 2]);
+    assert_eq!(drops.get(), 2);
+    drop(soa);
+
+    drops.set(0);
+    let mut soa = make();
+    soa.truncate_at(|el| *el.n == 3);
+    assert_eq!(soa.n(), [1, 2, 0]);
+    assert_eq!(drops.get(), 1);
+    drop(soa);
+
+    drops.set(0);
+    let mut soa = make();
+    soa.truncate_at(|el| *el.n == 9);
+    assert_eq!(soa.n(), [1, 2, 0, 3]);
+    assert_eq!(drops.get(), 0);
+}
+
+#[test]
+fn spare_capacity_mut() {
+    use std::mem::MaybeUninit;
+
+    let mut soa = soa![Tuple(1, 2, 3)];
+    soa.reserve(3);
+    let capacity = soa.capacity();
+    let spare = soa.spare_capacity_mut();
+    assert_eq!(spare.0.len(), capacity - 1);
+    for i in 0..2 {
+        spare.0[i] = MaybeUninit::new(10 + i as u8);
+        spare.1[i] = MaybeUninit::new(20 + i as u16);
+        spare.2[i] = MaybeUninit::new(30 + i as u32);
+    }
+    unsafe { soa.set_len(3) };
+    assert_eq!(
+        soa,
+        soa![Tuple(1, 2, 3), Tuple(10, 20, 30), Tuple(11, 21, 31)]
+    );
+
+    let mut soa = Soa::<Skipped>::with_capacity(1);
+    let spare = soa.spare_capacity_mut();
+    spare.foo[0] = MaybeUninit::new(1);
+    spare.bar[0] = MaybeUninit::new(2);
+    spare.soa_skipped[0] = MaybeUninit::new(SkippedSoaSkipped {
+        name: "skipped".into(),
+        baz: SingleDrop::DEFAULT,
+    });
+    unsafe { soa.set_len(1) };
+    assert_eq!(soa.idx(0).name, "skipped");
+    assert_eq!(soa.bar(), [2]);
+}
+
+#[test]
+fn align_simd() {
+    #[derive(Soars, Debug, PartialEq)]
+    #[soa_derive(Debug, PartialEq)]
+    struct Lanes {
+        tag: u8,
+        #[align(simd)]
+        values: f32,
+    }
+
+    #[cfg(target_feature = "avx512f")]
+    assert_eq!(SIMD_ALIGN, 64);
+    #[cfg(all(target_feature = "avx", not(target_feature = "avx512f")))]
+    assert_eq!(SIMD_ALIGN, 32);
+    #[cfg(all(
+        any(target_feature = "sse", target_feature = "neon"),
+        not(target_feature = "avx")
+    ))]
+    assert_eq!(SIMD_ALIGN, 16);
+
+    let soa: Soa<_> = (0..5)
+        .map(|i| Lanes {
+            tag: i,
+            values: 0.0,
+        })
+        .collect();
+    assert_eq!(soa.values().as_ptr() as usize % SIMD_ALIGN, 0);
+    assert_eq!(soa.capacity_report().fields[1].offset % SIMD_ALIGN, 0);
+}
+
+#[test]
+fn set_len() {
+    use soa_rs::SoaRaw;
+
+    let mut soa = Soa::<El>::with_capacity(ABCDE.len());
+    for (i, el) in ABCDE.into_iter().enumerate() {
+        unsafe { soa.raw().offset(i).set(el) };
+    }
+    unsafe { soa.set_len(ABCDE.len()) };
+    assert_eq!(soa, Soa::from(ABCDE));
+
+    // Move the tail out before hiding it so that nothing is leaked
+    let tail: Vec<_> = (2..5)
+        .map(|i| unsafe { soa.raw().offset(i).get() })
+        .collect();
+    unsafe { soa.set_len(2) };
+    assert_eq!(soa, Soa::from([A, B].as_slice()));
+    assert_eq!(tail, [C, D, E]);
+}
+
+#[test]
+fn clear_and_shrink() {
+    let drops = Rc::new(Cell::new(0));
+    let mut soa: Soa<_> = (0..5)
+        .map(|n| Counted {
+            n,
+            drops: DropCounter(drops.clone()),
+        })
+        .collect();
+    assert!(soa.capacity() > 0);
+    soa.clear_and_shrink();
+    assert!(soa.is_empty());
+    assert_eq!(soa.capacity(), 0);
+    assert_eq!(drops.get(), 5);
+
+    soa.push(Counted {
+        n: 5,
+        drops: DropCounter(drops.clone()),
+    });
+    assert_eq!(soa.n(), [5]);
+    drop(soa);
+    assert_eq!(drops.get(), 6);
+
+    let mut soa = Soa::<Unit>::new();
+    soa.push(Unit);
+    soa.clear_and_shrink();
+    assert!(soa.is_empty());
+}
+
+#[test]
+fn iter_with_field() {
+    let soa: Soa<_> = [4, 8, 2, 16]
+        .into_iter()
+        .enumerate()
+        .map(|(i, n)| Tuple(i as u8, 0, n))
+        .collect();
+    let normalized: Vec<_> = soa
+        .iter_with_field(|slice| slice.f2())
+        .map(|(el, column)| {
+            let max = column.iter().max().unwrap();
+            (*el.0, *el.2 as f32 / *max as f32)
+        })
+        .collect();
+    assert_eq!(normalized, [(0, 0.25), (1, 0.5), (2, 0.125), (3, 1.0)]);
+
+    let sub = soa.idx(1..3);
+    let (last, column) = sub.iter_with_field(|slice| slice.f2()).next_back().unwrap();
+    assert_eq!(*last.2, 2);
+    assert_eq!(column, [8, 2]);
+}
+
+#[test]
+fn skip_n_take_n() {
+    let soa = Soa::from(ABCDE);
+    assert_eq!(soa.iter().skip_n(2).as_slice(), &soa.idx(2..));
+    assert_eq!(soa.iter().take_n(3).as_slice(), &soa.idx(..3));
+    assert_eq!(soa.iter().skip_n(1).take_n(3).as_slice(), &soa.idx(1..4));
+    assert_eq!(soa.iter().take_n(3).skip_n(1).as_slice(), &soa.idx(1..3));
+    assert_eq!(soa.iter().skip_n(1).skip_n(1).as_slice(), &soa.idx(2..));
+    assert!(soa.iter().skip_n(9).as_slice().is_empty());
+    assert_eq!(soa.iter().take_n(9).len(), 5);
+
+    let mut iter = soa.iter();
+    iter.next();
+    let iter = iter.skip_n(1).take_n(2);
+    assert_eq!(iter.len(), 2);
+    assert!(iter.eq(soa.idx(2..4).iter()));
+}
+
+#[test]
+fn iter_rev() {
+    let soa = Soa::from(ABCDE);
+    let expected: Vec<_> = soa.foo().iter().rev().copied().collect();
+    let reversed: Vec<_> = soa.foo_rev().copied().collect();
+    assert_eq!(reversed, expected);
+    assert!(soa.bar_rev().eq(soa.bar().iter().rev()));
+    assert!(soa.iter_rev().eq(soa.iter().rev()));
+    assert_eq!(soa.idx(1..3).foo_rev().len(), 2);
+
+    let flat = soa![Body {
+        pos: Vector3 {
+            x: 1.0,
+            y: 2.0,
+            z: 3.0,
+        },
+        vel: Velocity(Vector3 {
+            x: 0.0,
+            y: 0.0,
+            z: 0.0,
+        }),
+        mass: 4.0,
+    }];
+    assert!(flat.mass_rev().eq(&[4.0]));
+}
+
+#[test]
+fn pop_front() {
+    let mut soa: Soa<El> = ABCDE.into();
+    assert_eq!(soa.pop_front(), Some(A));
+    assert_eq!(soa.pop_front(), Some(B));
+    assert_eq!(soa, soa![C, D, E]);
+    assert_eq!(soa.pop_front(), Some(C));
+    assert_eq!(soa.pop_front(), Some(D));
+    assert_eq!(soa.pop_front(), Some(E));
+    assert_eq!(soa.pop_front(), None);
+    assert!(soa.is_empty());
+}
+
+#[test]
+fn swap_remove_front() {
+    let mut soa: Soa<El> = ABCDE.into();
+    assert_eq!(soa.swap_remove_front(), Some(A));
+    assert_eq!(soa, soa![E, B, C, D]);
+    assert_eq!(soa.swap_remove_front(), Some(E));
+    assert_eq!(soa, soa![D, B, C]);
+    assert_eq!(soa.swap_remove_front(), Some(D));
+    assert_eq!(soa.swap_remove_front(), Some(C));
+    assert_eq!(soa.swap_remove_front(), Some(B));
+    assert_eq!(soa.swap_remove_front(), None);
+    assert!(soa.is_empty());
+}
+
+#[test]
+fn eq_std_collections() {
+    let soa: Soa<El> = ABCDE.into();
+    assert_eq!(soa, ABCDE);
+    assert_eq!(soa, ABCDE[..]);
+    assert_eq!(soa, ABCDE.to_vec());
+    assert_eq!(*soa.idx(1..3), [B, C]);
+    assert_eq!(*soa.idx(..0), []);
+    assert_ne!(soa, [A, B, C, D]);
+    assert_ne!(soa, [A, B, C, D, A]);
+    assert_ne!(*soa.idx(1..), ABCDE);
+
+    let empty = Soa::<El>::new();
+    assert_eq!(empty, Vec::new());
+}
+
+#[test]
+fn shrink_to_fit_empty() {
+    let mut soa: Soa<El> = ABCDE.into();
+    soa.clear();
+    assert!(soa.capacity() >= 5);
+    soa.shrink_to_fit();
+    assert_eq!(soa.capacity(), 0);
+
+    // The container is usable again after the allocation is released
+    let before = allocations();
+    soa.push(A);
+    assert_eq!(allocations(), before + 1);
+    assert_eq!(soa, [A]);
+    soa.pop();
+    soa.shrink_to_fit();
+    assert_eq!(soa.capacity(), 0);
+}
+
+#[test]
+fn getter_rename() {
+    #[derive(Soars, Debug, PartialEq)]
+    #[soa_derive(Debug, PartialEq)]
+    struct Particle2(
+        #[soa(getter = "pos")] [f32; 2],
+        #[soa(getter = "velocity")] [f32; 2],
+        u8,
+    );
+
+    let mut soa = soa![
+        Particle2([0.0, 1.0], [2.0, 3.0], 4),
+        Particle2([5.0, 6.0], [7.0, 8.0], 9),
+    ];
+    assert_eq!(soa.pos(), [[0.0, 1.0], [5.0, 6.0]]);
+    assert_eq!(soa.velocity(), [[2.0, 3.0], [7.0, 8.0]]);
+    assert_eq!(soa.f2(), [4, 9]);
+    soa.velocity_mut()[1] = [0.0; 2];
+    assert!(soa.velocity_rev().eq(&[[0.0; 2], [2.0, 3.0]]));
+    assert_eq!(soa.idx(1), Particle2Ref(&[5.0, 6.0], &[0.0; 2], &9));
+}
+
+#[test]
+fn append_moves_elements() {
+    let drops = Rc::new(Cell::new(0));
+    let counted = |n| Counted {
+        n,
+        drops: DropCounter(drops.clone()),
+    };
+    let mut soa: Soa<Counted> = (0..2).map(counted).collect();
+    let mut other: Soa<Counted> = (2..5).map(counted).collect();
+    soa.append(&mut other);
+    assert!(other.is_empty());
+    assert_eq!(drops.get(), 0);
+    assert_eq!(soa.n(), [0, 1, 2, 3, 4]);
+    drop(other);
+    assert_eq!(drops.get(), 0);
+    drop(soa);
+    assert_eq!(drops.get(), 5);
+}
+
+#[test]
+fn concat() {
+    let parts: Vec<Soa<El>> = vec![soa![A, B], soa![C], Soa::new(), soa![D, E, A]];
+    let concatenated = Soa::concat(parts.iter().map(|part| part.idx(..)));
+
+    let mut appended = Soa::<El>::new();
+    for part in &parts {
+        let mut part: Soa<El> = part.iter().collect();
+        appended.append(&mut part);
+        assert!(part.is_empty());
+    }
+    assert_eq!(concatenated, appended);
+    assert_eq!(concatenated, [A, B, C, D, E, A]);
+    assert_eq!(concatenated.capacity(), 6);
+
+    let empty = Soa::<El>::concat(Vec::<Soa<El>>::new());
+    assert!(empty.is_empty());
+    assert_eq!(empty.capacity(), 0);
+}
+
+#[test]
+fn to_vec() {
+    let soa: Soa<El> = ABCDE.into();
+    assert_eq!(soa.to_vec(), ABCDE);
+    assert_eq!(soa.idx(1..3).to_vec(), [B, C]);
+    assert_eq!(soa, ABCDE);
+}
+
+#[test]
+fn resize_default() {
+    let first = ExtraImplTester {
+        things: 1,
+        stuff: 2,
+    };
+    let second = ExtraImplTester {
+        things: 3,
+        stuff: 4,
+    };
+    let mut soa = soa![first, second];
+    soa.resize_default(5);
+    assert_eq!(soa.len(), 5);
+    assert_eq!(
+        soa,
+        [
+            first,
+            second,
+            Default::default(),
+            Default::default(),
+            Default::default()
+        ]
+    );
+    soa.resize_default(2);
+    assert_eq!(soa, [first, second]);
+}
+
+#[test]
+fn copy_within() {
+    let mut soa: Soa<Tuple> = (0..6).map(|i| Tuple(i, i.into(), i.into())).collect();
+    soa.copy_within(0..4, 2);
+    let expected: Vec<_> = [0, 1, 0, 1, 2, 3]
+        .into_iter()
+        .map(|i| Tuple(i, i.into(), i.into()))
+        .collect();
+    assert_eq!(soa, expected);
+
+    soa.copy_within(3.., 0);
+    let expected: Vec<_> = [1, 2, 3, 1, 2, 3]
+        .into_iter()
+        .map(|i| Tuple(i, i.into(), i.into()))
+        .collect();
+    assert_eq!(soa, expected);
+}
+
+#[test]
+#[should_panic(expected = "index out of bounds")]
+fn copy_within_out_of_bounds() {
+    let mut soa = soa![Tuple(0, 0, 0), Tuple(1, 1, 1), Tuple(2, 2, 2)];
+    soa.copy_within(1..3, 2);
+}
+
+#[test]
+#[should_panic(expected = "index out of bounds")]
+fn copy_within_overflow() {
+    let mut soa = soa![Tuple(0, 0, 0), Tuple(1, 1, 1), Tuple(2, 2, 2)];
+    soa.copy_within(..=usize::MAX, 0);
+}
+
+#[test]
+fn is_sorted() {
+    let soa: Soa<El> = ABCDE.into();
+    assert!(soa.is_sorted_by(|a, b| a.foo <= b.foo));
+    assert!(!soa.is_sorted_by(|a, b| a.foo >= b.foo));
+    let soa = soa![C, A, B];
+    assert!(!soa.is_sorted_by(|a, b| a.foo <= b.foo));
+    assert!(Soa::<El>::new().is_sorted_by(|_, _| false));
+
+    let mut soa = soa![
+        ExtraImplTester {
+            things: 1,
+            stuff: 2
+        },
+        ExtraImplTester {
+            things: 1,
+            stuff: 3
+        },
+    ];
+    assert!(soa.is_sorted());
+    soa.reverse();
+    assert!(!soa.is_sorted());
+}
+
+#[test]
+fn index_pair() {
+    let mut soa: Soa<El> = ABCDE.into();
+    assert_eq!(soa.idx((0, 2)), (A.as_soa_ref(), C.as_soa_ref()));
+    assert_eq!(soa.get((1, 1)), Some((B.as_soa_ref(), B.as_soa_ref())));
+    assert!(soa.get((0, 5)).is_none());
+    assert!(soa.get_mut((0, 0)).is_none());
+    assert!(soa.get_mut((5, 0)).is_none());
+
+    let (a, b) = soa.idx_mut((4, 0));
+    std::mem::swap(a.foo, b.foo);
+    assert_eq!(*soa.foo(), [16, 4, 8, 12, 0]);
+
+    let [a, b, c] = soa.idx([3, 1, 3]);
+    assert_eq!([*a.foo, *b.foo, *c.foo], [12, 4, 12]);
+    assert!(soa.get_mut([3, 1, 3]).is_none());
+    for el in soa.idx_mut([3, 1, 2]) {
+        *el.bar += 1;
+    }
+    assert_eq!(*soa.bar(), [1, 6, 10, 14, 17]);
+}
+
+#[test]
+fn partition_point() {
+    let soa: Soa<El> = ABCDE.into();
+    assert_eq!(soa.partition_point(|el| *el.foo < 9), 3);
+    assert_eq!(soa.partition_point(|el| *el.foo <= 8), 3);
+    assert_eq!(soa.partition_point(|_| true), 5);
+    assert_eq!(soa.partition_point(|_| false), 0);
+    assert_eq!(Soa::<El>::new().partition_point(|_| true), 0);
+}
+
+#[test]
+fn into_iter_as_slice_both_ends() {
+    let soa: Soa<El> = ABCDE.into();
+    let mut iter = soa.into_iter();
+    assert_eq!(iter.next(), Some(A));
+    assert_eq!(iter.next_back(), Some(E));
+    assert_eq!(*iter.as_slice(), [B, C, D]);
+    assert_eq!(*iter.as_mut_slice(), [B, C, D]);
+    assert_eq!(iter.len(), 3);
+}
+
+#[test]
+fn append_slice() {
+    let mut soa = soa![A, B];
+    let other: Soa<El> = ABCDE.into();
+    soa.append_slice(&other.idx(1..3));
+    assert_eq!(soa, [A, B, B, C]);
+    soa.append_slice(&soa![E]);
+    assert_eq!(soa, [A, B, B, C, E]);
+    assert_eq!(other, ABCDE);
+}
+
+#[test]
+fn soa_columns_macro() {
+    let soa: Soa<El> = soa_columns!(El {
+        foo: [0, 4, 8, 12, 16],
+        bar: (0..5).map(|i| i * 4 + 1),
+        baz: std::iter::repeat_with(|| SingleDrop::DEFAULT).take(5),
+    });
+    assert_eq!(soa, soa![A, B, C, D, E]);
+}
+
+#[test]
+#[should_panic(expected = "columns have different lengths")]
+fn soa_columns_macro_length_mismatch() {
+    let _ = soa_columns!(ExtraImplTester {
+        things: [1, 2, 3],
+        stuff: [4, 5],
+    });
+}
+
+#[test]
+fn soa_array_shorthand() {
+    #[derive(Soars, Debug, Clone, Copy, PartialEq)]
+    #[soa(array)]
+    #[soa_derive(Debug, PartialEq)]
+    struct Point {
+        x: f32,
+        y: f32,
+    }
+
+    const POINTS: PointArray<2> =
+        PointArray::from_array([Point { x: 1.0, y: 2.0 }, Point { x: 3.0, y: 4.0 }]);
+    assert_eq!(POINTS.as_slice().x(), [1.0, 3.0]);
+    assert_eq!(POINTS.as_slice().y(), [2.0, 4.0]);
+    assert_eq!(POINTS, POINTS);
+
+    // Without the attribute, no array type is generated, so the name stays
+    // free to use
+    #[derive(Soars)]
+    #[soa_derive(Debug, PartialEq)]
+    struct Counter {
+        id: u8,
+        count: u32,
+    }
+
+    #[derive(Debug, PartialEq)]
+    struct CounterArray(u8);
+
+    let soa = soa![Counter { id: 1, count: 3 }];
+    assert_eq!(soa.id(), [1]);
+    assert_eq!(soa.count(), [3]);
+    assert_eq!(CounterArray(1), CounterArray(1));
+}
+
+#[test]
+fn ref_hash() {
+    use std::{
+        collections::hash_map::DefaultHasher,
+        hash::{Hash, Hasher},
+    };
+
+    fn hash(value: impl Hash) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        value.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    let soa: Soa<El> = ABCDE.into();
+    for (el, expected) in soa.iter().zip(&ABCDE) {
+        assert_eq!(hash(el), hash(expected));
+        assert_eq!(hash(el), hash(expected.as_soa_ref()));
+    }
+    assert_ne!(hash(soa.idx(0)), hash(soa.idx(1)));
+
+    // A qualified path is replaced by the generated impl as well, and types
+    // with fields that are not Hash still derive Soars
+    #[derive(Soars, Hash)]
+    #[soa_derive(std::hash::Hash)]
+    struct Key {
+        id: u32,
+        name: &'static str,
+    }
+
+    #[derive(Soars)]
+    struct Unhashable {
+        value: f32,
+    }
+
+    let keys = soa![Key { id: 1, name: "a" }, Key { id: 2, name: "b" }];
+    assert_eq!(hash(keys.idx(1)), hash(Key { id: 2, name: "b" }));
+    let _ = soa![Unhashable { value: 1.0 }];
+}
+
+#[test]
+fn get_unchecked() {
+    let mut soa: Soa<El> = ABCDE.into();
+    for (i, expected) in ABCDE.iter().enumerate() {
+        // SAFETY: i < soa.len()
+        assert_eq!(unsafe { soa.get_unchecked(i) }, expected.as_soa_ref());
+    }
+    for i in 0..soa.len() {
+        // SAFETY: i < soa.len()
+        *unsafe { soa.get_unchecked_mut(i) }.bar += 1;
+    }
+    assert_eq!(*soa.bar(), [2, 6, 10, 14, 18]);
+    let tail = soa.idx(3..);
+    // SAFETY: 1 < tail.len()
+    assert_eq!(*unsafe { tail.get_unchecked(1) }.foo, E.foo);
+}
+
+#[test]
+fn try_reserve() {
+    let mut soa = Soa::<El>::new();
+    assert_eq!(soa.try_reserve(3), Ok(()));
+    assert!(soa.capacity() >= 3);
+    soa.extend(ABCDE);
+    assert_eq!(soa.try_reserve(100), Ok(()));
+    assert!(soa.capacity() >= 105);
+    assert_eq!(soa, ABCDE);
+
+    let capacity = soa.capacity();
+    assert_eq!(
+        soa.try_reserve(usize::MAX),
+        Err(TryReserveError::CapacityOverflow)
+    );
+    assert_eq!(
+        soa.try_reserve(isize::MAX as usize / 2),
+        Err(TryReserveError::CapacityOverflow)
+    );
+    assert_eq!(soa.capacity(), capacity);
+    assert_eq!(soa, ABCDE);
+
+    let mut soa = Soa::<Unit>::new();
+    assert_eq!(soa.try_reserve(usize::MAX), Ok(()));
+}
+
+#[test]
+fn try_with_capacity() {
+    #[derive(Soars)]
+    #[soa_derive(Debug, PartialEq)]
+    struct Large {
+        bytes: [u8; 1 << 20],
+        tag: u8,
+    }
+
+    let soa = Soa::<Large>::try_with_capacity(4).unwrap();
+    assert_eq!(soa.capacity(), 4);
+    assert_eq!(
+        Soa::<Large>::try_with_capacity(1 << 44).err(),
+        Some(TryReserveError::CapacityOverflow)
+    );
+    assert_eq!(Soa::<Large>::try_with_capacity(0).unwrap().capacity(), 0);
+    assert_eq!(
+        Soa::<Unit>::try_with_capacity(10).unwrap().capacity(),
+        usize::MAX
+    );
+}
+
+#[test]
+fn leak() {
+    let drops = Rc::new(Cell::new(0));
+    let soa: Soa<Counted> = (0..3)
+        .map(|n| Counted {
+            n,
+            drops: DropCounter(drops.clone()),
+        })
+        .collect();
+    let mut leaked = soa.leak();
+    for n in leaked.n_mut() {
+        *n += 10;
+    }
+    assert_eq!(leaked.n(), [10, 11, 12]);
+    assert_eq!(drops.get(), 0);
+}
+
+#[test]
+fn into_columns() {
+    let soa = soa![
+        Body {
+            pos: Vector3 {
+                x: 1.0,
+                y: 2.0,
+                z: 3.0
+            },
+            vel: Velocity(Vector3 {
+                x: 4.0,
+                y: 5.0,
+                z: 6.0
+            }),
+            mass: 7.0,
+        },
+        Body {
+            pos: Vector3 {
+                x: 8.0,
+                y: 9.0,
+                z: 10.0
+            },
+            vel: Velocity(Vector3 {
+                x: 11.0,
+                y: 12.0,
+                z: 13.0
+            }),
+            mass: 14.0,
+        },
+    ];
+    let columns = soa.clone().into_columns();
+    assert_eq!(columns.pos.x, [1.0, 8.0]);
+    assert_eq!(columns.pos.z, [3.0, 10.0]);
+    assert_eq!(columns.vel.0.y, [5.0, 12.0]);
+    let mass: Box<[f32]> = columns.mass.into_boxed_slice();
+    assert_eq!(*mass, [7.0, 14.0]);
+
+    let columns = Tuple::into_columns(soa![Tuple(1, 2, 3), Tuple(4, 5, 6)]);
+    assert_eq!(columns.0, [1, 4]);
+    assert_eq!(columns.1, [2, 5]);
+    assert_eq!(columns.2, [3, 6]);
+
+    let columns = soa.into_columns();
+    assert_eq!(columns.mass.len(), 2);
+    assert_eq!(Body::from_columns(columns).unwrap().mass(), [7.0, 14.0]);
+}
+
+#[test]
+fn phantom_field() {
+    #[derive(Soars, Debug, Clone, PartialEq)]
+    #[soa_derive(Debug, PartialEq)]
+    struct Id<T> {
+        _marker: PhantomData<T>,
+        value: u32,
+    }
+
+    impl<T> Id<T> {
+        fn new(value: u32) -> Self {
+            Self {
+                _marker: PhantomData,
+                value,
+            }
+        }
+    }
+
+    let mut soa: Soa<Id<String>> = (0..4).map(Id::new).collect();
+    soa.push(Id::new(4));
+    assert_eq!(soa.value(), [0, 1, 2, 3, 4]);
+    assert_eq!(soa.idx(2)._marker, PhantomData);
+    assert_eq!(soa.pop(), Some(Id::new(4)));
+    soa.remove(0);
+    assert_eq!(
+        soa.iter().map(|id| *id.value).collect::<Vec<_>>(),
+        [1, 2, 3]
+    );
+    assert_eq!(soa.capacity_report().fields.len(), 1);
+
+    let columns = soa.into_columns();
+    assert_eq!(columns.value, [1, 2, 3]);
+    let soa = Id::<String>::from_columns(columns).unwrap();
+    assert_eq!(soa.value(), [1, 2, 3]);
+}
+
+#[test]
+fn zip_mut() {
+    let mut soa: Soa<El> = ABCDE.into_iter().collect();
+    for (foo, bar, baz) in soa.zip_mut() {
+        *foo += *bar as u64;
+        *bar = baz.0;
+    }
+    assert_eq!(soa.foo(), [1, 9, 17, 25, 33]);
+    assert_eq!(soa.bar(), [0; 5]);
+    assert_eq!(soa.get_mut(1..3).unwrap().zip_mut().count(), 2);
+
+    let mut soa = soa![Tuple(1, 2, 3), Tuple(4, 5, 6)];
+    let (a, b, c) = soa.zip_mut().last().unwrap();
+    *a = *c as u8;
+    *b = 0;
+    assert_eq!(soa, soa![Tuple(1, 2, 3), Tuple(6, 0, 6)]);
+}
+
+#[test]
+fn cmp_with_slices() {
+    let soa: Soa<El> = ABCDE.into_iter().collect();
+    let mut greater = ABCDE;
+    greater[2].bar += 1;
+    let mut less = ABCDE;
+    less[2].foo -= 1;
+
+    assert_eq!(soa.partial_cmp(&ABCDE), Some(Ordering::Equal));
+    assert_eq!(soa.partial_cmp(&greater), Some(Ordering::Less));
+    assert_eq!(soa.partial_cmp(&less), Some(Ordering::Greater));
+    assert_eq!(soa.partial_cmp(&greater[..]), Some(Ordering::Less));
+    assert_eq!(soa.partial_cmp(&less.to_vec()), Some(Ordering::Greater));
+    assert_eq!(soa.partial_cmp(&ABCDE[..4]), Some(Ordering::Greater));
+    assert_eq!(
+        soa.get(..2).unwrap().as_ref().partial_cmp(&ABCDE[..]),
+        Some(Ordering::Less)
+    );
+    assert!(soa < greater && soa > less);
+}
+
+#[test]
+fn chunk_by() {
+    let mut soa: Soa<El> = [A, B, B, C, C, C, D].into_iter().collect();
+    let runs: Vec<_> = soa
+        .chunk_by(|a, b| a.bar == b.bar)
+        .map(|run| (*run.idx(0).bar, run.len()))
+        .collect();
+    assert_eq!(runs, [(1, 1), (5, 2), (9, 3), (13, 1)]);
+
+    let runs: Vec<_> = soa
+        .chunk_by(|a, b| a.bar == b.bar)
+        .rev()
+        .map(|run| (*run.idx(0).bar, run.len()))
+        .collect();
+    assert_eq!(runs, [(13, 1), (9, 3), (5, 2), (1, 1)]);
+
+    let mut iter = soa.chunk_by(|a, b| a.bar == b.bar);
+    assert_eq!(iter.next().unwrap().len(), 1);
+    assert_eq!(iter.next_back().unwrap().len(), 1);
+    assert_eq!(iter.next_back().unwrap().len(), 3);
+    assert_eq!(iter.next().unwrap().len(), 2);
+    assert!(iter.next().is_none());
+    assert!(iter.next_back().is_none());
+
+    for (i, mut run) in soa.chunk_by_mut(|a, b| a.bar == b.bar).enumerate() {
+        run.foo_mut().fill(i as u64);
+    }
+    assert_eq!(soa.foo(), [0, 1, 1, 2, 2, 2, 3]);
+
+    let empty = Soa::<El>::new();
+    assert_eq!(empty.chunk_by(|_, _| true).count(), 0);
+}
+
+#[test]
+fn as_soa_ref_for_references() {
+    fn bar_of<R: AsSoaRef<Item = El>>(item: R) -> u8 {
+        *item.as_soa_ref().bar
+    }
+
+    let mut el = B;
+    assert_eq!(bar_of(&el), 5);
+    assert_eq!(bar_of(&mut el), 5);
+    assert_eq!(bar_of(el), 5);
+
+    let soa: Soa<El> = ABCDE.into_iter().collect();
+    assert_eq!(bar_of(soa.idx(2)), 9);
+    let refs = [soa.idx(1), soa.idx(2)];
+    assert_eq!(refs.iter().map(bar_of).collect::<Vec<_>>(), [5, 9]);
+    let els = [&A, &C];
+    assert_eq!(els.iter().map(bar_of).collect::<Vec<_>>(), [1, 9]);
+    assert_eq!(soa.index_of(&D), Some(3));
+}
+
+#[test]
+fn into_iter_fold_drops_once() {
+    let drops = Rc::new(Cell::new(0));
+    let counted = |range: std::ops::Range<u8>| -> Soa<Counted> {
+        range
+            .map(|n| Counted {
+                n,
+                drops: DropCounter(drops.clone()),
+            })
+            .collect()
+    };
+
+    let order = counted(0..4).into_iter().rfold(vec![], |mut acc, c| {
+        acc.push(c.n);
+        acc
+    });
+    assert_eq!(order, [3, 2, 1, 0]);
+    assert_eq!(drops.get(), 4);
+
+    let sum = counted(0..4).into_iter().fold(0, |acc, c| acc + c.n);
+    assert_eq!(sum, 6);
+    assert_eq!(drops.get(), 8);
+
+    let mut iter = counted(0..4).into_iter();
+    iter.next();
+    assert_eq!(iter.rfold(0, |acc, c| acc + c.n), 6);
+    assert_eq!(drops.get(), 12);
+
+    assert_eq!(counted(0..4).into_iter().last().map(|c| c.n), Some(3));
+    assert_eq!(drops.get(), 16);
+
+    let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        counted(0..4).into_iter().rfold((), |(), c| {
+            if c.n == 2 {
+                panic!("stop");
+            }
+        })
+    }));
+    assert!(result.is_err());
+    assert_eq!(drops.get(), 20);
+}
+
+#[test]
+fn select_nth_unstable_by() {
+    let mut state = 7u32;
+    let mut next = move || {
+        state ^= state << 13;
+        state ^= state >> 17;
+        state ^= state << 5;
+        state
+    };
+    let values: Vec<u32> = (0..200).map(|_| next() % 1000).collect();
+    let duplicates: Vec<u32> = (0..200).map(|_| next() % 4).collect();
+    let ascending: Vec<u32> = (0..200).collect();
+
+    for values in [values, duplicates, ascending] {
+        let mut sorted = values.clone();
+        sorted.sort();
+        for index in [0, 1, 50, 99, 100, 150, 198, 199] {
+            let mut soa: Soa<Tuple> = values.iter().map(|&v| Tuple(0, 0, v)).collect();
+            let (lesser, nth, greater) = soa.select_nth_unstable_by(index, |a, b| a.2.cmp(b.2));
+            let nth = *nth.2;
+            assert_eq!(nth, sorted[index]);
+            assert_eq!(lesser.len(), index);
+            assert_eq!(greater.len(), values.len() - index - 1);
+            assert!(lesser.f2().iter().all(|&v| v <= nth));
+            assert!(greater.f2().iter().all(|&v| v >= nth));
+
+            let mut after: Vec<_> = soa.f2().to_vec();
+            after.sort();
+            assert_eq!(after, sorted);
+        }
+    }
+
+    let mut soa = soa![Tuple(1, 2, 3)];
+    let (lesser, nth, greater) = soa.select_nth_unstable_by(0, |a, b| a.cmp(&b));
+    assert!(lesser.is_empty() && greater.is_empty());
+    assert_eq!(*nth.0, 1);
+}
+
+#[test]
+#[should_panic(expected = "index out of bounds")]
+fn select_nth_unstable_by_out_of_bounds() {
+    let mut soa = soa![Tuple(1, 2, 3)];
+    soa.select_nth_unstable_by(1, |a, b| a.cmp(&b));
+}
+
+#[test]
+fn columns_debug() {
+    #[derive(Soars)]
+    #[soa_derive(Debug, PartialEq)]
+    struct Point {
+        x: i32,
+        label: &'static str,
+    }
+
+    let soa = soa![Point { x: 1, label: "a" }, Point { x: -2, label: "b" }];
+    assert_eq!(
+        format!("{:?}", soa.columns_debug()),
+        r#"Point { x: [1, -2], label: ["a", "b"] }"#
+    );
+    assert_eq!(
+        format!("{:?}", Soa::<Point>::new().columns_debug()),
+        "Point { x: [], label: [] }"
+    );
+
+    let soa = soa![Tuple(1, 2, 3), Tuple(4, 5, 6)];
+    assert_eq!(
+        format!("{:?}", soa.columns_debug()),
+        "Tuple([1, 4], [2, 5], [3, 6])"
+    );
+
+    let soa = soa![Body {
+        pos: Vector3 {
+            x: 1.0,
+            y: 2.0,
+            z: 3.0
+        },
+        vel: Velocity(Vector3 {
+            x: 0.0,
+            y: 0.0,
+            z: 0.0
+        }),
+        mass: 4.0,
+    }];
+    assert_eq!(
+        format!("{:?}", soa.columns_debug()),
+        "Body { pos: Vector3Slices { x: [1.0], y: [2.0], z: [3.0] }, \
+         vel: VelocitySlices(Vector3Slices { x: [0.0], y: [0.0], z: [0.0] }), mass: [4.0] }"
+    );
+}
+
+#[test]
+fn swap_with_slice() {
+    let mut front: Soa<El> = [A, B].into_iter().collect();
+    let mut back: Soa<El> = [C, D].into_iter().collect();
+    front.swap_with_slice(&mut back);
+    assert_eq!(front, [C, D]);
+    assert_eq!(back, [A, B]);
+
+    let mut soa: Soa<El> = ABCDE.into_iter().collect();
+    let mut other: Soa<El> = [E, E].into_iter().collect();
+    soa.idx_mut(..2).swap_with_slice(&mut other);
+    assert_eq!(soa, [E, E, C, D, E]);
+    assert_eq!(other, [A, B]);
+}
+
+#[test]
+#[should_panic(expected = "destination and source slices have different lengths")]
+fn swap_with_slice_length_mismatch() {
+    let mut a: Soa<El> = [A, B].into_iter().collect();
+    let mut b: Soa<El> = [C].into_iter().collect();
+    a.swap_with_slice(&mut b);
+}
+
+#[test]
+fn array_eq() {
+    let array = ABCDE_SOA;
+    assert_eq!(array.as_slice(), array);
+}
+
+#[test]
+fn array_slice_mut() {
+    let mut array = ABCDE_SOA;
+    let mut slice = array.as_mut_slice();
+    for item in slice.iter_mut() {
+        *item.foo += 10;
+        *item.bar += 10;
+    }
+    let expected = ElArray::from_array(ABCDE.map(|el| El {
+        foo: el.foo + 10,
+        bar: el.bar + 10,
+        baz: el.baz,
+    }));
+    assert_eq!(slice, expected.as_slice());
+}
+
+#[test]
+fn slices() {
+    let soa = Soa::from(ABCDE);
+    let slices = soa.slices();
+    assert_eq!(slices.foo, soa.foo());
+    assert_eq!(slices.bar, soa.bar());
+    assert_eq!(slices.baz, soa.baz());
+}
+
+#[test]
+fn slices_mut() {
+    let mut soa = Soa::from(ABCDE);
+    let slices = soa.slices_mut();
+    for foo in slices.foo {
+        *foo += 10;
+    }
+    for bar in slices.bar {
+        *bar += 10;
+    }
+
+    let expected = ABCDE.map(|el| el.foo + 10);
+    assert_eq!(soa.foo(), expected);
+
+    let expected = ABCDE.map(|el| el.bar + 10);
+    assert_eq!(soa.bar(), expected);
+}
+
+#[test]
+fn array_with_box() {
+    #[derive(Soars)]
+    #[soa_array]
+    #[soa_derive(PartialEq)]
+    struct Example {
+        foo: Box<u8>,
+    }
+    let foo = Box::new(42_u8);
+    let x = ExampleArray::from_array([Example { foo }]);
+    let s = x.as_slice();
+    let _ = s.get(0).unwrap().foo;
+}
+
+fn assert_send<T: Send>(_t: T) {}
+fn assert_sync<T: Sync>(_t: T) {}
+
+#[test]
+fn send_sync() {
+    assert_send(soa![A]);
+    assert_sync(soa![A]);
+}
+
+#[test]
+#[allow(unused)]
+fn field_type_without_partial_eq() {
+    // See https://github.com/tim-harding/soa-rs/issues/10
+
+    struct TypeWithoutPartialEq {
+        foo: u8,
+    }
+
+    #[derive(Soars)]
+    struct Example {
+        a: TypeWithoutPartialEq,
+    }
+}
+
+#[test]
+fn serde() {
+    #[derive(Soars, serde::Deserialize)]
+    #[soa_derive(Debug, PartialEq)]
+    #[soa_derive(include(Ref), serde::Serialize)]
+    struct Test {
+        n: i32,
+        s: String,
+    }
+
+    let original = soa![
+        Test {
+            n: 10,
+            s: "Hello".to_string()
+        },
+        Test {
+            n: 20,
+            s: "Serde".to_string()
+        }
+    ];
+
+    let serial = serde_json::to_string(&original).unwrap();
+    let deserial: Soa<Test> = serde_json::from_str(&serial).unwrap();
+    assert_eq!(original, deserial);
+}
+
+#[test]
+fn serde_columnar() {
+    #[derive(Soars, Debug, PartialEq)]
+    #[soa_derive(Debug, PartialEq)]
+    #[soa_derive(include(Slices), serde::Serialize)]
+    #[soa_derive(include(Columns), serde::Deserialize)]
+    struct Test {
+        n: i32,
+        s: String,
+        b: bool,
+    }
+
+    let original = soa![
+        Test {
+            n: 10,
+            s: "Hello".to_string(),
+            b: true,
+        },
+        Test {
+            n: 20,
+            s: "Serde".to_string(),
+            b: false,
+        }
+    ];
+
+    let serial = serde_json::to_string(&Columnar(&original)).unwrap();
+    assert_eq!(
+        serial,
+        r#"{"n":[10,20],"s":["Hello","Serde"],"b":[true,false]}"#
+    );
+    let DeserializeColumnar(deserial) = serde_json::from_str(&serial).unwrap();
+    assert_eq!(original, deserial);
+
+    let mismatched = r#"{"n":[10,20],"s":["Hello"],"b":[true,false]}"#;
+    let error = serde_json::from_str::<DeserializeColumnar<Test>>(mismatched).unwrap_err();
+    assert_eq!(error.to_string(), "columns have different lengths");
+
+    let columns = TestColumns {
+        n: vec![1],
+        s: vec!["a".to_string()],
+        b: vec![true],
+    };
+    assert_eq!(
+        Test::from_columns(columns),
+        Ok(soa![Test {
+            n: 1,
+            s: "a".to_string(),
+            b: true
+        }])
+    );
+}
+
+#[test]
+fn columns_flatten() {
+    let columns = BodyColumns {
+        pos: Vector3Columns {
+            x: vec![1.0, 2.0],
+            y: vec![3.0, 4.0],
+            z: vec![5.0, 6.0],
+        },
+        vel: VelocityColumns(Vector3Columns {
+            x: vec![0.0, 0.0],
+            y: vec![0.0, 0.0],
+            z: vec![0.0, 0.0],
+        }),
+        mass: vec![7.0, 8.0],
+    };
+    let soa = Body::from_columns(columns).unwrap();
+    assert_eq!(soa.pos().z, [5.0, 6.0]);
+    assert_eq!(soa.mass(), [7.0, 8.0]);
+
+    let columns = BodyColumns {
+        pos: Vector3Columns {
+            x: vec![1.0, 2.0],
+            y: vec![3.0],
+            z: vec![5.0, 6.0],
+        },
+        vel: VelocityColumns(Vector3Columns {
+            x: vec![0.0, 0.0],
+            y: vec![0.0, 0.0],
+            z: vec![0.0, 0.0],
+        }),
+        mass: vec![7.0, 8.0],
+    };
+    assert_eq!(Body::from_columns(columns), Err(ColumnLengthError));
+}
+
+#[test]
+fn serde_matches_vec() {
+    #[derive(Soars, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+    #[soa_derive(Debug, PartialEq, serde::Serialize)]
+    struct Test(u8, String);
+
+    let vec = vec![Test(1, "one".into()), Test(2, "two".into())];
+    let soa: Soa<Test> = vec.iter().map(|t| Test(t.0, t.1.clone())).collect();
+    let serial = serde_json::to_string(&soa).unwrap();
+    assert_eq!(serial, serde_json::to_string(&vec).unwrap());
+    assert_eq!(serial, r#"[[1,"one"],[2,"two"]]"#);
+
+    let deserial: Soa<Test> = serde_json::from_str(&serial).unwrap();
+    assert_eq!(Vec::from(deserial), vec);
+
+    assert_eq!(serde_json::to_string(&Soa::<Test>::new()).unwrap(), "[]");
+    let empty: Soa<Test> = serde_json::from_str("[]").unwrap();
+    assert!(empty.is_empty());
+    assert!(serde_json::from_str::<Soa<Test>>("{}").is_err());
+    assert!(serde_json::from_str::<Soa<Test>>(r#"[[1,"one"],[2]]"#).is_err());
+}
+
+#[test]
+fn soa_attr() {
+    #[derive(Soars)]
+    #[soa_derive(include(Ref, Slices), serde::Serialize)]
+    #[soa_attr(Ref, serde(rename_all = "camelCase"))]
+    struct Test {
+        foo_bar: u8,
+        #[soa_attr(Ref, serde(rename = "b"))]
+        #[soa_attr(Slices, serde(skip))]
+        baz: u16,
+    }
+
+    let soa = soa![Test { foo_bar: 1, baz: 2 }, Test { foo_bar: 3, baz: 4 }];
+    assert_eq!(
+        serde_json::to_string(&soa.idx(0)).unwrap(),
+        r#"{"fooBar":1,"b":2}"#
+    );
+    assert_eq!(
+        serde_json::to_string(&soa.slices()).unwrap(),
+        r#"{"foo_bar":[1,3]}"#
+    );
+}
+
+#[test]
+fn mutex() {
     // Regression test for https://github.com/tim-harding/soa-rs/issues/13
     #[derive(Soars)]
     struct M(Mutex<usize>);
+    let soa = soa![M(Mutex::new(1))];
+    assert_eq!(*soa.idx(0).0.lock().unwrap(), 1);
+}
+
+#[test]
+fn extra_impls() {
+    let soa = soa![
+        ExtraImplTester {
+            things: 1,
+            stuff: 2
+        },
+        ExtraImplTester {
+            things: 1,
+            stuff: 3
+        },
+    ];
+    assert!(soa.idx(0) < soa.idx(1));
+    assert_eq!(soa.idx(0), soa.idx(0));
+}
+
+mod generic {
+    use super::*;
+
+    #[derive(Soars, Debug, Clone, PartialEq)]
+    #[soa_derive(Debug, PartialEq)]
+    struct Pair<T>(T, T);
+
+    #[derive(Soars, Debug, Clone, Copy, PartialEq)]
+    #[soa_derive(Debug, PartialEq)]
+    #[soa_array]
+    struct Wrapper<T>
+    where
+        T: Copy,
+    {
+        inner: T,
+        tag: u8,
+    }
+
+    #[test]
+    fn pair_u32() {
+        let mut soa = soa![Pair(1u32, 2), Pair(3, 4), Pair(5, 6)];
+        assert_eq!(soa.f0(), [1, 3, 5]);
+        assert_eq!(soa.f1(), [2, 4, 6]);
+
+        let slices = soa.slices();
+        assert_eq!(slices.0, [1, 3, 5]);
+        assert_eq!(slices.1, [2, 4, 6]);
+
+        assert_eq!(soa.idx(1), PairRef(&3, &4));
+        assert_eq!(soa.idx(1..), soa![Pair(3, 4), Pair(5, 6)]);
+        *soa.idx_mut(0).1 = 10;
+        assert_eq!(soa.pop(), Some(Pair(5, 6)));
+
+        let collected: Vec<_> = soa.into_iter().collect();
+        assert_eq!(collected, [Pair(1, 10), Pair(3, 4)]);
+    }
+
+    #[test]
+    fn pair_box() {
+        let soa: Soa<Pair<Box<u8>>> = (0..4).map(|i| Pair(Box::new(i), Box::new(i * 2))).collect();
+        assert_eq!(soa.len(), 4);
+        assert_eq!(*soa.idx(2).1, Box::new(4));
+        assert_eq!(
+            soa.slices().0.iter().map(|b| **b).collect::<Vec<_>>(),
+            [0, 1, 2, 3]
+        );
+
+        let copied: Soa<_> = soa.iter().collect();
+        assert_eq!(copied, soa);
+
+        let mut iter = soa.into_iter();
+        assert_eq!(iter.next_back(), Some(Pair(Box::new(3), Box::new(6))));
+        assert_eq!(iter.next(), Some(Pair(Box::new(0), Box::new(0))));
+        assert_eq!(iter.len(), 2);
+    }
+
+    #[test]
+    fn wrapper() {
+        let soa = soa![
+            Wrapper {
+                inner: 1.5f32,
+                tag: 0
+            },
+            Wrapper { inner: 2.5, tag: 1 },
+        ];
+        assert_eq!(soa.inner(), [1.5, 2.5]);
+        assert_eq!(soa.tag(), [0, 1]);
+
+        const ARRAY: WrapperArray<u16, 2> =
+            WrapperArray::from_array([Wrapper { inner: 7, tag: 8 }, Wrapper { inner: 9, tag: 10 }]);
+        assert_eq!(ARRAY.as_slice().inner(), [7, 9]);
+        assert_eq!(
+            ARRAY.as_slice().idx(1),
+            WrapperRef {
+                inner: &9,
+                tag: &10
+            }
+        );
+    }
+}
+
+#[cfg(feature = "nightly")]
+mod allocator {
+    use super::*;
+
+    #[test]
+    fn with_capacity_in() {
+        let before = allocations();
+        let soa = Soa::<El, _>::with_capacity_in(10, System);
+        assert!(soa.is_empty());
+        assert_eq!(soa.iter().count(), 0);
+        drop(soa);
+        // System bypasses the counting global allocator
+        assert_eq!(allocations(), before);
+    }
+
+    #[test]
+    fn new_in() {
+        let soa = Soa::<El, _>::new_in(System);
+        assert!(soa.is_empty());
+        let soa = Soa::<Unit, _>::with_capacity_in(10, System);
+        assert!(soa.is_empty());
+    }
 }
@@ -3,7 +3,8 @@ use crate::Soars;
 /// Similar to [`AsRef`], but for [`Soars::Ref`].
 ///
 /// This is primarily used to provide convenient implementations of standard
-/// traits for [`Slice`].
+/// traits for [`Slice`]. It is implemented for references as well, so generic
+/// code can accept both owned and borrowed elements.
 ///
 /// [`Slice`]: crate::Slice
 pub trait AsSoaRef {
@@ -19,3 +20,25 @@ pub trait AsSoaRef {
     /// Converts this type to an SoA reference of the associated type.
     fn as_soa_ref(&self) -> <Self::Item as Soars>::Ref<'_>;
 }
+
+impl<T> AsSoaRef for &T
+where
+    T: AsSoaRef + ?Sized,
+{
+    type Item = T::Item;
+
+    fn as_soa_ref(&self) -> <Self::Item as Soars>::Ref<'_> {
+        (**self).as_soa_ref()
+    }
+}
+
+impl<T> AsSoaRef for &mut T
+where
+    T: AsSoaRef + ?Sized,
+{
+    type Item = T::Item;
+
+    fn as_soa_ref(&self) -> <Self::Item as Soars>::Ref<'_> {
+        (**self).as_soa_ref()
+    }
+}
@@ -0,0 +1,21 @@
+//! Support for the derive macro's `bytemuck` methods.
+//!
+//! The methods are only callable when the field types are `Pod`, which is
+//! expressed as bounds on the concrete field types. These generic wrappers keep
+//! `bytemuck`'s blanket impls from making those bounds ambiguous.
+
+pub use ::bytemuck::Pod;
+
+pub fn cast_slice<T>(slice: &[T]) -> &[u8]
+where
+    T: Pod,
+{
+    ::bytemuck::cast_slice(slice)
+}
+
+pub fn cast_slice_mut<T>(slice: &mut [T]) -> &mut [u8]
+where
+    T: Pod,
+{
+    ::bytemuck::cast_slice_mut(slice)
+}
@@ -0,0 +1,68 @@
+/// A description of how the fields of a [`Soa`] are laid out in its
+/// allocation.
+///
+/// This is produced by [`Soa::capacity_report`]. It is intended for tuning
+/// `#[align]` attributes by showing how much padding they introduce compared
+/// to packing the field arrays back to back.
+///
+/// [`Soa`]: crate::Soa
+/// [`Soa::capacity_report`]: crate::Soa::capacity_report
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CapacityReport {
+    /// The layout of each field array, in allocation order.
+    pub fields: Vec<FieldReport>,
+    /// The total size of the allocation in bytes.
+    pub size: usize,
+    /// The size the allocation would have if the field arrays were packed
+    /// without any padding.
+    pub packed_size: usize,
+}
+
+/// The placement of a single field array within a [`CapacityReport`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct FieldReport {
+    /// The field name, or its index for tuple structs.
+    pub name: &'static str,
+    /// The offset of the array from the start of the allocation in bytes.
+    pub offset: usize,
+    /// The size of the array in bytes.
+    pub size: usize,
+    /// The number of padding bytes between the end of the previous array and
+    /// the start of this one.
+    pub padding: usize,
+}
+
+impl CapacityReport {
+    #[doc(hidden)]
+    pub fn new<I>(size: usize, fields: I) -> Self
+    where
+        I: IntoIterator<Item = (&'static str, usize, usize)>,
+    {
+        let mut end = 0;
+        let fields: Vec<_> = fields
+            .into_iter()
+            .map(|(name, offset, size)| {
+                let padding = offset - end;
+                end = offset + size;
+                FieldReport {
+                    name,
+                    offset,
+                    size,
+                    padding,
+                }
+            })
+            .collect();
+        let packed_size = fields.iter().map(|field| field.size).sum();
+        Self {
+            fields,
+            size,
+            packed_size,
+        }
+    }
+
+    /// Returns the total number of bytes spent on padding, including any
+    /// trailing padding after the last array.
+    pub fn padding(&self) -> usize {
+        self.size - self.packed_size
+    }
+}
@@ -0,0 +1,219 @@
+use crate::{Slice, SliceMut, SliceRef, SoaRaw, Soars};
+use std::{iter::FusedIterator, marker::PhantomData};
+
+/// An iterator over a [`Slice`] in (non-overlapping) chunks separated by a
+/// predicate.
+///
+/// This struct is created by the [`chunk_by`] method.
+///
+/// [`chunk_by`]: Slice::chunk_by
+pub struct ChunkBy<'a, T, F>
+where
+    T: 'a + Soars,
+{
+    slice: Slice<T, ()>,
+    len: usize,
+    pred: F,
+    marker: PhantomData<&'a T>,
+}
+
+impl<'a, T, F> ChunkBy<'a, T, F>
+where
+    T: Soars,
+{
+    pub(crate) fn new(slice: &'a Slice<T>, pred: F) -> Self {
+        Self {
+            len: slice.len(),
+            slice: unsafe { slice.as_sized() },
+            pred,
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<'a, T, F> Iterator for ChunkBy<'a, T, F>
+where
+    T: Soars,
+    F: FnMut(T::Ref<'_>, T::Ref<'_>) -> bool,
+{
+    type Item = SliceRef<'a, T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let len = run_len_front(self.slice, self.len, &mut self.pred)?;
+        let out = SliceRef {
+            slice: self.slice,
+            len,
+            marker: PhantomData,
+        };
+        self.len -= len;
+        self.slice.raw = unsafe { self.slice.raw().offset(len) };
+        Some(out)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len.min(1), Some(self.len))
+    }
+}
+
+impl<T, F> DoubleEndedIterator for ChunkBy<'_, T, F>
+where
+    T: Soars,
+    F: FnMut(T::Ref<'_>, T::Ref<'_>) -> bool,
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let len = run_len_back(self.slice, self.len, &mut self.pred)?;
+        self.len -= len;
+        let mut slice = self.slice;
+        slice.raw = unsafe { self.slice.raw().offset(self.len) };
+        Some(SliceRef {
+            slice,
+            len,
+            marker: PhantomData,
+        })
+    }
+}
+
+impl<T, F> FusedIterator for ChunkBy<'_, T, F>
+where
+    T: Soars,
+    F: FnMut(T::Ref<'_>, T::Ref<'_>) -> bool,
+{
+}
+
+/// An iterator over a [`Slice`] in (non-overlapping) mutable chunks separated
+/// by a predicate.
+///
+/// This struct is created by the [`chunk_by_mut`] method.
+///
+/// [`chunk_by_mut`]: Slice::chunk_by_mut
+pub struct ChunkByMut<'a, T, F>
+where
+    T: 'a + Soars,
+{
+    slice: Slice<T, ()>,
+    len: usize,
+    pred: F,
+    marker: PhantomData<&'a mut T>,
+}
+
+impl<'a, T, F> ChunkByMut<'a, T, F>
+where
+    T: Soars,
+{
+    pub(crate) fn new(slice: &'a mut Slice<T>, pred: F) -> Self {
+        Self {
+            len: slice.len(),
+            slice: unsafe { slice.as_sized() },
+            pred,
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<'a, T, F> Iterator for ChunkByMut<'a, T, F>
+where
+    T: Soars,
+    F: FnMut(T::Ref<'_>, T::Ref<'_>) -> bool,
+{
+    type Item = SliceMut<'a, T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let len = run_len_front(self.slice, self.len, &mut self.pred)?;
+        let out = SliceMut {
+            slice: self.slice,
+            len,
+            marker: PhantomData,
+        };
+        self.len -= len;
+        self.slice.raw = unsafe { self.slice.raw().offset(len) };
+        Some(out)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len.min(1), Some(self.len))
+    }
+}
+
+impl<T, F> DoubleEndedIterator for ChunkByMut<'_, T, F>
+where
+    T: Soars,
+    F: FnMut(T::Ref<'_>, T::Ref<'_>) -> bool,
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let len = run_len_back(self.slice, self.len, &mut self.pred)?;
+        self.len -= len;
+        let mut slice = self.slice;
+        slice.raw = unsafe { self.slice.raw().offset(self.len) };
+        Some(SliceMut {
+            slice,
+            len,
+            marker: PhantomData,
+        })
+    }
+}
+
+impl<T, F> FusedIterator for ChunkByMut<'_, T, F>
+where
+    T: Soars,
+    F: FnMut(T::Ref<'_>, T::Ref<'_>) -> bool,
+{
+}
+
+/// Returns the length of the run at the start of the `len` elements of
+/// `slice`, or [`None`] if there are no elements.
+fn run_len_front<T, F>(slice: Slice<T, ()>, len: usize, pred: &mut F) -> Option<usize>
+where
+    T: Soars,
+    F: FnMut(T::Ref<'_>, T::Ref<'_>) -> bool,
+{
+    if len == 0 {
+        return None;
+    }
+
+    let mut run = 1;
+    while run < len {
+        // SAFETY: Both indices are in bounds and the references do not
+        // outlive this call.
+        let (a, b) = unsafe {
+            (
+                slice.raw().offset(run - 1).get_ref(),
+                slice.raw().offset(run).get_ref(),
+            )
+        };
+        if !pred(a, b) {
+            break;
+        }
+        run += 1;
+    }
+    Some(run)
+}
+
+/// Returns the length of the run at the end of the `len` elements of `slice`,
+/// or [`None`] if there are no elements.
+fn run_len_back<T, F>(slice: Slice<T, ()>, len: usize, pred: &mut F) -> Option<usize>
+where
+    T: Soars,
+    F: FnMut(T::Ref<'_>, T::Ref<'_>) -> bool,
+{
+    if len == 0 {
+        return None;
+    }
+
+    let mut run = 1;
+    while run < len {
+        let start = len - run;
+        // SAFETY: Both indices are in bounds and the references do not
+        // outlive this call.
+        let (a, b) = unsafe {
+            (
+                slice.raw().offset(start - 1).get_ref(),
+                slice.raw().offset(start).get_ref(),
+            )
+        };
+        if !pred(a, b) {
+            break;
+        }
+        run += 1;
+    }
+    Some(run)
+}
@@ -0,0 +1,124 @@
+use crate::{Slice, SliceMut, SliceRef, SoaRaw, Soars};
+use std::marker::PhantomData;
+
+/// An iterator over a [`Slice`] in (non-overlapping) chunks of `chunk_size`
+/// elements.
+///
+/// When the slice len is not evenly divided by the chunk size, the last slice
+/// of the iteration will be the remainder.
+///
+/// This struct is created by the [`chunks`] method.
+///
+/// [`chunks`]: Slice::chunks
+pub struct Chunks<'a, T>
+where
+    T: 'a + Soars,
+{
+    slice: Slice<T, ()>,
+    len: usize,
+    chunk_size: usize,
+    marker: PhantomData<&'a T>,
+}
+
+impl<'a, T> Chunks<'a, T>
+where
+    T: Soars,
+{
+    pub(crate) fn new(slice: &'a Slice<T>, chunk_size: usize) -> Self {
+        Self {
+            len: slice.len(),
+            slice: unsafe { slice.as_sized() },
+            chunk_size,
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<'a, T> Iterator for Chunks<'a, T>
+where
+    T: Soars,
+{
+    type Item = SliceRef<'a, T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            None
+        } else {
+            let len = self.chunk_size.min(self.len);
+            let out = SliceRef {
+                slice: self.slice,
+                len,
+                marker: PhantomData,
+            };
+            self.len -= len;
+            self.slice.raw = unsafe { self.slice.raw().offset(len) };
+            Some(out)
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let n = self.len.div_ceil(self.chunk_size);
+        (n, Some(n))
+    }
+}
+
+/// An iterator over a [`Slice`] in (non-overlapping) mutable chunks of
+/// `chunk_size` elements.
+///
+/// When the slice len is not evenly divided by the chunk size, the last slice
+/// of the iteration will be the remainder.
+///
+/// This struct is created by the [`chunks_mut`] method.
+///
+/// [`chunks_mut`]: Slice::chunks_mut
+pub struct ChunksMut<'a, T>
+where
+    T: 'a + Soars,
+{
+    slice: Slice<T, ()>,
+    len: usize,
+    chunk_size: usize,
+    marker: PhantomData<&'a mut T>,
+}
+
+impl<'a, T> ChunksMut<'a, T>
+where
+    T: Soars,
+{
+    pub(crate) fn new(slice: &'a mut Slice<T>, chunk_size: usize) -> Self {
+        Self {
+            len: slice.len(),
+            slice: unsafe { slice.as_sized() },
+            chunk_size,
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<'a, T> Iterator for ChunksMut<'a, T>
+where
+    T: Soars,
+{
+    type Item = SliceMut<'a, T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            None
+        } else {
+            let len = self.chunk_size.min(self.len);
+            let out = SliceMut {
+                slice: self.slice,
+                len,
+                marker: PhantomData,
+            };
+            self.len -= len;
+            self.slice.raw = unsafe { self.slice.raw().offset(len) };
+            Some(out)
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let n = self.len.div_ceil(self.chunk_size);
+        (n, Some(n))
+    }
+}
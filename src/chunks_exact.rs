@@ -20,6 +20,7 @@ where
     remainder: SliceRef<'a, T>,
     parts_remaining: usize,
     chunk_size: usize,
+    offset: usize,
 }
 
 impl<'a, T> ChunksExact<'a, T>
@@ -37,6 +38,7 @@ where
             remainder,
             parts_remaining: fst_len / chunk_size,
             chunk_size,
+            offset: 0,
         }
     }
 
@@ -45,6 +47,30 @@ where
     pub fn remainder(&self) -> &Slice<T> {
         self.remainder.as_ref()
     }
+
+    /// Creates an iterator that yields each chunk along with the index of its
+    /// first element in the original slice.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use soa_rs::{Soa, Soars, soa, AsSlice};
+    /// # #[derive(Soars, Debug, PartialEq)]
+    /// # #[soa_derive(Debug, PartialEq)]
+    /// # struct Foo(char);
+    /// let soa = soa![Foo('l'), Foo('o'), Foo('r'), Foo('e'), Foo('m')];
+    /// let mut iter = soa.chunks_exact(2).enumerate_offsets();
+    /// assert_eq!(iter.next(), Some((0, soa![Foo('l'), Foo('o')].as_slice())));
+    /// assert_eq!(iter.next(), Some((2, soa![Foo('r'), Foo('e')].as_slice())));
+    /// assert!(iter.next().is_none());
+    /// ```
+    pub fn enumerate_offsets(self) -> impl Iterator<Item = (usize, SliceRef<'a, T>)> {
+        let Self {
+            offset, chunk_size, ..
+        } = self;
+        self.enumerate()
+            .map(move |(i, chunk)| (offset + i * chunk_size, chunk))
+    }
 }
 
 impl<'a, T> Iterator for ChunksExact<'a, T>
@@ -63,6 +89,7 @@ where
                 marker: PhantomData,
             };
             self.parts_remaining -= 1;
+            self.offset += self.chunk_size;
             self.slice.raw = unsafe { self.slice.raw().offset(self.chunk_size) };
             Some(out)
         }
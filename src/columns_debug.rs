@@ -0,0 +1,20 @@
+use std::fmt::{self, Debug, Formatter};
+
+/// Formats with the wrapped function.
+///
+/// Used by the `columns_debug` method generated by the [`Soars`] derive macro.
+///
+/// [`Soars`]: crate::Soars
+#[doc(hidden)]
+pub struct ColumnsDebug<F>(pub F)
+where
+    F: Fn(&mut Formatter<'_>) -> fmt::Result;
+
+impl<F> Debug for ColumnsDebug<F>
+where
+    F: Fn(&mut Formatter<'_>) -> fmt::Result,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        (self.0)(f)
+    }
+}
@@ -0,0 +1,72 @@
+use crate::{
+    iter_raw::{iter_with_raw, IterRaw, IterRawAdapter},
+    Slice, Soa, SoaRaw, Soars,
+};
+use std::{fmt::Debug, iter::FusedIterator, marker::PhantomData, mem::needs_drop};
+
+/// An iterator that moves every element out of a [`Soa`] while keeping its
+/// allocation.
+///
+/// This struct is created by the [`drain_all`] method. The [`Soa`] is empty
+/// from the moment the iterator is created, so if the iterator is leaked, the
+/// elements it has not yielded are leaked as well, but the [`Soa`] remains
+/// valid.
+///
+/// [`drain_all`]: Soa::drain_all
+pub struct DrainAll<'a, T>
+where
+    T: 'a + Soars,
+{
+    pub(crate) iter_raw: IterRaw<T, Self>,
+    pub(crate) _marker: PhantomData<&'a mut Soa<T>>,
+}
+
+impl<'a, T> IterRawAdapter<T> for DrainAll<'a, T>
+where
+    T: 'a + Soars,
+{
+    type Item = T;
+
+    fn item_from_raw(raw: T::Raw) -> Self::Item {
+        unsafe { raw.get() }
+    }
+}
+
+impl<'a, T> Debug for DrainAll<'a, T>
+where
+    T: 'a + Soars,
+    for<'b> T::Ref<'b>: Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("DrainAll").field(&self.as_slice()).finish()
+    }
+}
+
+impl<'a, T> DrainAll<'a, T>
+where
+    T: 'a + Soars,
+{
+    /// Returns an immutable slice of all elements that have not been yielded
+    /// yet.
+    pub fn as_slice(&self) -> &Slice<T> {
+        unsafe { self.iter_raw.as_slice() }
+    }
+
+    /// Returns a mutable slice of all elements that have not been yielded yet.
+    pub fn as_mut_slice(&mut self) -> &mut Slice<T> {
+        unsafe { self.iter_raw.as_mut_slice() }
+    }
+}
+
+impl<'a, T> Drop for DrainAll<'a, T>
+where
+    T: 'a + Soars,
+{
+    fn drop(&mut self) {
+        if needs_drop::<T>() {
+            for _ in self.by_ref() {}
+        }
+    }
+}
+
+iter_with_raw!(DrainAll<'a, T>, 'a);
@@ -0,0 +1,77 @@
+use crate::{Soa, SoaRaw, Soars};
+use std::iter::FusedIterator;
+
+/// An iterator that removes the elements of a [`Soa`] matching a predicate.
+///
+/// This struct is created by the [`extract_if`] method. Elements that are not
+/// yielded are shifted back to close the gaps when the iterator is dropped. If
+/// the iterator is leaked, the elements past the first removal may be leaked as
+/// well, but the [`Soa`] remains valid.
+///
+/// [`extract_if`]: Soa::extract_if
+pub struct ExtractIf<'a, T, F>
+where
+    T: Soars,
+{
+    pub(crate) soa: &'a mut Soa<T>,
+    /// The index of the next element to inspect.
+    pub(crate) idx: usize,
+    /// The number of elements removed so far.
+    pub(crate) del: usize,
+    /// The length of the [`Soa`] before extraction started.
+    pub(crate) old_len: usize,
+    pub(crate) pred: F,
+}
+
+impl<T, F> Iterator for ExtractIf<'_, T, F>
+where
+    T: Soars,
+    F: FnMut(T::RefMut<'_>) -> bool,
+{
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.idx < self.old_len {
+            let current = unsafe { self.soa.raw().offset(self.idx) };
+            let extract = (self.pred)(unsafe { current.get_mut() });
+            self.idx += 1;
+            if extract {
+                self.del += 1;
+                return Some(unsafe { current.get() });
+            } else if self.del > 0 {
+                unsafe {
+                    current.copy_to(self.soa.raw().offset(self.idx - 1 - self.del), 1);
+                }
+            }
+        }
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(self.old_len - self.idx))
+    }
+}
+
+impl<T, F> FusedIterator for ExtractIf<'_, T, F>
+where
+    T: Soars,
+    F: FnMut(T::RefMut<'_>) -> bool,
+{
+}
+
+impl<T, F> Drop for ExtractIf<'_, T, F>
+where
+    T: Soars,
+{
+    fn drop(&mut self) {
+        let tail = self.old_len - self.idx;
+        if self.del > 0 && tail > 0 {
+            unsafe {
+                let src = self.soa.raw().offset(self.idx);
+                let dst = self.soa.raw().offset(self.idx - self.del);
+                src.copy_to(dst, tail);
+            }
+        }
+        self.soa.len = self.old_len - self.del;
+    }
+}
@@ -0,0 +1,84 @@
+/// Summary statistics over the values of a single numeric field.
+///
+/// This is produced by the `debug_field_stats` method that the [`Soars`]
+/// derive macro generates for types tagged with `#[soa_stats]`. It is intended
+/// for tracing and debugging, such as spotting outliers or NaNs in a column.
+///
+/// [`Soars`]: crate::Soars
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FieldStats<T> {
+    /// The smallest value, or [`None`] if there are no comparable values.
+    pub min: Option<T>,
+    /// The largest value, or [`None`] if there are no comparable values.
+    pub max: Option<T>,
+    /// The sum of all values.
+    pub sum: f64,
+    /// The arithmetic mean of all values, or [`None`] if the field is empty.
+    pub mean: Option<f64>,
+}
+
+impl<T> FieldStats<T>
+where
+    T: Numeric,
+{
+    /// Computes the statistics for `values` in a single pass.
+    ///
+    /// Values that do not compare equal to themselves, such as NaN, are
+    /// skipped for `min` and `max` but propagate to `sum` and `mean`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use soa_rs::FieldStats;
+    /// let stats = FieldStats::from_slice(&[3u8, 1, 2]);
+    /// assert_eq!(stats.min, Some(1));
+    /// assert_eq!(stats.max, Some(3));
+    /// assert_eq!(stats.sum, 6.0);
+    /// assert_eq!(stats.mean, Some(2.0));
+    /// ```
+    pub fn from_slice(values: &[T]) -> Self {
+        let mut min: Option<T> = None;
+        let mut max: Option<T> = None;
+        let mut sum = 0.0;
+        for &value in values {
+            sum += value.to_f64();
+            if value.partial_cmp(&value).is_none() {
+                continue;
+            }
+            if min.is_none_or(|min| value < min) {
+                min = Some(value);
+            }
+            if max.is_none_or(|max| value > max) {
+                max = Some(value);
+            }
+        }
+
+        Self {
+            min,
+            max,
+            sum,
+            mean: (!values.is_empty()).then(|| sum / values.len() as f64),
+        }
+    }
+}
+
+/// A primitive numeric type that [`FieldStats`] can be computed for.
+pub trait Numeric: Copy + PartialOrd {
+    /// Converts the value to an `f64`, possibly losing precision.
+    fn to_f64(self) -> f64;
+}
+
+macro_rules! impl_numeric {
+    ($($t:ty),*) => {
+        $(
+        impl Numeric for $t {
+            #[inline]
+            fn to_f64(self) -> f64 {
+                self as f64
+            }
+        }
+        )*
+    };
+}
+
+impl_numeric!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, f32, f64);
@@ -0,0 +1,31 @@
+use crate::Soars;
+
+/// Creates an owned value from a [`Soars::Ref`], similar to [`ToOwned`] for
+/// references to SoA elements.
+///
+/// When using the [`Soars`] derive macro, this is implemented by cloning each
+/// field, as long as every field implements [`Clone`]. The derive also uses it
+/// to implement [`Extend`] and [`FromIterator`] for [`Soa`] over `FooRef` and
+/// `FooRefMut`, so that elements can be copied between containers by
+/// reference.
+///
+/// # Examples
+///
+/// ```
+/// # use soa_rs::{Soa, Soars, FromSoaRef, soa};
+/// # #[derive(Soars, Debug, PartialEq)]
+/// # #[soa_derive(Debug, PartialEq)]
+/// # struct Foo(String);
+/// let soa1 = soa![Foo("a".into()), Foo("b".into())];
+/// assert_eq!(Foo::from_soa_ref(soa1.idx(1)), Foo("b".into()));
+///
+/// let mut soa2 = Soa::<Foo>::new();
+/// soa2.extend(soa1.iter());
+/// assert_eq!(soa1, soa2);
+/// ```
+///
+/// [`Soa`]: crate::Soa
+pub trait FromSoaRef: Soars {
+    /// Creates an owned value from the given reference.
+    fn from_soa_ref(item: Self::Ref<'_>) -> Self;
+}
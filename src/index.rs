@@ -1,7 +1,9 @@
 use crate::{Slice, SliceMut, SliceRef, SoaRaw, Soars};
 use std::{
     marker::PhantomData,
-    ops::{Range, RangeFrom, RangeFull, RangeInclusive, RangeTo, RangeToInclusive},
+    ops::{
+        Bound, Range, RangeBounds, RangeFrom, RangeFull, RangeInclusive, RangeTo, RangeToInclusive,
+    },
 };
 
 /// A helper trait for indexing operations.
@@ -209,3 +211,78 @@ where
         (*self.start()..*self.end() + 1).get_mut(slice)
     }
 }
+
+impl<T> SoaIndex<T> for (usize, usize)
+where
+    T: Soars,
+{
+    type Output<'a> = (T::Ref<'a>, T::Ref<'a>)
+    where
+        T: 'a;
+
+    type OutputMut<'a> = (T::RefMut<'a>, T::RefMut<'a>)
+    where
+        T: 'a;
+
+    #[inline]
+    fn get(self, slice: &Slice<T>) -> Option<Self::Output<'_>> {
+        let [a, b] = [self.0, self.1].get(slice)?;
+        Some((a, b))
+    }
+
+    /// Returns [`None`] if the indices are equal, since that would alias the
+    /// same element mutably.
+    #[inline]
+    fn get_mut(self, slice: &mut Slice<T>) -> Option<Self::OutputMut<'_>> {
+        let [a, b] = [self.0, self.1].get_mut(slice)?;
+        Some((a, b))
+    }
+}
+
+impl<T, const N: usize> SoaIndex<T> for [usize; N]
+where
+    T: Soars,
+{
+    type Output<'a> = [T::Ref<'a>; N]
+    where
+        T: 'a;
+
+    type OutputMut<'a> = [T::RefMut<'a>; N]
+    where
+        T: 'a;
+
+    #[inline]
+    fn get(self, slice: &Slice<T>) -> Option<Self::Output<'_>> {
+        self.iter()
+            .all(|&i| i < slice.len())
+            .then(|| self.map(|i| unsafe { slice.raw().offset(i).get_ref() }))
+    }
+
+    /// Returns [`None`] if any two indices are equal, since that would alias
+    /// the same element mutably.
+    #[inline]
+    fn get_mut(self, slice: &mut Slice<T>) -> Option<Self::OutputMut<'_>> {
+        slice.get_disjoint_mut(self)
+    }
+}
+
+/// Converts `range` to the equivalent [`Range`] for a slice of length `len`.
+///
+/// Returns [`None`] if the start is past the end, if the end is past `len`, or
+/// if either bound overflows.
+pub(crate) fn bounds<R>(range: R, len: usize) -> Option<Range<usize>>
+where
+    R: RangeBounds<usize>,
+{
+    let start = match range.start_bound() {
+        Bound::Included(&start) => start,
+        Bound::Excluded(&start) => start.checked_add(1)?,
+        Bound::Unbounded => 0,
+    };
+    let end = match range.end_bound() {
+        Bound::Included(&end) => end.checked_add(1)?,
+        Bound::Excluded(&end) => end,
+        Bound::Unbounded => len,
+    };
+    (start <= end && end <= len).then_some(start..end)
+}
@@ -5,7 +5,7 @@ use crate::{
 use std::{
     fmt::Debug,
     iter::FusedIterator,
-    mem::{needs_drop, size_of},
+    mem::{needs_drop, size_of, ManuallyDrop},
 };
 
 /// An iterator that moves out of a [`Soa`].
@@ -68,6 +68,87 @@ where
     pub fn as_mut_slice(&mut self) -> &mut Slice<T> {
         unsafe { self.iter_raw.as_mut_slice() }
     }
+
+    /// Maps each remaining element with `f` and collects the results into a
+    /// [`Soa`], reusing the allocation of the original [`Soa`].
+    ///
+    /// This is equivalent to `iter.map(f).collect::<Soa<T>>()` but never
+    /// allocates. [`Vec`] performs this optimization automatically through
+    /// specialization, which is not available on stable Rust.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use soa_rs::{Soa, Soars, soa};
+    /// # #[derive(Soars, Debug, PartialEq)]
+    /// # #[soa_derive(Debug, PartialEq)]
+    /// # struct Foo(usize);
+    /// let soa = soa![Foo(1), Foo(2), Foo(3)];
+    /// let mut iter = soa.into_iter();
+    /// iter.next();
+    /// let soa = iter.map_in_place(|foo| Foo(foo.0 * 10));
+    /// assert_eq!(soa, soa![Foo(20), Foo(30)]);
+    /// ```
+    pub fn map_in_place<F>(self, mut f: F) -> Soa<T>
+    where
+        F: FnMut(T) -> T,
+    {
+        /// Drops the mapped and unvisited elements and frees the buffer if
+        /// `f` panics.
+        struct InPlaceGuard<T>
+        where
+            T: Soars,
+        {
+            base: T::Raw,
+            src: T::Raw,
+            cap: usize,
+            len: usize,
+            read: usize,
+            written: usize,
+        }
+
+        impl<T> Drop for InPlaceGuard<T>
+        where
+            T: Soars,
+        {
+            fn drop(&mut self) {
+                for i in 0..self.written {
+                    drop(unsafe { self.base.offset(i).get() });
+                }
+                for i in self.read..self.len {
+                    drop(unsafe { self.src.offset(i).get() });
+                }
+                unsafe { self.base.dealloc(self.cap) };
+            }
+        }
+
+        if size_of::<T>() == 0 {
+            return self.map(f).collect();
+        }
+
+        let this = ManuallyDrop::new(self);
+        let mut guard = InPlaceGuard::<T> {
+            base: unsafe { <T::Raw as SoaRaw>::from_parts(this.ptr, this.cap) },
+            src: this.iter_raw.slice.raw(),
+            cap: this.cap,
+            len: this.iter_raw.len,
+            read: 0,
+            written: 0,
+        };
+
+        while guard.read < guard.len {
+            let element = unsafe { guard.src.offset(guard.read).get() };
+            guard.read += 1;
+            // The destination is never ahead of the source, so this only
+            // overwrites elements that have already been read.
+            let mapped = f(element);
+            unsafe { guard.base.offset(guard.written).set(mapped) };
+            guard.written += 1;
+        }
+
+        let guard = ManuallyDrop::new(guard);
+        unsafe { Soa::from_raw_parts(this.ptr, guard.written, guard.cap) }
+    }
 }
 
 impl<T> Drop for IntoIter<T>
@@ -1,6 +1,6 @@
 use crate::{
     iter_raw::{iter_with_raw, IterRaw, IterRawAdapter},
-    Slice, SoaRaw, Soars,
+    FromSoaRef, Slice, SoaRaw, Soars,
 };
 use std::{
     fmt::{self, Debug, Formatter},
@@ -69,6 +69,79 @@ where
     pub fn as_slice(&self) -> &'a Slice<T> {
         unsafe { self.iter_raw.as_slice() }
     }
+
+    /// Creates an iterator which clones each element into an owned `T` using
+    /// [`FromSoaRef`].
+    ///
+    /// This is the SoA counterpart of [`Iterator::cloned`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use soa_rs::{Soa, Soars, soa};
+    /// # #[derive(Soars, Debug, PartialEq)]
+    /// # #[soa_derive(Debug, PartialEq)]
+    /// # struct Foo(String);
+    /// let soa = soa![Foo("a".into()), Foo("b".into())];
+    /// let vec: Vec<Foo> = soa.iter().cloned().collect();
+    /// assert_eq!(vec, [Foo("a".into()), Foo("b".into())]);
+    /// ```
+    pub fn cloned(
+        self,
+    ) -> impl DoubleEndedIterator<Item = T> + ExactSizeIterator + FusedIterator + 'a
+    where
+        T: FromSoaRef,
+    {
+        self.map(T::from_soa_ref)
+    }
+
+    /// Skips the first `n` elements in O(1), returning an [`Iter`] over the
+    /// rest.
+    ///
+    /// Unlike [`Iterator::skip`], the result still provides
+    /// [`Iter::as_slice`]. If fewer than `n` elements remain, the result is
+    /// empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use soa_rs::{Soa, Soars, soa};
+    /// # #[derive(Soars, Debug, PartialEq)]
+    /// # #[soa_derive(Debug, PartialEq)]
+    /// # struct Foo(usize);
+    /// let soa = soa![Foo(1), Foo(2), Foo(3), Foo(4)];
+    /// let iter = soa.iter().skip_n(1);
+    /// assert_eq!(iter.as_slice(), &soa![Foo(2), Foo(3), Foo(4)]);
+    /// ```
+    pub fn skip_n(mut self, n: usize) -> Self {
+        let n = n.min(self.iter_raw.len);
+        self.iter_raw.slice.raw = unsafe { self.iter_raw.slice.raw().offset(n) };
+        self.iter_raw.len -= n;
+        self
+    }
+
+    /// Limits the iterator to the first `n` elements in O(1), returning an
+    /// [`Iter`] over them.
+    ///
+    /// Unlike [`Iterator::take`], the result still provides
+    /// [`Iter::as_slice`]. If fewer than `n` elements remain, the iterator is
+    /// unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use soa_rs::{Soa, Soars, soa};
+    /// # #[derive(Soars, Debug, PartialEq)]
+    /// # #[soa_derive(Debug, PartialEq)]
+    /// # struct Foo(usize);
+    /// let soa = soa![Foo(1), Foo(2), Foo(3), Foo(4)];
+    /// let iter = soa.iter().take_n(2);
+    /// assert_eq!(iter.as_slice(), &soa![Foo(1), Foo(2)]);
+    /// ```
+    pub fn take_n(mut self, n: usize) -> Self {
+        self.iter_raw.len = self.iter_raw.len.min(n);
+        self
+    }
 }
 
 impl<'a, T> IterRawAdapter<T> for Iter<'a, T>
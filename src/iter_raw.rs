@@ -1,5 +1,5 @@
 use crate::{Slice, SoaRaw, Soars};
-use std::{fmt::Debug, iter::FusedIterator, marker::PhantomData};
+use std::{fmt::Debug, iter::FusedIterator, marker::PhantomData, mem::needs_drop};
 
 pub trait IterRawAdapter<T>
 where
@@ -31,6 +31,82 @@ where
     pub(crate) unsafe fn as_mut_slice(&mut self) -> &mut Slice<T> {
         unsafe { self.slice.as_unsized_mut(self.len) }
     }
+
+    /// Drops the items at `start..end`, which the iterator must no longer
+    /// track. If one of the destructors panics, the rest are still dropped.
+    unsafe fn drop_range(raw: T::Raw, start: usize, end: usize) {
+        struct Guard<T: Soars, A: IterRawAdapter<T>> {
+            raw: T::Raw,
+            next: usize,
+            end: usize,
+            adapter: PhantomData<A>,
+        }
+
+        impl<T: Soars, A: IterRawAdapter<T>> Drop for Guard<T, A> {
+            fn drop(&mut self) {
+                while self.next < self.end {
+                    let i = self.next;
+                    self.next += 1;
+                    drop(A::item_from_raw(unsafe { self.raw.offset(i) }));
+                }
+            }
+        }
+
+        if needs_drop::<A::Item>() {
+            let mut guard = Guard::<T, A> {
+                raw,
+                next: start,
+                end,
+                adapter: PhantomData,
+            };
+            while guard.next < guard.end {
+                let i = guard.next;
+                guard.next += 1;
+                drop(A::item_from_raw(unsafe { raw.offset(i) }));
+            }
+        }
+    }
+
+    /// Folds the remaining items front to back, advancing the iterator before
+    /// each item is handed to `f`.
+    ///
+    /// Since [`IterRaw`] is [`Copy`], owning iterators have to fold through
+    /// this rather than [`Iterator::fold`]. Otherwise, the items would be
+    /// yielded by the copy and then dropped again by the original. Keeping the
+    /// state current also means that if `f` panics, only the items not yet
+    /// yielded are left for the owner to drop.
+    #[inline]
+    pub(crate) fn fold_in_place<B, F>(&mut self, init: B, mut f: F) -> B
+    where
+        F: FnMut(B, A::Item) -> B,
+    {
+        let mut acc = init;
+        while self.len > 0 {
+            let raw = self.slice.raw();
+            self.len -= 1;
+            self.slice.raw = unsafe { raw.offset(1) };
+            acc = f(acc, A::item_from_raw(raw));
+        }
+        acc
+    }
+
+    /// Folds the remaining items back to front. See
+    /// [`IterRaw::fold_in_place`].
+    #[inline]
+    pub(crate) fn rfold_in_place<B, F>(&mut self, init: B, mut f: F) -> B
+    where
+        F: FnMut(B, A::Item) -> B,
+    {
+        let mut acc = init;
+        while self.len > 0 {
+            self.len -= 1;
+            acc = f(
+                acc,
+                A::item_from_raw(unsafe { self.slice.raw().offset(self.len) }),
+            );
+        }
+        acc
+    }
 }
 
 impl<T, A> Clone for IterRaw<T, A>
@@ -93,13 +169,17 @@ where
 
     #[inline]
     fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        let raw = self.slice.raw();
         if n >= self.len {
+            let len = self.len;
             self.len = 0;
+            unsafe { Self::drop_range(raw, 0, len) };
             None
         } else {
-            let out = A::item_from_raw(self.slice.raw());
             self.len -= n + 1;
-            self.slice.raw = unsafe { self.slice.raw().offset(n + 1) };
+            self.slice.raw = unsafe { raw.offset(n + 1) };
+            let out = A::item_from_raw(unsafe { raw.offset(n) });
+            unsafe { Self::drop_range(raw, 0, n) };
             Some(out)
         }
     }
@@ -117,36 +197,12 @@ where
         }
     }
 
-    fn fold<B, F>(self, init: B, mut f: F) -> B
+    fn fold<B, F>(mut self, init: B, f: F) -> B
     where
         Self: Sized,
         F: FnMut(B, Self::Item) -> B,
     {
-        let Self {
-            slice,
-            len,
-            adapter: _,
-        } = self;
-        if len == 0 {
-            return init;
-        }
-        let mut acc = init;
-        let mut i = 0;
-        loop {
-            acc = f(acc, A::item_from_raw(unsafe { slice.raw().offset(i) }));
-
-            // SAFETY: see std::slice::Iter::fold
-            //
-            // `i` can't overflow since it'll only reach usize::MAX if the
-            // slice had that length, in which case we'll break out of the loop
-            // after the increment
-            i = unsafe { i.unchecked_add(1) };
-
-            if i == len {
-                break;
-            }
-        }
-        acc
+        self.fold_in_place(init, f)
     }
 }
 
@@ -169,16 +225,27 @@ where
 
     #[inline]
     fn nth_back(&mut self, n: usize) -> Option<Self::Item> {
+        let raw = self.slice.raw();
         if n >= self.len {
+            let len = self.len;
             self.len = 0;
+            unsafe { Self::drop_range(raw, 0, len) };
             None
         } else {
             self.len -= n + 1;
-            Some(A::item_from_raw(unsafe {
-                self.slice.as_unsized(self.len).raw().offset(self.len)
-            }))
+            let out = A::item_from_raw(unsafe { raw.offset(self.len) });
+            unsafe { Self::drop_range(raw, self.len + 1, self.len + 1 + n) };
+            Some(out)
         }
     }
+
+    fn rfold<B, F>(mut self, init: B, f: F) -> B
+    where
+        Self: Sized,
+        F: FnMut(B, Self::Item) -> B,
+    {
+        self.rfold_in_place(init, f)
+    }
 }
 
 impl<T, A> FusedIterator for IterRaw<T, A>
@@ -223,19 +290,19 @@ macro_rules! iter_with_raw {
                 self.iter_raw.nth(n)
             }
 
-            fn last(self) -> Option<Self::Item>
+            fn last(mut self) -> Option<Self::Item>
             where
                 Self: Sized,
             {
-                self.iter_raw.last()
+                self.iter_raw.next_back()
             }
 
-            fn fold<B, F>(self, init: B, f: F) -> B
+            fn fold<B, F>(mut self, init: B, f: F) -> B
             where
                 Self: Sized,
                 F: FnMut(B, Self::Item) -> B,
             {
-                self.iter_raw.fold(init, f)
+                self.iter_raw.fold_in_place(init, f)
             }
         }
 
@@ -247,6 +314,18 @@ macro_rules! iter_with_raw {
             fn next_back(&mut self) -> Option<Self::Item> {
                 self.iter_raw.next_back()
             }
+
+            fn nth_back(&mut self, n: usize) -> Option<Self::Item> {
+                self.iter_raw.nth_back(n)
+            }
+
+            fn rfold<B, F>(mut self, init: B, f: F) -> B
+            where
+                Self: Sized,
+                F: FnMut(B, Self::Item) -> B,
+            {
+                self.iter_raw.rfold_in_place(init, f)
+            }
         }
 
         impl<$($lifetime,)? T> FusedIterator for $t where T: $($lifetime +)? Soars {}
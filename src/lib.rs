@@ -124,6 +124,42 @@
 //! assert_eq!(soa.foo(), [11, 13]);
 //! ```
 //!
+//! Postpend `_rev` to iterate over a field in reverse.
+//! ```
+//! # use soa_rs::{soa, Soars};
+//! # #[derive(Soars, Debug, Clone, Copy, PartialEq)]
+//! # #[soa_derive(Debug, PartialEq)]
+//! # struct Example {
+//! #     foo: u8,
+//! #     bar: u16,
+//! # }
+//! # let soa = soa![
+//! #     Example { foo: 1, bar: 2 },
+//! #     Example { foo: 3, bar: 4 },
+//! # ];
+//! assert!(soa.bar_rev().eq(&[4, 2]));
+//! ```
+//!
+//! Postpend `_ptr` or `_ptr_mut` for a pointer to the start of the field array,
+//! such as to pass it over FFI. Writing through the pointer is only allowed
+//! if it came from `_ptr_mut`.
+//! ```
+//! # use soa_rs::{soa, Soars};
+//! # #[derive(Soars, Debug, Clone, Copy, PartialEq)]
+//! # #[soa_derive(Debug, PartialEq)]
+//! # struct Example {
+//! #     foo: u8,
+//! #     bar: u16,
+//! # }
+//! # let mut soa = soa![
+//! #     Example { foo: 1, bar: 2 },
+//! #     Example { foo: 3, bar: 4 },
+//! # ];
+//! let bar = soa.bar_ptr_mut();
+//! unsafe { bar.add(1).write(5) };
+//! assert_eq!(soa.bar(), [2, 5]);
+//! ```
+//!
 //! For tuple structs, prepend the field number with `f`:
 //! ```
 //! # use soa_rs::{soa, Soars};
@@ -134,6 +170,75 @@
 //! assert_eq!(soa.f0(), [5, 10]);
 //! ```
 //!
+//! Getters can be renamed with `#[soa(getter = "...")]`:
+//! ```
+//! # use soa_rs::{soa, Soars};
+//! #[derive(Soars)]
+//! # #[soa_derive(Debug, PartialEq)]
+//! struct Example(u8, #[soa(getter = "velocity")] f32);
+//! let mut soa = soa![Example(5, 0.5), Example(10, 1.0)];
+//! soa.velocity_mut()[0] = 2.0;
+//! assert_eq!(soa.velocity(), [2.0, 1.0]);
+//! ```
+//!
+//! A getter that clashes with a method generated for another field, such as
+//! `data_rev` or `data_ptr` next to a field `data`, is an error and has to be
+//! renamed:
+//! ```compile_fail
+//! # use soa_rs::Soars;
+//! #[derive(Soars)]
+//! struct Example {
+//!     data: u8,
+//!     data_rev: u8,
+//! }
+//! ```
+//!
+//! Tuple structs can also get all of the field slices at once as a tuple:
+//! ```
+//! # use soa_rs::{soa, Soars};
+//! #[derive(Soars)]
+//! # #[soa_derive(Debug, PartialEq)]
+//! struct Example(u8, u16);
+//! let mut soa = soa![Example(1, 2), Example(3, 4)];
+//! let (xs, ys) = soa.as_field_slices_mut();
+//! xs[0] = ys[1] as u8;
+//! assert_eq!(soa.as_field_slices(), ([4, 3].as_slice(), [2, 4].as_slice()));
+//! ```
+//!
+//! To update several fields of each element together, `zip_mut` iterates the
+//! mutable field slices in lockstep. Since it works on plain slice iterators,
+//! it tends to optimize better than going through `iter_mut`. It is not
+//! available for structs with `#[soa_flatten]` fields.
+//! ```
+//! # use soa_rs::{soa, Soars};
+//! #[derive(Soars)]
+//! # #[soa_derive(Debug, PartialEq)]
+//! struct Example {
+//!     pos: f32,
+//!     vel: f32,
+//! }
+//! let mut soa = soa![Example { pos: 1.0, vel: 2.0 }, Example { pos: 3.0, vel: 4.0 }];
+//! for (pos, vel) in soa.zip_mut() {
+//!     *pos += *vel;
+//! }
+//! assert_eq!(soa.pos(), [3.0, 7.0]);
+//! ```
+//!
+//! # Generics
+//!
+//! Generic structs are supported, including trait bounds and where clauses.
+//! The generated types carry the same generic parameters.
+//! ```
+//! # use soa_rs::{soa, Soars};
+//! #[derive(Soars)]
+//! # #[soa_derive(Debug, PartialEq)]
+//! struct Pair<T>(T, T);
+//! let soa = soa![Pair("a", "b"), Pair("c", "d")];
+//! assert_eq!(soa.f1(), ["b", "d"]);
+//! ```
+//!
+//! Generic structs cannot use `#[soa_skip]`.
+//!
 //! # Serde
 //!
 //! [`serde`](https://serde.rs/) support is enabled by the `serde` feature
@@ -145,8 +250,43 @@
 //! struct Test(u32);
 //! ```
 //!
+//! To serialize column by column instead, see `Columnar`.
+//!
+//! # Bytemuck
+//!
+//! The `bytemuck` feature flag adds `foo_bytes` and `foo_bytes_mut` getters
+//! that view the slice for field `foo` as bytes when its type implements
+//! [`bytemuck::Pod`](https://docs.rs/bytemuck/latest/bytemuck/trait.Pod.html).
+//!
+//! ```ignore
+//! #[derive(Soars)]
+//! struct Vertex {
+//!     position: [f32; 3],
+//!     color: u32,
+//! }
+//!
+//! let soa = soa![Vertex { position: [0.0; 3], color: 0 }];
+//! assert_eq!(soa.position_bytes().len(), 12);
+//! ```
+//!
+//! # Rayon
+//!
+//! Parallel iteration with [`rayon`](https://docs.rs/rayon/) is enabled by
+//! the `rayon` feature flag. Slices support `par_iter`, `par_iter_mut`, and
+//! parallel exact chunks.
+//!
+//! # Allocators
+//!
+//! The `nightly` feature flag uses the unstable `allocator_api` to let
+//! [`Soa`] allocate with a custom allocator through `Soa::new_in` and
+//! `Soa::with_capacity_in`. It requires a nightly compiler. Such a [`Soa`]
+//! supports the [`Slice`] API and deallocates with its allocator when dropped,
+//! but growing, shrinking, and the rest of the [`Soa`] methods and trait
+//! implementations are only available with the default `Global` allocator.
+//!
 //! [`Soars`]: soa_rs_derive::Soars
 #![warn(missing_docs)]
+#![cfg_attr(feature = "nightly", feature(allocator_api))]
 
 mod soa;
 pub use soa::Soa;
@@ -163,6 +303,12 @@ pub use iter::Iter;
 mod iter_mut;
 pub use iter_mut::IterMut;
 
+mod extract_if;
+pub use extract_if::ExtractIf;
+
+mod drain_all;
+pub use drain_all::DrainAll;
+
 mod slice;
 pub use slice::Slice;
 
@@ -182,9 +328,23 @@ mod soa_raw;
 #[doc(hidden)]
 pub use soa_raw::SoaRaw;
 
+mod small_soa;
+pub use small_soa::SmallSoa;
+#[doc(hidden)]
+pub use small_soa::SoaInline;
+
+mod chunks;
+pub use chunks::{Chunks, ChunksMut};
+
 mod chunks_exact;
 pub use chunks_exact::ChunksExact;
 
+mod chunk_by;
+pub use chunk_by::{ChunkBy, ChunkByMut};
+
+mod windows;
+pub use windows::Windows;
+
 mod iter_raw;
 
 mod as_slice;
@@ -193,41 +353,198 @@ pub use as_slice::{AsMutSlice, AsSlice};
 mod as_soa_ref;
 pub use as_soa_ref::AsSoaRef;
 
+mod from_soa_ref;
+pub use from_soa_ref::FromSoaRef;
+
+mod soa_columns;
+pub use soa_columns::{ColumnLengthError, SoaColumns};
+
+mod field_stats;
+pub use field_stats::{FieldStats, Numeric};
+
+mod capacity_report;
+pub use capacity_report::{CapacityReport, FieldReport};
+
+mod try_reserve_error;
+pub use try_reserve_error::TryReserveError;
+
+mod columns_debug;
+#[doc(hidden)]
+pub use columns_debug::ColumnsDebug;
+
+mod simd;
+pub use simd::SIMD_ALIGN;
+
+#[cfg(feature = "bytemuck")]
+mod pod;
+#[cfg(feature = "bytemuck")]
+#[doc(hidden)]
+pub use pod::PodColumn;
+#[cfg(feature = "bytemuck")]
+pub use pod::SoaPod;
+
 #[cfg(feature = "serde")]
 mod serde;
+#[cfg(feature = "serde")]
+pub use crate::serde::{Columnar, DeserializeColumnar};
+
+#[cfg(feature = "bytemuck")]
+#[doc(hidden)]
+pub mod bytemuck;
+
+#[cfg(feature = "rayon")]
+mod rayon;
+#[cfg(feature = "rayon")]
+pub use rayon::{ParChunksExact, ParChunksExactMut, ParIter, ParIterMut};
 
 /// Derive macro for the [`Soars`] trait.
 ///
 /// Deriving Soars for some struct `Foo` will create the following additional
 /// structs:
 ///
-/// | Struct         | Field type | Use                                          |
-/// |----------------|------------|----------------------------------------------|
-/// | `FooSoaRaw`    | `*mut T`   | Low-level, unsafe memory handling for SoA    |
-/// | `FooRef`       | `&T`       | SoA element reference                        |
-/// | `FooRefMut`    | `&mut T`   | Mutable SoA element reference                |
-/// | `FooSlices`    | `&[T]`     | SoA fields                                   |
-/// | `FooSlicesMut` | `&mut [T]` | Mutable SoA fields                           |
-/// | `FooArray`     | `[T; N]`   | `const`-compatible SoA                       |
-/// | `FooDeref`     |            | SoA [`Deref`] target, provides slice getters |
+/// | Struct            | Field type              | Use                                             |
+/// |-------------------|-------------------------|-------------------------------------------------|
+/// | `FooSoaRaw`       | `*mut T`                | Low-level, unsafe memory handling for SoA       |
+/// | `FooRef`          | `&T`                    | SoA element reference                           |
+/// | `FooRefMut`       | `&mut T`                | Mutable SoA element reference                   |
+/// | `FooSlices`       | `&[T]`                  | SoA fields                                      |
+/// | `FooSlicesMut`    | `&mut [T]`              | Mutable SoA fields                              |
+/// | `FooSlicesUninit` | `&mut [MaybeUninit<T>]` | Spare capacity, see [`Soa::spare_capacity_mut`] |
+/// | `FooArray`        | `[T; N]`                | `const`-compatible SoA                          |
+/// | `FooColumns`      | `Vec<T>`                | Owned fields, see [`SoaColumns`]                |
+/// | `FooDeref`        |                         | SoA [`Deref`] target, provides slice getters    |
+/// | `FooSoaInline`    | `[MaybeUninit<T>; N]`   | Inline storage for [`SmallSoa`]                 |
 ///
 /// The [`Soars`] trait implementation for `Foo` references these as associated
 /// types. [`AsSoaRef`] is also implemented for `Foo`, `FooRef`, and `FooRefMut`.
+/// If every field implements [`Clone`], [`FromSoaRef`] is implemented for
+/// `Foo`.
 ///
 /// # Arrays
 ///
-/// The `FooArray` type is only generated when the `#[soa_array]` attribute is
-/// added to the struct. Only structs without interior mutability support this
-/// attribute for the time being, due to [this
-/// issue](https://github.com/rust-lang/rust/issues/80384). SOA array types are
+/// The `FooArray` type is only generated when the `#[soa_array]` attribute, or
+/// the equivalent `#[soa(array)]`, is added to the struct. SOA array types are
 /// stack-allocated like normal arrays and are `const`-initializable.
 ///
+/// ```
+/// # use soa_rs::{Soars, AsSlice};
+/// #[derive(Soars)]
+/// #[soa(array)]
+/// # #[soa_derive(Debug, PartialEq)]
+/// struct Foo {
+///     foo: u8,
+///     bar: u16,
+/// }
+///
+/// const ARRAY: FooArray<2> = FooArray::from_array([
+///     Foo { foo: 1, bar: 2 },
+///     Foo { foo: 3, bar: 4 },
+/// ]);
+/// assert_eq!(ARRAY.as_slice().bar(), [2, 4]);
+/// ```
+///
+/// Only structs without interior mutability support this attribute for the
+/// time being, due to [this
+/// issue](https://github.com/rust-lang/rust/issues/80384). Since a `const` item
+/// is copied wherever it is used, changes made through interior mutability
+/// would not persist. Fields of the standard library's interior mutability
+/// types are an error: `Cell`, `RefCell`, `UnsafeCell`, `OnceCell`, `Mutex`,
+/// `RwLock`, the atomic types, and similar, along with arrays, tuples, and
+/// `Option`s of them. This is detected from the field types as written, so type
+/// aliases, generic parameters, and other structs that contain one of these
+/// types are not caught.
+///
+/// ```compile_fail
+/// # use soa_rs::Soars;
+/// # use std::cell::Cell;
+/// #[derive(Soars)]
+/// #[soa(array)]
+/// struct Foo {
+///     foo: u8,
+///     bar: Cell<u16>,
+/// }
+/// ```
+///
+/// # Field statistics
+///
+/// Adding the `#[soa_stats]` attribute to a struct whose fields are all
+/// [`Numeric`] generates a `FooFieldStats` struct with a [`FieldStats`] for
+/// each field, along with a `debug_field_stats` method on the [`Slice`] to
+/// compute them.
+///
+/// ```
+/// # use soa_rs::{Soars, soa};
+/// #[derive(Soars)]
+/// #[soa_stats]
+/// # #[soa_derive(Debug, PartialEq)]
+/// struct Foo {
+///     foo: u8,
+///     bar: f32,
+/// }
+///
+/// let soa = soa![Foo { foo: 1, bar: 2.0 }, Foo { foo: 3, bar: f32::NAN }];
+/// let stats = soa.debug_field_stats();
+/// assert_eq!(stats.foo.max, Some(3));
+/// assert!(stats.bar.mean.unwrap().is_nan());
+/// ```
+///
+/// # Field bytes
+///
+/// With the `bytemuck` feature, if every field is
+/// [`bytemuck::Pod`](https://docs.rs/bytemuck/latest/bytemuck/trait.Pod.html),
+/// a `for_each_field_bytes` method is provided on the [`Slice`]. It calls the
+/// given function with the name and raw bytes of each field in declaration
+/// order, which is useful for writing columnar formats. Tuple struct fields are
+/// named by their index.
+///
+/// ```ignore
+/// #[derive(Soars)]
+/// struct Foo {
+///     foo: u8,
+///     bar: u32,
+/// }
+///
+/// let soa = soa![Foo { foo: 1, bar: 2 }, Foo { foo: 3, bar: 4 }];
+/// let mut columns = vec![];
+/// soa.for_each_field_bytes(|name, bytes| columns.push((name, bytes.len())));
+/// assert_eq!(columns, [("foo", 2), ("bar", 8)]);
+/// ```
+///
+/// # Columnar debugging
+///
+/// The [`Debug`](std::fmt::Debug) implementation of [`Slice`] prints a list of
+/// elements. To see the field arrays instead, `columns_debug` returns a value
+/// that prints each field name with its slice. Flattened fields print the
+/// `Slices` type of the nested struct, so it needs to derive `Debug`.
+///
+/// ```
+/// # use soa_rs::{Soars, soa};
+/// # #[derive(Soars)]
+/// # #[soa_derive(Debug, PartialEq)]
+/// struct Foo {
+///     foo: u8,
+///     bar: u32,
+/// }
+///
+/// let soa = soa![Foo { foo: 1, bar: 2 }, Foo { foo: 3, bar: 4 }];
+/// let debug = format!("{:?}", soa.columns_debug());
+/// assert_eq!(debug, "Foo { foo: [1, 3], bar: [2, 4] }");
+/// ```
+///
+/// With the `bytemuck` feature, the `#[soa_pod]` attribute, which requires
+/// every field to be `bytemuck::Pod`, allows the columns to be collected with
+/// `Slice::as_column_bytes` and turned back into a [`Soa`] with
+/// `Soa::from_column_bytes`. `#[soa_pod]` cannot be combined with `#[soa_skip]`
+/// or `#[soa_flatten]`.
+///
 /// # Derive for generated types
 ///
 /// The `soa_derive` attribute can be used to derive traits for the generated
 /// types. `Copy` and `Clone` are added automatically for `FooRef` and
-/// `FooSlices`. In the following example, we have the following trait
-/// implementations:
+/// `FooSlices`. Deriving [`Hash`](std::hash::Hash) for `FooRef` only requires
+/// the fields to implement it, not the type parameters, and produces the same
+/// hash as a derived implementation on `Foo`. `FooColumns` only gets derives that name it with `include(Columns)`.
+/// In the following example, we have the following trait implementations:
 ///
 /// | Struct         | `Copy`/`Clone` | `Debug`/`PartialEq` | `Eq` | `PartialOrd` |
 /// |----------------|----------------|---------------------|------|--------------|
@@ -246,6 +563,23 @@ mod serde;
 /// struct Foo(u8);
 /// ```
 ///
+/// Helper attributes needed by those derives can be forwarded with
+/// `soa_attr`, which names one generated type followed by the attributes to
+/// add to it. On the struct, the attributes are added to the generated
+/// struct. On a field, they are added to the corresponding field of the
+/// generated struct.
+///
+/// ```ignore
+/// #[derive(Soars)]
+/// #[soa_derive(include(Ref), serde::Serialize)]
+/// #[soa_attr(Ref, serde(rename_all = "camelCase"))]
+/// struct Foo {
+///     foo_bar: u8,
+///     #[soa_attr(Ref, serde(rename = "b"))]
+///     baz: u16,
+/// }
+/// ```
+///
 /// # Alignment
 ///
 /// Individual fields can be tagged with the `align` attribute to raise their
@@ -260,6 +594,145 @@ mod serde;
 /// struct Foo(#[align(8)] u8);
 /// ```
 ///
+/// Use `#[align(simd)]` to align the field to the SIMD register width of the
+/// target instead. See [`SIMD_ALIGN`] for the values used and the fallback when
+/// no SIMD target feature is enabled.
+///
+/// ```
+/// # use soa_rs::{Soars, Soa, SIMD_ALIGN};
+/// #[derive(Soars)]
+/// # #[soa_derive(Debug, PartialEq)]
+/// struct Foo(u8, #[align(simd)] f32);
+///
+/// let soa = Soa::<Foo>::with_capacity(3);
+/// assert_eq!(soa.f1().as_ptr() as usize % SIMD_ALIGN, 0);
+/// ```
+///
+/// To give every field the same alignment, put `#[soa(align = ...)]` on the
+/// struct instead. A field's own `align` attribute takes precedence.
+///
+/// ```
+/// # use soa_rs::{Soars, Soa};
+/// #[derive(Soars)]
+/// # #[soa_derive(Debug, PartialEq)]
+/// #[soa(align = 64)]
+/// struct Foo(f32, f32, #[align(8)] u8);
+///
+/// let soa = Soa::<Foo>::with_capacity(3);
+/// assert_eq!(soa.f0().as_ptr() as usize % 64, 0);
+/// assert_eq!(soa.f1().as_ptr() as usize % 64, 0);
+/// ```
+///
+/// To hand the field arrays to an external API, `FooSoaRaw::field_alignments`
+/// returns the alignment of each array, including any raised with `align`,
+/// and `field_ptrs` returns the start of each array for a given slice. Both
+/// list the arrays in allocation order and leave out `PhantomData` fields.
+///
+/// ```
+/// # use soa_rs::{Soars, soa};
+/// #[derive(Soars)]
+/// # #[soa_derive(Debug, PartialEq)]
+/// struct Foo(u8, #[align(16)] u32);
+///
+/// let soa = soa![Foo(1, 2), Foo(3, 4)];
+/// assert_eq!(FooSoaRaw::field_alignments(), [1, 16]);
+/// let [f0, f1] = soa.field_ptrs();
+/// assert_eq!(f0.as_ptr().cast_const(), soa.f0().as_ptr());
+/// assert_eq!(f1.as_ptr() as *const u32, soa.f1().as_ptr());
+/// ```
+///
+/// # Skipped fields
+///
+/// Fields tagged with `#[soa_skip]` do not get their own array. Instead, all
+/// skipped fields are stored together in a single array of a generated
+/// struct, much like a [`Vec`] of them. This reduces the number of arrays to
+/// allocate and manage for fields that are rarely accessed. Skipped fields
+/// are still available through `FooRef` and `FooRefMut`, but they do not have
+/// slice getters and are not included in `FooSlices` or `FooSlicesMut`. The
+/// `align` attribute has no effect on skipped fields. `#[soa_skip]` is only
+/// supported for structs with named fields and cannot be combined with
+/// `#[soa_array]`.
+///
+/// ```
+/// # use soa_rs::{Soars, soa};
+/// # #[derive(Soars, Debug, PartialEq)]
+/// # #[soa_derive(Debug, PartialEq)]
+/// struct Foo {
+///     foo: u8,
+///     #[soa_skip]
+///     name: String,
+///     #[soa_skip]
+///     id: u64,
+/// }
+///
+/// let mut soa = soa![Foo { foo: 1, name: "a".into(), id: 10 }];
+/// assert_eq!(soa.foo(), [1]);
+/// assert_eq!(soa.idx(0).name, "a");
+/// *soa.idx_mut(0).id = 20;
+/// assert_eq!(soa.pop(), Some(Foo { foo: 1, name: "a".into(), id: 20 }));
+/// ```
+///
+/// # Flattened fields
+///
+/// A field whose type also implements [`Soars`] can be tagged with
+/// `#[soa_flatten]`. Rather than storing the nested struct in a single array,
+/// each of its fields gets its own array. The getter for a flattened field
+/// returns the `Slices` type of the nested struct, and `FooRef` and
+/// `FooRefMut` hold its `Ref` and `RefMut` types. Flattened fields cannot be
+/// combined with `#[soa_skip]`, `#[soa_array]`, or `#[soa_stats]`.
+///
+/// ```
+/// # use soa_rs::{Soars, soa};
+/// #[derive(Soars, Debug, PartialEq)]
+/// #[soa_derive(Debug, PartialEq)]
+/// struct Vector3 {
+///     x: f32,
+///     y: f32,
+///     z: f32,
+/// }
+///
+/// #[derive(Soars, Debug, PartialEq)]
+/// #[soa_derive(Debug, PartialEq)]
+/// struct Body {
+///     #[soa_flatten]
+///     pos: Vector3,
+///     mass: f32,
+/// }
+///
+/// let mut soa = soa![
+///     Body { pos: Vector3 { x: 1.0, y: 2.0, z: 3.0 }, mass: 4.0 },
+///     Body { pos: Vector3 { x: 5.0, y: 6.0, z: 7.0 }, mass: 8.0 },
+/// ];
+/// assert_eq!(soa.pos().y, [2.0, 6.0]);
+/// assert_eq!(soa.mass(), [4.0, 8.0]);
+/// *soa.idx_mut(1).pos.z = 0.0;
+/// assert_eq!(*soa.idx(1).pos.z, 0.0);
+/// ```
+///
+/// # PhantomData fields
+///
+/// Fields whose type is spelled `PhantomData` are zero-sized, so they are left
+/// out of the allocation and do not add to the offset or alignment
+/// calculations. In `FooRef`, `FooRefMut`, and `FooColumns`, they are
+/// [`PhantomData`] as well. The `align` attribute opts a field out of this.
+///
+/// ```
+/// # use soa_rs::{Soars, soa};
+/// # use std::marker::PhantomData;
+/// #[derive(Soars, Debug, PartialEq)]
+/// #[soa_derive(Debug, PartialEq)]
+/// struct Id<T> {
+///     value: u32,
+///     marker: PhantomData<T>,
+/// }
+///
+/// let soa = soa![Id::<String> { value: 1, marker: PhantomData }];
+/// assert_eq!(soa.value(), [1]);
+/// assert_eq!(soa.idx(0).marker, PhantomData);
+/// assert_eq!(soa.capacity_report().fields.len(), 1);
+/// ```
+///
+/// [`PhantomData`]: std::marker::PhantomData
 /// [`Deref`]: std::ops::Deref
 pub use soa_rs_derive::Soars;
 
@@ -328,6 +801,63 @@ macro_rules! soa {
     };
 }
 
+/// Creates a [`Soa`] from an iterable for each field.
+///
+/// The columns are zipped together, constructing one element from each row.
+/// Unlike [`SoaColumns::from_columns`], the columns can be any [`IntoIterator`]
+/// rather than [`Vec`]s. Only structs with named fields are supported.
+///
+/// # Panics
+///
+/// Panics if the columns do not all have the same length.
+///
+/// # Examples
+///
+/// ```
+/// # use soa_rs::{Soars, soa, soa_columns};
+/// # #[derive(Soars, Debug, PartialEq)]
+/// # #[soa_derive(Debug, PartialEq)]
+/// struct Foo {
+///     foo: u8,
+///     bar: char,
+/// }
+///
+/// let soa = soa_columns!(Foo {
+///     foo: [1, 2, 3],
+///     bar: "abc".chars(),
+/// });
+/// assert_eq!(
+///     soa,
+///     soa![
+///         Foo { foo: 1, bar: 'a' },
+///         Foo { foo: 2, bar: 'b' },
+///         Foo { foo: 3, bar: 'c' },
+///     ]
+/// );
+/// ```
+#[macro_export]
+macro_rules! soa_columns {
+    ($($ty:ident)::+ { $($field:ident: $column:expr),+ $(,)? }) => {
+        {
+            // Evaluate every column before binding any of the field names
+            let ($(mut $field,)+) = ($(::std::iter::IntoIterator::into_iter($column),)+);
+            let capacity = [$($field.size_hint().0),+].into_iter().min().unwrap_or(0);
+            let mut out = $crate::Soa::<$($ty)::+>::with_capacity(capacity);
+            loop {
+                match ($($field.next(),)+) {
+                    ($(Some($field),)+) => out.push($($ty)::+ { $($field),+ }),
+                    ($($field,)+) => {
+                        if $($field.is_none())&&+ {
+                            break out;
+                        }
+                        panic!("columns have different lengths");
+                    }
+                }
+            }
+        }
+    };
+}
+
 #[doc = include_str!("../README.md")]
 mod readme_tests {}
 
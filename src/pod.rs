@@ -0,0 +1,43 @@
+use crate::Soars;
+use bytemuck::Pod;
+use std::{mem::size_of, ptr::NonNull};
+
+/// A [`Soars`] type whose fields are all [`Pod`].
+///
+/// This is implemented by the derive macro for types with the `#[soa_pod]`
+/// attribute and enables [`Slice::as_column_bytes`] and
+/// [`Soa::from_column_bytes`].
+///
+/// # Safety
+///
+/// [`SoaPod::pod_columns`] must return one column per field, in declaration
+/// order, pointing into the arrays of the given raw.
+///
+/// [`Slice::as_column_bytes`]: crate::Slice::as_column_bytes
+/// [`Soa::from_column_bytes`]: crate::Soa::from_column_bytes
+pub unsafe trait SoaPod: Soars {
+    #[doc(hidden)]
+    fn pod_columns(raw: Self::Raw) -> Vec<PodColumn>;
+}
+
+/// The array of a field of a [`SoaPod`] type.
+#[doc(hidden)]
+#[derive(Debug, Clone, Copy)]
+pub struct PodColumn {
+    pub name: &'static str,
+    pub ptr: *mut u8,
+    pub size: usize,
+}
+
+impl PodColumn {
+    pub fn new<T>(name: &'static str, ptr: NonNull<T>) -> Self
+    where
+        T: Pod,
+    {
+        Self {
+            name,
+            ptr: ptr.as_ptr().cast(),
+            size: size_of::<T>(),
+        }
+    }
+}
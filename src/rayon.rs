@@ -0,0 +1,615 @@
+use crate::{Slice, SliceMut, SliceRef, SoaRaw, Soars};
+use rayon::iter::{
+    plumbing::{bridge, Consumer, Producer, ProducerCallback, UnindexedConsumer},
+    IndexedParallelIterator, IntoParallelIterator, ParallelIterator,
+};
+use std::marker::PhantomData;
+
+impl<T> Slice<T>
+where
+    T: Soars,
+{
+    /// Returns a parallel iterator over `chunk_size` elements of the slice at
+    /// a time, starting at the beginning of the slice.
+    ///
+    /// This is the parallel equivalent of [`Slice::chunks_exact`]. If
+    /// `chunk_size` does not divide the length of the slice, then the last up
+    /// to `chunk_size-1` elements will be omitted and can be retrieved from
+    /// [`ParChunksExact::remainder`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `chunk_size` is 0.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use soa_rs::{Soa, Soars, soa};
+    /// # use rayon::prelude::*;
+    /// # #[derive(Soars, Debug, PartialEq)]
+    /// # #[soa_derive(Debug, PartialEq)]
+    /// # struct Foo(u32);
+    /// let soa = soa![Foo(1), Foo(2), Foo(3), Foo(4), Foo(5)];
+    /// let sums: Vec<u32> = soa
+    ///     .par_chunks_exact(2)
+    ///     .map(|chunk| chunk.f0().iter().sum())
+    ///     .collect();
+    /// assert_eq!(sums, [3, 7]);
+    /// ```
+    pub fn par_chunks_exact(&self, chunk_size: usize) -> ParChunksExact<'_, T> {
+        if chunk_size == 0 {
+            panic!("chunk size must be nonzero")
+        }
+
+        let rem_len = self.len() % chunk_size;
+        let fst_len = self.len() - rem_len;
+        ParChunksExact {
+            chunks: ChunksProducer::new(unsafe { self.as_sized() }, fst_len, chunk_size),
+            remainder: self.idx(fst_len..),
+        }
+    }
+
+    /// Returns a parallel iterator over `chunk_size` elements of the slice at
+    /// a time, starting at the beginning of the slice. The chunks are mutable
+    /// and do not overlap.
+    ///
+    /// This is the mutable counterpart to [`Slice::par_chunks_exact`]. If
+    /// `chunk_size` does not divide the length of the slice, then the last up
+    /// to `chunk_size-1` elements will be omitted.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `chunk_size` is 0.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use soa_rs::{Soa, Soars, soa};
+    /// # use rayon::prelude::*;
+    /// # #[derive(Soars, Debug, PartialEq)]
+    /// # #[soa_derive(Debug, PartialEq)]
+    /// # struct Foo(u32);
+    /// let mut soa = soa![Foo(1), Foo(2), Foo(3), Foo(4), Foo(5)];
+    /// soa.par_chunks_exact_mut(2).for_each(|mut chunk| {
+    ///     for foo in chunk.f0_mut() {
+    ///         *foo *= 10;
+    ///     }
+    /// });
+    /// assert_eq!(soa, soa![Foo(10), Foo(20), Foo(30), Foo(40), Foo(5)]);
+    /// ```
+    pub fn par_chunks_exact_mut(&mut self, chunk_size: usize) -> ParChunksExactMut<'_, T> {
+        if chunk_size == 0 {
+            panic!("chunk size must be nonzero")
+        }
+
+        let fst_len = self.len() - self.len() % chunk_size;
+        ParChunksExactMut {
+            chunks: ChunksProducer::new(unsafe { self.as_sized() }, fst_len, chunk_size),
+        }
+    }
+}
+
+/// Parallel iterator over immutable, non-overlapping chunks of a [`Slice`].
+///
+/// This struct is created by the [`par_chunks_exact`] method.
+///
+/// [`par_chunks_exact`]: Slice::par_chunks_exact
+pub struct ParChunksExact<'a, T>
+where
+    T: 'a + Soars,
+{
+    chunks: ChunksProducer<T, SliceRef<'a, T>>,
+    remainder: SliceRef<'a, T>,
+}
+
+impl<'a, T> ParChunksExact<'a, T>
+where
+    T: Soars,
+{
+    /// Returns the remainder of the original slice that is not going to be
+    /// returned by the iterator.
+    pub fn remainder(&self) -> &Slice<T> {
+        self.remainder.as_ref()
+    }
+}
+
+impl<'a, T> ParallelIterator for ParChunksExact<'a, T>
+where
+    T: Soars + Send + Sync,
+{
+    type Item = SliceRef<'a, T>;
+
+    fn drive_unindexed<C>(self, consumer: C) -> C::Result
+    where
+        C: UnindexedConsumer<Self::Item>,
+    {
+        bridge(self, consumer)
+    }
+
+    fn opt_len(&self) -> Option<usize> {
+        Some(self.chunks.len())
+    }
+}
+
+impl<'a, T> IndexedParallelIterator for ParChunksExact<'a, T>
+where
+    T: Soars + Send + Sync,
+{
+    fn len(&self) -> usize {
+        self.chunks.len()
+    }
+
+    fn drive<C: Consumer<Self::Item>>(self, consumer: C) -> C::Result {
+        bridge(self, consumer)
+    }
+
+    fn with_producer<CB: ProducerCallback<Self::Item>>(self, callback: CB) -> CB::Output {
+        callback.callback(self.chunks)
+    }
+}
+
+/// Parallel iterator over mutable, non-overlapping chunks of a [`Slice`].
+///
+/// This struct is created by the [`par_chunks_exact_mut`] method.
+///
+/// [`par_chunks_exact_mut`]: Slice::par_chunks_exact_mut
+pub struct ParChunksExactMut<'a, T>
+where
+    T: 'a + Soars,
+{
+    chunks: ChunksProducer<T, SliceMut<'a, T>>,
+}
+
+impl<'a, T> ParallelIterator for ParChunksExactMut<'a, T>
+where
+    T: Soars + Send,
+{
+    type Item = SliceMut<'a, T>;
+
+    fn drive_unindexed<C>(self, consumer: C) -> C::Result
+    where
+        C: UnindexedConsumer<Self::Item>,
+    {
+        bridge(self, consumer)
+    }
+
+    fn opt_len(&self) -> Option<usize> {
+        Some(self.chunks.len())
+    }
+}
+
+impl<'a, T> IndexedParallelIterator for ParChunksExactMut<'a, T>
+where
+    T: Soars + Send,
+{
+    fn len(&self) -> usize {
+        self.chunks.len()
+    }
+
+    fn drive<C: Consumer<Self::Item>>(self, consumer: C) -> C::Result {
+        bridge(self, consumer)
+    }
+
+    fn with_producer<CB: ProducerCallback<Self::Item>>(self, callback: CB) -> CB::Output {
+        callback.callback(self.chunks)
+    }
+}
+
+impl<'a, T> IntoParallelIterator for &'a Slice<T>
+where
+    T: Soars + Send + Sync,
+    T::Ref<'a>: Send,
+{
+    type Iter = ParIter<'a, T>;
+    type Item = T::Ref<'a>;
+
+    fn into_par_iter(self) -> Self::Iter {
+        ParIter {
+            elems: ElemsProducer::new(unsafe { self.as_sized() }, self.len()),
+        }
+    }
+}
+
+impl<'a, T> IntoParallelIterator for &'a mut Slice<T>
+where
+    T: Soars + Send,
+    T::RefMut<'a>: Send,
+{
+    type Iter = ParIterMut<'a, T>;
+    type Item = T::RefMut<'a>;
+
+    fn into_par_iter(self) -> Self::Iter {
+        ParIterMut {
+            elems: ElemsProducer::new(unsafe { self.as_sized() }, self.len()),
+        }
+    }
+}
+
+/// Parallel iterator over the elements of a [`Slice`].
+///
+/// This struct is created by the [`par_iter`] method.
+///
+/// # Examples
+///
+/// ```
+/// # use soa_rs::{Soa, Soars, soa};
+/// # use rayon::prelude::*;
+/// # #[derive(Soars, Debug, PartialEq)]
+/// # #[soa_derive(Debug, PartialEq)]
+/// # struct Foo(u32);
+/// let soa = soa![Foo(1), Foo(2), Foo(3), Foo(4)];
+/// let sum: u32 = soa.par_iter().map(|foo| *foo.0).sum();
+/// assert_eq!(sum, 10);
+/// ```
+///
+/// [`par_iter`]: rayon::iter::IntoParallelRefIterator::par_iter
+pub struct ParIter<'a, T>
+where
+    T: 'a + Soars,
+{
+    elems: ElemsProducer<T, &'a T>,
+}
+
+impl<'a, T> ParallelIterator for ParIter<'a, T>
+where
+    T: Soars + Send + Sync,
+    T::Ref<'a>: Send,
+{
+    type Item = T::Ref<'a>;
+
+    fn drive_unindexed<C>(self, consumer: C) -> C::Result
+    where
+        C: UnindexedConsumer<Self::Item>,
+    {
+        bridge(self, consumer)
+    }
+
+    fn opt_len(&self) -> Option<usize> {
+        Some(self.elems.len)
+    }
+}
+
+impl<'a, T> IndexedParallelIterator for ParIter<'a, T>
+where
+    T: Soars + Send + Sync,
+    T::Ref<'a>: Send,
+{
+    fn len(&self) -> usize {
+        self.elems.len
+    }
+
+    fn drive<C: Consumer<Self::Item>>(self, consumer: C) -> C::Result {
+        bridge(self, consumer)
+    }
+
+    fn with_producer<CB: ProducerCallback<Self::Item>>(self, callback: CB) -> CB::Output {
+        callback.callback(self.elems)
+    }
+}
+
+/// Parallel iterator over mutable references to the elements of a [`Slice`].
+///
+/// This struct is created by the [`par_iter_mut`] method.
+///
+/// # Examples
+///
+/// ```
+/// # use soa_rs::{Soa, Soars, soa};
+/// # use rayon::prelude::*;
+/// # #[derive(Soars, Debug, PartialEq)]
+/// # #[soa_derive(Debug, PartialEq)]
+/// # struct Foo(u32);
+/// let mut soa = soa![Foo(1), Foo(2), Foo(3)];
+/// soa.par_iter_mut().for_each(|foo| *foo.0 *= 2);
+/// assert_eq!(soa, soa![Foo(2), Foo(4), Foo(6)]);
+/// ```
+///
+/// [`par_iter_mut`]: rayon::iter::IntoParallelRefMutIterator::par_iter_mut
+pub struct ParIterMut<'a, T>
+where
+    T: 'a + Soars,
+{
+    elems: ElemsProducer<T, &'a mut T>,
+}
+
+impl<'a, T> ParallelIterator for ParIterMut<'a, T>
+where
+    T: Soars + Send,
+    T::RefMut<'a>: Send,
+{
+    type Item = T::RefMut<'a>;
+
+    fn drive_unindexed<C>(self, consumer: C) -> C::Result
+    where
+        C: UnindexedConsumer<Self::Item>,
+    {
+        bridge(self, consumer)
+    }
+
+    fn opt_len(&self) -> Option<usize> {
+        Some(self.elems.len)
+    }
+}
+
+impl<'a, T> IndexedParallelIterator for ParIterMut<'a, T>
+where
+    T: Soars + Send,
+    T::RefMut<'a>: Send,
+{
+    fn len(&self) -> usize {
+        self.elems.len
+    }
+
+    fn drive<C: Consumer<Self::Item>>(self, consumer: C) -> C::Result {
+        bridge(self, consumer)
+    }
+
+    fn with_producer<CB: ProducerCallback<Self::Item>>(self, callback: CB) -> CB::Output {
+        callback.callback(self.elems)
+    }
+}
+
+/// Constructs an element reference from its position.
+///
+/// This is implemented for `&T` and `&mut T`, which stand in for
+/// [`Soars::Ref`] and [`Soars::RefMut`] respectively.
+pub trait ElemAdapter<T>
+where
+    T: Soars,
+{
+    type Item;
+
+    fn elem_from_raw(raw: T::Raw) -> Self::Item;
+}
+
+impl<'a, T> ElemAdapter<T> for &'a T
+where
+    T: Soars,
+{
+    type Item = T::Ref<'a>;
+
+    fn elem_from_raw(raw: T::Raw) -> Self::Item {
+        unsafe { raw.get_ref() }
+    }
+}
+
+impl<'a, T> ElemAdapter<T> for &'a mut T
+where
+    T: Soars,
+{
+    type Item = T::RefMut<'a>;
+
+    fn elem_from_raw(raw: T::Raw) -> Self::Item {
+        unsafe { raw.get_mut() }
+    }
+}
+
+/// Splits a run of elements for rayon and iterates over them sequentially
+/// once the pieces are small enough.
+///
+/// Splitting only ever divides the range of elements between the two halves,
+/// so the elements handed out to different threads never overlap.
+pub struct ElemsProducer<T, A>
+where
+    T: Soars,
+{
+    slice: Slice<T, ()>,
+    len: usize,
+    adapter: PhantomData<A>,
+}
+
+impl<T, A> ElemsProducer<T, A>
+where
+    T: Soars,
+{
+    fn new(slice: Slice<T, ()>, len: usize) -> Self {
+        Self {
+            slice,
+            len,
+            adapter: PhantomData,
+        }
+    }
+}
+
+impl<T, A> Producer for ElemsProducer<T, A>
+where
+    T: Soars,
+    A: ElemAdapter<T>,
+    Self: Send,
+{
+    type Item = A::Item;
+    type IntoIter = Self;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self
+    }
+
+    fn split_at(self, index: usize) -> (Self, Self) {
+        let left = Self::new(self.slice, index);
+        let right = Self::new(
+            Slice::with_raw(unsafe { self.slice.raw().offset(index) }),
+            self.len - index,
+        );
+        (left, right)
+    }
+}
+
+impl<T, A> Iterator for ElemsProducer<T, A>
+where
+    T: Soars,
+    A: ElemAdapter<T>,
+{
+    type Item = A::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            None
+        } else {
+            let out = A::elem_from_raw(self.slice.raw());
+            self.len -= 1;
+            self.slice.raw = unsafe { self.slice.raw().offset(1) };
+            Some(out)
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<T, A> DoubleEndedIterator for ElemsProducer<T, A>
+where
+    T: Soars,
+    A: ElemAdapter<T>,
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            None
+        } else {
+            self.len -= 1;
+            Some(A::elem_from_raw(unsafe {
+                self.slice.raw().offset(self.len)
+            }))
+        }
+    }
+}
+
+impl<T, A> ExactSizeIterator for ElemsProducer<T, A>
+where
+    T: Soars,
+    A: ElemAdapter<T>,
+{
+}
+
+/// Constructs a chunk from its starting position and length.
+pub trait ChunkAdapter<T>
+where
+    T: Soars,
+{
+    fn chunk_from_raw(slice: Slice<T, ()>, len: usize) -> Self;
+}
+
+impl<'a, T> ChunkAdapter<T> for SliceRef<'a, T>
+where
+    T: Soars,
+{
+    fn chunk_from_raw(slice: Slice<T, ()>, len: usize) -> Self {
+        unsafe { SliceRef::from_slice(slice, len) }
+    }
+}
+
+impl<'a, T> ChunkAdapter<T> for SliceMut<'a, T>
+where
+    T: Soars,
+{
+    fn chunk_from_raw(slice: Slice<T, ()>, len: usize) -> Self {
+        unsafe { SliceMut::from_slice(slice, len) }
+    }
+}
+
+/// Splits a run of exact chunks for rayon and iterates over them sequentially
+/// once the pieces are small enough.
+///
+/// Splitting only ever divides the range of chunks between the two halves, so
+/// the chunks handed out to different threads never overlap.
+pub struct ChunksProducer<T, A>
+where
+    T: Soars,
+{
+    slice: Slice<T, ()>,
+    chunks: usize,
+    chunk_size: usize,
+    adapter: PhantomData<A>,
+}
+
+impl<T, A> ChunksProducer<T, A>
+where
+    T: Soars,
+{
+    fn new(slice: Slice<T, ()>, len: usize, chunk_size: usize) -> Self {
+        debug_assert_eq!(len % chunk_size, 0);
+        Self {
+            slice,
+            chunks: len / chunk_size,
+            chunk_size,
+            adapter: PhantomData,
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.chunks
+    }
+}
+
+impl<T, A> Producer for ChunksProducer<T, A>
+where
+    T: Soars,
+    A: ChunkAdapter<T> + Send,
+    Self: Send,
+{
+    type Item = A;
+    type IntoIter = Self;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self
+    }
+
+    fn split_at(self, index: usize) -> (Self, Self) {
+        let left = Self {
+            chunks: index,
+            ..self
+        };
+        let right = Self {
+            slice: Slice::with_raw(unsafe { self.slice.raw().offset(index * self.chunk_size) }),
+            chunks: self.chunks - index,
+            ..self
+        };
+        (left, right)
+    }
+}
+
+impl<T, A> Iterator for ChunksProducer<T, A>
+where
+    T: Soars,
+    A: ChunkAdapter<T>,
+{
+    type Item = A;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.chunks == 0 {
+            None
+        } else {
+            let out = A::chunk_from_raw(self.slice, self.chunk_size);
+            self.chunks -= 1;
+            self.slice.raw = unsafe { self.slice.raw().offset(self.chunk_size) };
+            Some(out)
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.chunks, Some(self.chunks))
+    }
+}
+
+impl<T, A> DoubleEndedIterator for ChunksProducer<T, A>
+where
+    T: Soars,
+    A: ChunkAdapter<T>,
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.chunks == 0 {
+            None
+        } else {
+            self.chunks -= 1;
+            let raw = unsafe { self.slice.raw().offset(self.chunks * self.chunk_size) };
+            Some(A::chunk_from_raw(Slice::with_raw(raw), self.chunk_size))
+        }
+    }
+}
+
+impl<T, A> ExactSizeIterator for ChunksProducer<T, A>
+where
+    T: Soars,
+    A: ChunkAdapter<T>,
+{
+}
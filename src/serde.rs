@@ -1,11 +1,12 @@
-use crate::{Soa, Soars};
+use crate::{Slice, Soa, SoaColumns, Soars};
 use serde::{
-    de::{Deserialize, Deserializer, SeqAccess, Visitor},
+    de::{Deserialize, Deserializer, Error, SeqAccess, Visitor},
     ser::{Serialize, SerializeSeq, Serializer},
 };
 use std::{
-    fmt::{self, Formatter},
+    fmt::{self, Debug, Formatter},
     marker::PhantomData,
+    mem::size_of,
 };
 
 impl<T> Serialize for Soa<T>
@@ -53,10 +54,117 @@ where
     where
         A: SeqAccess<'de>,
     {
-        let mut out = Soa::<T>::new();
+        // Like Vec, don't trust the size hint for more than about a megabyte
+        const MAX_PREALLOC_BYTES: usize = 1024 * 1024;
+        let cap = seq
+            .size_hint()
+            .unwrap_or(0)
+            .min(MAX_PREALLOC_BYTES / size_of::<T>().max(1));
+        let mut out = Soa::<T>::with_capacity(cap);
         while let Some(next) = seq.next_element()? {
             out.push(next);
         }
         Ok(out)
     }
 }
+
+/// Serializes a [`Slice`] column by column rather than element by element.
+///
+/// The output is a map from each field name to the sequence of that field's
+/// values, in declaration order. This uses the [`Serialize`] implementation
+/// of `FooSlices`, which can be added with
+/// `#[soa_derive(include(Slices), serde::Serialize)]`. Use
+/// [`DeserializeColumnar`] to read it back. Fields tagged with `#[soa_skip]`
+/// are not included in `FooSlices`, so they are not serialized.
+///
+/// # Examples
+///
+/// ```ignore
+/// # use soa_rs::{Columnar, DeserializeColumnar, Soars, soa};
+/// #[derive(Soars, Debug, PartialEq)]
+/// #[soa_derive(Debug, PartialEq)]
+/// #[soa_derive(include(Slices), serde::Serialize)]
+/// #[soa_derive(include(Columns), serde::Deserialize)]
+/// struct Foo {
+///     foo: u8,
+///     bar: u16,
+/// }
+///
+/// let soa = soa![Foo { foo: 1, bar: 2 }, Foo { foo: 3, bar: 4 }];
+/// let json = serde_json::to_string(&Columnar(&soa)).unwrap();
+/// assert_eq!(json, r#"{"foo":[1,3],"bar":[2,4]}"#);
+///
+/// let DeserializeColumnar(deserial) = serde_json::from_str(&json).unwrap();
+/// assert_eq!(deserial, soa);
+/// ```
+pub struct Columnar<'a, T>(pub &'a Slice<T>)
+where
+    T: Soars;
+
+impl<T> Clone for Columnar<'_, T>
+where
+    T: Soars,
+{
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for Columnar<'_, T> where T: Soars {}
+
+impl<T> Debug for Columnar<'_, T>
+where
+    T: Soars,
+    for<'a> T::Ref<'a>: Debug,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Columnar").field(&self.0).finish()
+    }
+}
+
+impl<T> Serialize for Columnar<'_, T>
+where
+    T: Soars,
+    for<'a> T::Slices<'a>: Serialize,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        self.0.slices().serialize(serializer)
+    }
+}
+
+/// Deserializes a [`Soa`] from the column by column format written by
+/// [`Columnar`].
+///
+/// This uses the [`Deserialize`] implementation of `FooColumns`, which can be
+/// added with `#[soa_derive(include(Columns), serde::Deserialize)]`. All
+/// columns must have the same length, otherwise deserialization fails.
+pub struct DeserializeColumnar<T>(pub Soa<T>)
+where
+    T: Soars;
+
+impl<T> Debug for DeserializeColumnar<T>
+where
+    T: Soars,
+    for<'a> T::Ref<'a>: Debug,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("DeserializeColumnar").field(&self.0).finish()
+    }
+}
+
+impl<'de, T> Deserialize<'de> for DeserializeColumnar<T>
+where
+    T: SoaColumns,
+    T::Columns: Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let columns = T::Columns::deserialize(deserializer)?;
+        T::from_columns(columns).map(Self).map_err(D::Error::custom)
+    }
+}
@@ -0,0 +1,29 @@
+/// The alignment in bytes applied to fields tagged with `#[align(simd)]`.
+///
+/// This matches the width of the widest SIMD registers enabled for the
+/// compilation target:
+///
+/// | Target feature                        | Alignment |
+/// |---------------------------------------|-----------|
+/// | `avx512f`                             | 64        |
+/// | `avx`                                 | 32        |
+/// | `sse`, `neon`, `altivec`, `simd128`   | 16        |
+///
+/// When none of these features is enabled, this is 1, so `#[align(simd)]`
+/// leaves the natural alignment of the field unchanged. Enable the relevant
+/// target features, such as with `-C target-cpu=native`, to opt into wider
+/// alignment.
+pub const SIMD_ALIGN: usize = if cfg!(target_feature = "avx512f") {
+    64
+} else if cfg!(target_feature = "avx") {
+    32
+} else if cfg!(any(
+    target_feature = "sse",
+    target_feature = "neon",
+    target_feature = "altivec",
+    target_feature = "simd128",
+)) {
+    16
+} else {
+    1
+};
@@ -1,13 +1,23 @@
 use crate::{
-    chunks_exact::ChunksExact, index::SoaIndex, iter_raw::IterRaw, AsMutSlice, AsSlice, Iter,
-    IterMut, SliceMut, SliceRef, SoaDeref, SoaRaw, Soars,
+    chunk_by::{ChunkBy, ChunkByMut},
+    chunks::{Chunks, ChunksMut},
+    chunks_exact::ChunksExact,
+    index::{self, SoaIndex},
+    iter_raw::IterRaw,
+    windows::Windows,
+    AsMutSlice, AsSlice, AsSoaRef, FromSoaRef, Iter, IterMut, SliceMut, SliceRef, Soa, SoaDeref,
+    SoaRaw, Soars,
 };
 use std::{
     cmp::Ordering,
+    collections::HashMap,
+    convert::Infallible,
     fmt::{self, Debug, Formatter},
     hash::{Hash, Hasher},
+    iter::Rev,
     marker::PhantomData,
-    ops::{ControlFlow, Deref, DerefMut},
+    mem::ManuallyDrop,
+    ops::{Deref, DerefMut, Range, RangeBounds},
 };
 
 /// A dynamically-sized view into the contents of a [`Soa`].
@@ -87,7 +97,7 @@ where
     ///
     /// - `length` must be valid for the underlying type `T`.
     /// - The lifetime of the returned reference is unconstrained. Ensure that
-    /// the right lifetimes are applied.
+    ///   the right lifetimes are applied.
     pub(crate) unsafe fn as_unsized_mut<'a>(&mut self, len: usize) -> &'a mut Slice<T> {
         &mut *(std::ptr::slice_from_raw_parts_mut(self, len) as *mut Slice<T>)
     }
@@ -98,7 +108,7 @@ where
     ///
     /// - `length` must be valid for the underlying type `T`.
     /// - The lifetime of the returned reference is unconstrained. Ensure that
-    /// the right lifetimes are applied.
+    ///   the right lifetimes are applied.
     pub(crate) unsafe fn as_unsized<'a>(&self, len: usize) -> &'a Slice<T> {
         &*(std::ptr::slice_from_raw_parts(self, len) as *const Slice<T>)
     }
@@ -178,7 +188,7 @@ where
     /// assert_eq!(iter.next(), Some(FooRef(&4)));
     /// assert_eq!(iter.next(), None);
     /// ```
-    pub const fn iter(&self) -> Iter<T> {
+    pub const fn iter(&self) -> Iter<'_, T> {
         Iter {
             iter_raw: IterRaw {
                 slice: unsafe { self.as_sized() },
@@ -189,6 +199,28 @@ where
         }
     }
 
+    /// Returns an iterator over the elements in reverse order.
+    ///
+    /// This is shorthand for `self.iter().rev()`. To iterate over a single
+    /// field in reverse without constructing element references, use the
+    /// generated `{field}_rev` getters instead.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use soa_rs::{Soa, Soars, soa};
+    /// # #[derive(Soars, Debug, PartialEq)]
+    /// # #[soa_derive(Debug, PartialEq)]
+    /// # struct Foo(usize);
+    /// let soa = soa![Foo(1), Foo(2), Foo(3)];
+    /// let mut iter = soa.iter_rev();
+    /// assert_eq!(iter.next(), Some(FooRef(&3)));
+    /// assert!(soa.f0_rev().eq(&[3, 2, 1]));
+    /// ```
+    pub fn iter_rev(&self) -> Rev<Iter<'_, T>> {
+        self.iter().rev()
+    }
+
     /// Returns an iterator over the elements that allows modifying each value.
     ///
     /// The iterator yields all items from start to end.
@@ -207,7 +239,7 @@ where
     /// }
     /// assert_eq!(soa, soa![Foo(2), Foo(4), Foo(8)]);
     /// ```
-    pub fn iter_mut(&mut self) -> IterMut<T> {
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
         IterMut {
             iter_raw: IterRaw {
                 slice: unsafe { self.as_sized() },
@@ -222,10 +254,14 @@ where
     /// index.
     ///
     /// - If given a position, returns a reference to the element at that
-    /// position or None if out of bounds.
+    ///   position or None if out of bounds.
     ///
     /// - If given a range, returns the subslice corresponding to that range, or
-    /// None if out of bounds.
+    ///   None if out of bounds.
+    ///
+    /// - If given a pair or array of positions, returns references to the
+    ///   elements at each position, or None if any is out of bounds. The
+    ///   positions may repeat, unlike with [`get_mut`].
     ///
     /// # Examples
     ///
@@ -245,7 +281,11 @@ where
     /// assert_eq!(soa.get(1..3), Some(soa![Foo(40), Foo(30)].as_slice()));
     /// assert_eq!(soa.get(1..=3), Some(soa![Foo(40), Foo(30), Foo(20)].as_slice()));
     /// assert!(soa.get(2..5).is_none());
+    /// assert_eq!(soa.get((3, 1)), Some((FooRef(&20), FooRef(&40))));
+    /// assert_eq!(soa.get([0, 0]), Some([FooRef(&10), FooRef(&10)]));
     /// ```
+    ///
+    /// [`get_mut`]: Slice::get_mut
     #[inline]
     pub fn get<I>(&self, index: I) -> Option<I::Output<'_>>
     where
@@ -257,6 +297,9 @@ where
     /// Returns a mutable reference to an element or subslice depending on the
     /// type of index (see [`get`]) or `None` if the index is out of bounds.
     ///
+    /// Like [`slice::get_disjoint_mut`], a pair or array of positions returns
+    /// `None` if any two are equal.
+    ///
     /// # Examples
     ///
     /// ```
@@ -269,6 +312,12 @@ where
     ///     *elem.0 = 42;
     /// }
     /// assert_eq!(soa, soa![Foo(1), Foo(42), Foo(3)]);
+    ///
+    /// if let Some((a, b)) = soa.get_mut((0, 2)) {
+    ///     std::mem::swap(a.0, b.0);
+    /// }
+    /// assert_eq!(soa, soa![Foo(3), Foo(42), Foo(1)]);
+    /// assert!(soa.get_mut((1, 1)).is_none());
     /// ```
     ///
     /// [`get`]: Slice::get
@@ -279,6 +328,41 @@ where
         index.get_mut(self)
     }
 
+    /// Returns mutable references to several elements at once, or `None` if
+    /// any index is out of bounds or if any index appears more than once.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use soa_rs::{Soa, Soars, soa};
+    /// # #[derive(Soars, Debug, PartialEq)]
+    /// # #[soa_derive(Debug, PartialEq)]
+    /// # struct Foo(usize);
+    /// let mut soa = soa![Foo(1), Foo(2), Foo(3), Foo(4)];
+    /// if let Some([a, b]) = soa.get_disjoint_mut([0, 3]) {
+    ///     std::mem::swap(a.0, b.0);
+    /// }
+    /// assert_eq!(soa, soa![Foo(4), Foo(2), Foo(3), Foo(1)]);
+    /// assert!(soa.get_disjoint_mut([1, 1]).is_none());
+    /// assert!(soa.get_disjoint_mut([1, 4]).is_none());
+    /// ```
+    pub fn get_disjoint_mut<const N: usize>(
+        &mut self,
+        indices: [usize; N],
+    ) -> Option<[T::RefMut<'_>; N]> {
+        for (i, &index) in indices.iter().enumerate() {
+            if index >= self.len() || indices[..i].contains(&index) {
+                return None;
+            }
+        }
+
+        // SAFETY: The indices are in bounds and pairwise distinct, so none of
+        // the references alias.
+        Some(std::array::from_fn(|i| unsafe {
+            self.raw().offset(indices[i]).get_mut()
+        }))
+    }
+
     /// Returns a reference to the element at the given index.
     ///
     /// This is similar to [`Index`], which is not implementable for this type.
@@ -343,6 +427,115 @@ where
         self.get_mut(index).expect("index out of bounds")
     }
 
+    /// Returns a copy of the element at the given index.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the index is out-of-bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use soa_rs::{Soa, Soars, soa};
+    /// # #[derive(Soars, Debug, PartialEq, Clone, Copy)]
+    /// # #[soa_derive(Debug, PartialEq)]
+    /// # struct Foo(usize);
+    /// let soa = soa![Foo(10), Foo(20), Foo(30)];
+    /// assert_eq!(soa.nth_copied(1), Foo(20));
+    /// ```
+    pub fn nth_copied(&self, index: usize) -> T
+    where
+        T: Copy,
+    {
+        assert!(index < self.len(), "index out of bounds");
+        // SAFETY: The index is in bounds and T: Copy, so reading it out leaves
+        // the original intact
+        unsafe { self.raw().offset(index).get() }
+    }
+
+    /// Returns a clone of the element at the given index, created with
+    /// [`FromSoaRef`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the index is out-of-bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use soa_rs::{Soa, Soars, soa};
+    /// # #[derive(Soars, Debug, PartialEq, Clone)]
+    /// # #[soa_derive(Debug, PartialEq)]
+    /// # struct Foo(String);
+    /// let soa = soa![Foo("a".into()), Foo("b".into())];
+    /// assert_eq!(soa.nth_cloned(1), Foo("b".into()));
+    /// ```
+    pub fn nth_cloned(&self, index: usize) -> T
+    where
+        T: FromSoaRef,
+    {
+        T::from_soa_ref(self.idx(index))
+    }
+
+    /// Returns a reference to the element at the given index without bounds
+    /// checking.
+    ///
+    /// For a safe alternative, see [`get`] or [`idx`].
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure that `index < self.len()`. Calling this method
+    /// with an out-of-bounds index is undefined behavior even if the resulting
+    /// reference is not used.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use soa_rs::{Soa, Soars, soa};
+    /// # #[derive(Soars, Debug, PartialEq)]
+    /// # #[soa_derive(Debug, PartialEq)]
+    /// # struct Foo(usize);
+    /// let soa = soa![Foo(1), Foo(2), Foo(3)];
+    /// assert_eq!(unsafe { soa.get_unchecked(1) }, FooRef(&2));
+    /// ```
+    ///
+    /// [`get`]: Slice::get
+    /// [`idx`]: Slice::idx
+    pub unsafe fn get_unchecked(&self, index: usize) -> T::Ref<'_> {
+        debug_assert!(index < self.len());
+        unsafe { self.raw().offset(index).get_ref() }
+    }
+
+    /// Returns a mutable reference to the element at the given index without
+    /// bounds checking.
+    ///
+    /// For a safe alternative, see [`get_mut`] or [`idx_mut`].
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure that `index < self.len()`. Calling this method
+    /// with an out-of-bounds index is undefined behavior even if the resulting
+    /// reference is not used.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use soa_rs::{Soa, Soars, soa};
+    /// # #[derive(Soars, Debug, PartialEq)]
+    /// # #[soa_derive(Debug, PartialEq)]
+    /// # struct Foo(usize);
+    /// let mut soa = soa![Foo(1), Foo(2), Foo(3)];
+    /// *unsafe { soa.get_unchecked_mut(1) }.0 = 42;
+    /// assert_eq!(soa, soa![Foo(1), Foo(42), Foo(3)]);
+    /// ```
+    ///
+    /// [`get_mut`]: Slice::get_mut
+    /// [`idx_mut`]: Slice::idx_mut
+    pub unsafe fn get_unchecked_mut(&mut self, index: usize) -> T::RefMut<'_> {
+        debug_assert!(index < self.len());
+        unsafe { self.raw().offset(index).get_mut() }
+    }
+
     /// Swaps the position of two elements.
     ///
     /// # Arguments
@@ -379,6 +572,192 @@ where
         }
     }
 
+    /// Swaps all elements in `self` with those in `other`.
+    ///
+    /// The two slices must have the same length. To exchange two whole
+    /// [`Soa`]s, [`std::mem::swap`] is cheaper since it only swaps the
+    /// pointers.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the two slices have different lengths.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use soa_rs::{Soa, Soars, soa};
+    /// # #[derive(Soars, Debug, PartialEq)]
+    /// # #[soa_derive(Debug, PartialEq)]
+    /// # struct Foo(usize);
+    /// let mut a = soa![Foo(1), Foo(2), Foo(3)];
+    /// let mut b = soa![Foo(4), Foo(5)];
+    /// a.idx_mut(1..).swap_with_slice(&mut b);
+    /// assert_eq!(a, soa![Foo(1), Foo(4), Foo(5)]);
+    /// assert_eq!(b, soa![Foo(2), Foo(3)]);
+    /// ```
+    pub fn swap_with_slice(&mut self, other: &mut Self) {
+        assert_eq!(
+            self.len(),
+            other.len(),
+            "destination and source slices have different lengths"
+        );
+
+        // SAFETY: Both slices have len elements and cannot overlap since they
+        // are borrowed mutably.
+        unsafe {
+            for i in 0..self.len() {
+                let a = self.raw().offset(i);
+                let b = other.raw().offset(i);
+                let tmp = a.get();
+                b.copy_to(a, 1);
+                b.set(tmp);
+            }
+        }
+    }
+
+    /// Copies elements from one part of the slice to another part of itself.
+    ///
+    /// `src` is the range within the slice to copy from and `dest` is the
+    /// starting index of the range to copy to, which will have the same length
+    /// as `src`. The two ranges may overlap.
+    ///
+    /// # Panics
+    ///
+    /// Panics if either range exceeds the end of the slice, or if the end of
+    /// `src` is before the start.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use soa_rs::{Soa, Soars, soa};
+    /// # #[derive(Soars, Debug, PartialEq, Clone, Copy)]
+    /// # #[soa_derive(Debug, PartialEq)]
+    /// # struct Foo(usize);
+    /// let mut soa = soa![Foo(0), Foo(1), Foo(2), Foo(3), Foo(4)];
+    /// soa.copy_within(1..4, 2);
+    /// assert_eq!(soa, soa![Foo(0), Foo(1), Foo(1), Foo(2), Foo(3)]);
+    /// ```
+    pub fn copy_within<R>(&mut self, src: R, dest: usize)
+    where
+        R: RangeBounds<usize>,
+        T: Copy,
+    {
+        let Some(Range { start, end }) = index::bounds(src, self.len()) else {
+            panic!("index out of bounds")
+        };
+        let count = end - start;
+        if dest > self.len() - count {
+            panic!("index out of bounds");
+        }
+
+        // SAFETY: Both ranges were checked against the length, copy_to permits
+        // overlap, and T: Copy means the overwritten elements need no drop
+        unsafe {
+            self.raw()
+                .offset(start)
+                .copy_to(self.raw().offset(dest), count);
+        }
+    }
+
+    /// Fills the slice with clones of `value`.
+    ///
+    /// Each existing element is dropped as it is replaced. The last slot
+    /// receives `value` itself rather than a clone.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use soa_rs::{Soa, Soars, soa};
+    /// # #[derive(Soars, Debug, PartialEq, Clone)]
+    /// # #[soa_derive(Debug, PartialEq)]
+    /// # struct Foo(String);
+    /// let mut soa = soa![Foo("a".into()), Foo("b".into()), Foo("c".into())];
+    /// soa.fill(Foo("z".into()));
+    /// assert_eq!(soa, soa![Foo("z".into()), Foo("z".into()), Foo("z".into())]);
+    /// ```
+    pub fn fill(&mut self, value: T)
+    where
+        T: Clone,
+    {
+        let Some(last) = self.len().checked_sub(1) else {
+            return;
+        };
+        for i in 0..last {
+            // SAFETY: The new element is created before the old one is moved
+            // out, so a panicking clone leaves the slice untouched
+            unsafe { self.replace_unchecked(i, value.clone()) };
+        }
+        unsafe { self.replace_unchecked(last, value) };
+    }
+
+    /// Fills the slice with elements returned by calling `f` repeatedly.
+    ///
+    /// Each existing element is dropped as it is replaced, from first to last.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use soa_rs::{Soa, Soars, soa};
+    /// # #[derive(Soars, Debug, PartialEq)]
+    /// # #[soa_derive(Debug, PartialEq)]
+    /// # struct Foo(usize);
+    /// let mut soa = soa![Foo(0), Foo(0), Foo(0)];
+    /// let mut n = 0;
+    /// soa.fill_with(|| {
+    ///     n += 1;
+    ///     Foo(n)
+    /// });
+    /// assert_eq!(soa, soa![Foo(1), Foo(2), Foo(3)]);
+    /// ```
+    pub fn fill_with<F>(&mut self, mut f: F)
+    where
+        F: FnMut() -> T,
+    {
+        for i in 0..self.len() {
+            unsafe { self.replace_unchecked(i, f()) };
+        }
+    }
+
+    /// Fills the slice with elements returned by calling `f` with each index.
+    ///
+    /// This is like [`Slice::fill_with`], but `f` is given the index of the
+    /// slot being filled. Each existing element is dropped as it is replaced,
+    /// from first to last. If `f` panics, the elements already replaced keep
+    /// their new values and the rest are left untouched.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use soa_rs::{Soa, Soars, soa};
+    /// # #[derive(Soars, Debug, PartialEq)]
+    /// # #[soa_derive(Debug, PartialEq)]
+    /// # struct Foo(usize);
+    /// let mut soa = soa![Foo(0), Foo(0), Foo(0)];
+    /// soa.fill_indexed(|i| Foo(i * 10));
+    /// assert_eq!(soa, soa![Foo(0), Foo(10), Foo(20)]);
+    /// ```
+    pub fn fill_indexed<F>(&mut self, mut f: F)
+    where
+        F: FnMut(usize) -> T,
+    {
+        for i in 0..self.len() {
+            unsafe { self.replace_unchecked(i, f(i)) };
+        }
+    }
+
+    /// Replaces the element at `index` with `element` and drops the old one.
+    ///
+    /// # Safety
+    ///
+    /// `index` must be in bounds.
+    unsafe fn replace_unchecked(&mut self, index: usize, element: T) {
+        let ith = self.raw().offset(index);
+        let old = ith.get();
+        ith.set(element);
+        // Dropped last so that the slot is valid even if the destructor panics
+        drop(old);
+    }
+
     /// Returns the first element of the slice, or None if empty.
     ///
     /// # Examples
@@ -455,18 +834,8 @@ where
         self.get_mut(self.len().saturating_sub(1))
     }
 
-    /// Returns an iterator over `chunk_size` elements of the slice at a time,
-    /// starting at the beginning of the slice.
-    ///
-    /// The chunks are slices and do not overlap. If `chunk_size` does not divide
-    /// the length of the slice, then the last up to `chunk_size-1` elements will
-    /// be omitted and can be retrieved from the [`remainder`] function of the
-    /// iterator.
-    ///
-    /// Due to each chunk having exactly `chunk_size` elements, the compiler can
-    /// often optimize the resulting code better than in the case of chunks.
-    ///
-    /// [`remainder`]: ChunksExact::remainder
+    /// Returns the first element and the rest of the slice, or [`None`] if it
+    /// is empty.
     ///
     /// # Examples
     ///
@@ -474,26 +843,21 @@ where
     /// # use soa_rs::{Soa, Soars, soa, AsSlice};
     /// # #[derive(Soars, Debug, PartialEq)]
     /// # #[soa_derive(Debug, PartialEq)]
-    /// # struct Foo(char);
-    /// let soa = soa![Foo('l'), Foo('o'), Foo('r'), Foo('e'), Foo('m')];
-    /// let mut iter = soa.chunks_exact(2);
-    /// assert_eq!(iter.next(), Some(soa![Foo('l'), Foo('o')].as_slice()));
-    /// assert_eq!(iter.next(), Some(soa![Foo('r'), Foo('e')].as_slice()));
-    /// assert!(iter.next().is_none());
-    /// assert_eq!(iter.remainder(), &soa![Foo('m')]);
+    /// # struct Foo(usize);
+    /// let soa = soa![Foo(0), Foo(1), Foo(2)];
+    /// let (first, rest) = soa.split_first().unwrap();
+    /// assert_eq!(first, FooRef(&0));
+    /// assert_eq!(rest, soa![Foo(1), Foo(2)]);
     /// ```
-    pub fn chunks_exact(&self, chunk_size: usize) -> ChunksExact<'_, T> {
-        if chunk_size == 0 {
-            panic!("chunk size must be nonzero")
-        }
-
-        ChunksExact::new(self, chunk_size)
+    pub fn split_first(&self) -> Option<(T::Ref<'_>, SliceRef<'_, T>)> {
+        let len = self.len().checked_sub(1)?;
+        let first = unsafe { self.raw().get_ref() };
+        let rest = unsafe { SliceRef::from_slice(Slice::with_raw(self.raw().offset(1)), len) };
+        Some((first, rest))
     }
 
-    /// Returns a collection of slices for each field of the slice.
-    ///
-    /// For convenience, slices can also be aquired using the getter methods for
-    /// individual fields.
+    /// Returns the first element and the rest of the slice as mutable
+    /// references, or [`None`] if it is empty.
     ///
     /// # Examples
     ///
@@ -501,53 +865,1347 @@ where
     /// # use soa_rs::{Soa, Soars, soa};
     /// # #[derive(Soars, Debug, PartialEq)]
     /// # #[soa_derive(Debug, PartialEq)]
-    /// # struct Foo {
-    /// #     foo: u8,
-    /// #     bar: u8,
-    /// # }
-    /// let soa = soa![Foo { foo: 1, bar: 2 }, Foo { foo: 3, bar: 4 }];
-    /// let slices = soa.slices();
-    /// assert_eq!(slices.foo, soa.foo());
-    /// assert_eq!(slices.bar, soa.bar());
+    /// # struct Foo(usize);
+    /// let mut soa = soa![Foo(0), Foo(1), Foo(2)];
+    /// if let Some((first, mut rest)) = soa.split_first_mut() {
+    ///     *first.0 = 10;
+    ///     rest.f0_mut()[1] = 20;
+    /// }
+    /// assert_eq!(soa, soa![Foo(10), Foo(1), Foo(20)]);
     /// ```
-    pub fn slices(&self) -> T::Slices<'_> {
-        unsafe { self.raw.slices(self.len()) }
+    pub fn split_first_mut(&mut self) -> Option<(T::RefMut<'_>, SliceMut<'_, T>)> {
+        let len = self.len().checked_sub(1)?;
+        let first = unsafe { self.raw().get_mut() };
+        let rest = unsafe { SliceMut::from_slice(Slice::with_raw(self.raw().offset(1)), len) };
+        Some((first, rest))
     }
 
-    /// Returns a collection of mutable slices for each field of the slice.
-    ///
-    /// For convenience, individual mutable slices can also be aquired using the
-    /// getter methods for individual fields. This method is necessary to be
-    /// able to mutably borrow multiple SoA fields simultaneously.
+    /// Returns the last element and the rest of the slice, or [`None`] if it
+    /// is empty.
     ///
     /// # Examples
     ///
     /// ```
-    /// # use soa_rs::{Soa, Soars, soa};
+    /// # use soa_rs::{Soa, Soars, soa, AsSlice};
     /// # #[derive(Soars, Debug, PartialEq)]
     /// # #[soa_derive(Debug, PartialEq)]
-    /// # struct Foo {
-    /// #     foo: u8,
-    /// #     bar: u8,
-    /// # }
-    /// let mut soa = soa![Foo { foo: 1, bar: 0 }, Foo { foo: 2, bar: 0 }];
-    /// let slices = soa.slices_mut();
-    /// for (foo, bar) in slices.foo.iter().zip(slices.bar) {
-    ///     *bar = foo * 2;
-    /// }
-    /// assert_eq!(soa.bar(), [2, 4]);
+    /// # struct Foo(usize);
+    /// let soa = soa![Foo(0), Foo(1), Foo(2)];
+    /// let (last, rest) = soa.split_last().unwrap();
+    /// assert_eq!(last, FooRef(&2));
+    /// assert_eq!(rest, soa![Foo(0), Foo(1)]);
     /// ```
-    pub fn slices_mut(&mut self) -> T::SlicesMut<'_> {
-        unsafe { self.raw.slices_mut(self.len()) }
+    pub fn split_last(&self) -> Option<(T::Ref<'_>, SliceRef<'_, T>)> {
+        let len = self.len().checked_sub(1)?;
+        let last = unsafe { self.raw().offset(len).get_ref() };
+        let rest = unsafe { SliceRef::from_slice(Slice::with_raw(self.raw()), len) };
+        Some((last, rest))
     }
 
-    /// Converts from an unsized variant to sized variant
+    /// Returns the last element and the rest of the slice as mutable
+    /// references, or [`None`] if it is empty.
     ///
-    /// # Safety
+    /// # Examples
     ///
-    /// Since this returns an owned value, it implicitly extends the lifetime &
-    /// in an unbounded way. The caller must ensure proper lifetimes with, for
-    /// example, [`PhantomData`].
+    /// ```
+    /// # use soa_rs::{Soa, Soars, soa};
+    /// # #[derive(Soars, Debug, PartialEq)]
+    /// # #[soa_derive(Debug, PartialEq)]
+    /// # struct Foo(usize);
+    /// let mut soa = soa![Foo(0), Foo(1), Foo(2)];
+    /// if let Some((last, mut rest)) = soa.split_last_mut() {
+    ///     *last.0 = 10;
+    ///     rest.f0_mut()[0] = 20;
+    /// }
+    /// assert_eq!(soa, soa![Foo(20), Foo(1), Foo(10)]);
+    /// ```
+    pub fn split_last_mut(&mut self) -> Option<(T::RefMut<'_>, SliceMut<'_, T>)> {
+        let len = self.len().checked_sub(1)?;
+        let last = unsafe { self.raw().offset(len).get_mut() };
+        let rest = unsafe { SliceMut::from_slice(Slice::with_raw(self.raw()), len) };
+        Some((last, rest))
+    }
+
+    /// Returns the index of the first element that satisfies the predicate,
+    /// or [`None`] if no element matches.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use soa_rs::{Soa, Soars, soa};
+    /// # #[derive(Soars, Debug, PartialEq)]
+    /// # #[soa_derive(Debug, PartialEq)]
+    /// # struct Foo(usize);
+    /// let soa = soa![Foo(1), Foo(2), Foo(3), Foo(2)];
+    /// assert_eq!(soa.position(|foo| *foo.0 == 2), Some(1));
+    /// assert_eq!(soa.position(|foo| *foo.0 == 4), None);
+    /// ```
+    pub fn position<F>(&self, f: F) -> Option<usize>
+    where
+        F: FnMut(T::Ref<'_>) -> bool,
+    {
+        self.iter().position(f)
+    }
+
+    /// Returns the index of the last element that satisfies the predicate,
+    /// or [`None`] if no element matches.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use soa_rs::{Soa, Soars, soa};
+    /// # #[derive(Soars, Debug, PartialEq)]
+    /// # #[soa_derive(Debug, PartialEq)]
+    /// # struct Foo(usize);
+    /// let soa = soa![Foo(1), Foo(2), Foo(3), Foo(2)];
+    /// assert_eq!(soa.rposition(|foo| *foo.0 == 2), Some(3));
+    /// assert_eq!(soa.rposition(|foo| *foo.0 == 4), None);
+    /// ```
+    pub fn rposition<F>(&self, f: F) -> Option<usize>
+    where
+        F: FnMut(T::Ref<'_>) -> bool,
+    {
+        self.iter().rposition(f)
+    }
+
+    /// Returns the index of the first element equal to `needle`, or [`None`]
+    /// if there is no such element.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use soa_rs::{Soa, Soars, soa};
+    /// # #[derive(Soars, Debug, PartialEq)]
+    /// # #[soa_derive(Debug, PartialEq)]
+    /// # struct Foo(usize);
+    /// let soa = soa![Foo(1), Foo(2), Foo(3), Foo(2)];
+    /// assert_eq!(soa.index_of(Foo(2)), Some(1));
+    /// assert_eq!(soa.index_of(soa.idx(2)), Some(2));
+    /// assert_eq!(soa.index_of(Foo(4)), None);
+    /// ```
+    pub fn index_of<R>(&self, needle: R) -> Option<usize>
+    where
+        R: AsSoaRef<Item = T>,
+        for<'a> T::Ref<'a>: PartialEq,
+    {
+        let needle = needle.as_soa_ref();
+        // SAFETY: The references are in bounds and do not outlive the borrow
+        // of needle, which is shorter than the borrow of self.
+        (0..self.len()).position(|i| unsafe { self.raw().offset(i).get_ref() } == needle)
+    }
+
+    /// Returns a mutable reference to the first element that satisfies the
+    /// predicate, or [`None`] if no element matches.
+    ///
+    /// The predicate is given an immutable reference to each element.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use soa_rs::{Soa, Soars, soa};
+    /// # #[derive(Soars, Debug, PartialEq)]
+    /// # #[soa_derive(Debug, PartialEq)]
+    /// # struct Foo(usize);
+    /// let mut soa = soa![Foo(1), Foo(5), Foo(10)];
+    /// if let Some(foo) = soa.find_mut(|foo| *foo.0 > 3) {
+    ///     *foo.0 = 0;
+    /// }
+    /// assert_eq!(soa, soa![Foo(1), Foo(0), Foo(10)]);
+    /// assert!(soa.find_mut(|foo| *foo.0 > 10).is_none());
+    /// ```
+    pub fn find_mut<P>(&mut self, predicate: P) -> Option<T::RefMut<'_>>
+    where
+        P: FnMut(T::Ref<'_>) -> bool,
+    {
+        let index = self.position(predicate)?;
+        // SAFETY: The index was found by iterating the slice.
+        Some(unsafe { self.raw().offset(index).get_mut() })
+    }
+
+    /// Returns an iterator over `chunk_size` elements of the slice at a time,
+    /// starting at the beginning of the slice.
+    ///
+    /// The chunks are slices and do not overlap. If `chunk_size` does not divide
+    /// the length of the slice, then the last chunk will not have length
+    /// `chunk_size`.
+    ///
+    /// See [`chunks_exact`] for a variant of this iterator that returns chunks
+    /// of always exactly `chunk_size` elements.
+    ///
+    /// [`chunks_exact`]: Slice::chunks_exact
+    ///
+    /// # Panics
+    ///
+    /// Panics if `chunk_size` is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use soa_rs::{Soa, Soars, soa, AsSlice};
+    /// # #[derive(Soars, Debug, PartialEq)]
+    /// # #[soa_derive(Debug, PartialEq)]
+    /// # struct Foo(char);
+    /// let soa = soa![Foo('l'), Foo('o'), Foo('r'), Foo('e'), Foo('m')];
+    /// let mut iter = soa.chunks(2);
+    /// assert_eq!(iter.next(), Some(soa![Foo('l'), Foo('o')].as_slice()));
+    /// assert_eq!(iter.next(), Some(soa![Foo('r'), Foo('e')].as_slice()));
+    /// assert_eq!(iter.next(), Some(soa![Foo('m')].as_slice()));
+    /// assert!(iter.next().is_none());
+    /// ```
+    pub fn chunks(&self, chunk_size: usize) -> Chunks<'_, T> {
+        if chunk_size == 0 {
+            panic!("chunk size must be nonzero")
+        }
+
+        Chunks::new(self, chunk_size)
+    }
+
+    /// Returns an iterator over `chunk_size` elements of the slice at a time,
+    /// starting at the beginning of the slice.
+    ///
+    /// The chunks are mutable slices and do not overlap. If `chunk_size` does
+    /// not divide the length of the slice, then the last chunk will not have
+    /// length `chunk_size`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `chunk_size` is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use soa_rs::{Soa, Soars, soa};
+    /// # #[derive(Soars, Debug, PartialEq)]
+    /// # #[soa_derive(Debug, PartialEq)]
+    /// # struct Foo(usize);
+    /// let mut soa = soa![Foo(0), Foo(0), Foo(0), Foo(0), Foo(0)];
+    /// for (i, mut chunk) in soa.chunks_mut(2).enumerate() {
+    ///     chunk.f0_mut().fill(i);
+    /// }
+    /// assert_eq!(soa, soa![Foo(0), Foo(0), Foo(1), Foo(1), Foo(2)]);
+    /// ```
+    pub fn chunks_mut(&mut self, chunk_size: usize) -> ChunksMut<'_, T> {
+        if chunk_size == 0 {
+            panic!("chunk size must be nonzero")
+        }
+
+        ChunksMut::new(self, chunk_size)
+    }
+
+    /// Returns an iterator over `chunk_size` elements of the slice at a time,
+    /// starting at the beginning of the slice.
+    ///
+    /// The chunks are slices and do not overlap. If `chunk_size` does not divide
+    /// the length of the slice, then the last up to `chunk_size-1` elements will
+    /// be omitted and can be retrieved from the [`remainder`] function of the
+    /// iterator.
+    ///
+    /// Due to each chunk having exactly `chunk_size` elements, the compiler can
+    /// often optimize the resulting code better than in the case of chunks.
+    ///
+    /// [`remainder`]: ChunksExact::remainder
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use soa_rs::{Soa, Soars, soa, AsSlice};
+    /// # #[derive(Soars, Debug, PartialEq)]
+    /// # #[soa_derive(Debug, PartialEq)]
+    /// # struct Foo(char);
+    /// let soa = soa![Foo('l'), Foo('o'), Foo('r'), Foo('e'), Foo('m')];
+    /// let mut iter = soa.chunks_exact(2);
+    /// assert_eq!(iter.next(), Some(soa![Foo('l'), Foo('o')].as_slice()));
+    /// assert_eq!(iter.next(), Some(soa![Foo('r'), Foo('e')].as_slice()));
+    /// assert!(iter.next().is_none());
+    /// assert_eq!(iter.remainder(), &soa![Foo('m')]);
+    /// ```
+    pub fn chunks_exact(&self, chunk_size: usize) -> ChunksExact<'_, T> {
+        if chunk_size == 0 {
+            panic!("chunk size must be nonzero")
+        }
+
+        ChunksExact::new(self, chunk_size)
+    }
+
+    /// Returns an iterator over the slice producing non-overlapping runs of
+    /// elements, using the predicate to separate them.
+    ///
+    /// The predicate is called for every pair of consecutive elements, meaning
+    /// that it is called on `slice[0]` and `slice[1]`, followed by `slice[1]`
+    /// and `slice[2]`, and so on. A new run starts wherever it returns `false`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use soa_rs::{Soa, Soars, soa, AsSlice};
+    /// # #[derive(Soars, Debug, PartialEq)]
+    /// # #[soa_derive(Debug, PartialEq)]
+    /// # struct Foo(u8);
+    /// let soa = soa![Foo(1), Foo(1), Foo(2), Foo(3), Foo(3)];
+    /// let mut iter = soa.chunk_by(|a, b| a.0 == b.0);
+    /// assert_eq!(iter.next(), Some(soa![Foo(1), Foo(1)].as_slice()));
+    /// assert_eq!(iter.next(), Some(soa![Foo(2)].as_slice()));
+    /// assert_eq!(iter.next(), Some(soa![Foo(3), Foo(3)].as_slice()));
+    /// assert!(iter.next().is_none());
+    /// ```
+    pub fn chunk_by<F>(&self, pred: F) -> ChunkBy<'_, T, F>
+    where
+        F: FnMut(T::Ref<'_>, T::Ref<'_>) -> bool,
+    {
+        ChunkBy::new(self, pred)
+    }
+
+    /// Returns an iterator over the slice producing non-overlapping mutable
+    /// runs of elements, using the predicate to separate them.
+    ///
+    /// See [`Slice::chunk_by`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use soa_rs::{Soa, Soars, soa};
+    /// # #[derive(Soars, Debug, PartialEq)]
+    /// # #[soa_derive(Debug, PartialEq)]
+    /// # struct Foo(u8, usize);
+    /// let mut soa = soa![Foo(1, 0), Foo(1, 0), Foo(2, 0), Foo(3, 0), Foo(3, 0)];
+    /// for (i, mut run) in soa.chunk_by_mut(|a, b| a.0 == b.0).enumerate() {
+    ///     run.f1_mut().fill(i);
+    /// }
+    /// assert_eq!(soa.f1(), [0, 0, 1, 2, 2]);
+    /// ```
+    pub fn chunk_by_mut<F>(&mut self, pred: F) -> ChunkByMut<'_, T, F>
+    where
+        F: FnMut(T::Ref<'_>, T::Ref<'_>) -> bool,
+    {
+        ChunkByMut::new(self, pred)
+    }
+
+    /// Returns an iterator over all contiguous windows of length `size`. The
+    /// windows overlap. If the slice is shorter than `size`, the iterator
+    /// returns no values.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `size` is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use soa_rs::{Soa, Soars, soa, AsSlice};
+    /// # #[derive(Soars, Debug, PartialEq)]
+    /// # #[soa_derive(Debug, PartialEq)]
+    /// # struct Foo(char);
+    /// let soa = soa![Foo('l'), Foo('o'), Foo('r'), Foo('e')];
+    /// let mut iter = soa.windows(3);
+    /// assert_eq!(iter.next(), Some(soa![Foo('l'), Foo('o'), Foo('r')].as_slice()));
+    /// assert_eq!(iter.next(), Some(soa![Foo('o'), Foo('r'), Foo('e')].as_slice()));
+    /// assert!(iter.next().is_none());
+    /// ```
+    pub fn windows(&self, size: usize) -> Windows<'_, T> {
+        if size == 0 {
+            panic!("window size must be nonzero")
+        }
+
+        Windows::new(self, size)
+    }
+
+    /// Applies `f` to each contiguous window of length `window` and collects
+    /// the results into a new [`Soa`].
+    ///
+    /// The result has `len - window + 1` elements, or none if the slice is
+    /// shorter than `window`. See [`Slice::windows`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `window` is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use soa_rs::{Soa, Soars, soa};
+    /// # #[derive(Soars, Debug, PartialEq)]
+    /// # #[soa_derive(Debug, PartialEq)]
+    /// # struct Foo(u32);
+    /// #[derive(Soars, Debug, PartialEq)]
+    /// #[soa_derive(Debug, PartialEq)]
+    /// struct Mean(f32);
+    ///
+    /// let soa = soa![Foo(1), Foo(2), Foo(6), Foo(3)];
+    /// let means = soa.rolling(2, |window| {
+    ///     Mean(window.f0().iter().sum::<u32>() as f32 / 2.0)
+    /// });
+    /// assert_eq!(means, soa![Mean(1.5), Mean(4.0), Mean(4.5)]);
+    /// ```
+    pub fn rolling<U, F>(&self, window: usize, f: F) -> Soa<U>
+    where
+        U: Soars,
+        F: FnMut(SliceRef<'_, T>) -> U,
+    {
+        self.windows(window).map(f).collect()
+    }
+
+    /// Sorts the slice with a comparator function.
+    ///
+    /// This sort is stable (i.e., does not reorder equal elements) and
+    /// O(n * log(n)) worst-case. Rather than swapping elements as it goes,
+    /// this sorts a list of indices and then moves each element into place
+    /// exactly once, which is much cheaper when `T` has many fields. It
+    /// allocates a buffer of `len` indices to do so.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use soa_rs::{Soa, Soars, soa};
+    /// # #[derive(Soars, Debug, PartialEq)]
+    /// # #[soa_derive(Debug, PartialEq)]
+    /// # struct Foo(usize, char);
+    /// let mut soa = soa![Foo(3, 'a'), Foo(1, 'b'), Foo(2, 'c'), Foo(1, 'd')];
+    /// soa.sort_by(|a, b| a.0.cmp(b.0));
+    /// assert_eq!(soa, soa![Foo(1, 'b'), Foo(1, 'd'), Foo(2, 'c'), Foo(3, 'a')]);
+    /// ```
+    pub fn sort_by<F>(&mut self, mut compare: F)
+    where
+        F: FnMut(T::Ref<'_>, T::Ref<'_>) -> Ordering,
+    {
+        let raw = self.raw();
+        let mut indices: Vec<_> = (0..self.len()).collect();
+        indices
+            .sort_by(|&a, &b| unsafe { compare(raw.offset(a).get_ref(), raw.offset(b).get_ref()) });
+        unsafe { self.permute(&mut indices) }
+    }
+
+    /// Sorts the slice with a key extraction function.
+    ///
+    /// This sort is stable (i.e., does not reorder equal elements). See
+    /// [`sort_by`] for details.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use soa_rs::{Soa, Soars, soa};
+    /// # #[derive(Soars, Debug, PartialEq)]
+    /// # #[soa_derive(Debug, PartialEq)]
+    /// # struct Foo(i8);
+    /// let mut soa = soa![Foo(-5), Foo(4), Foo(1), Foo(-3), Foo(2)];
+    /// soa.sort_by_key(|foo| foo.0.abs());
+    /// assert_eq!(soa, soa![Foo(1), Foo(2), Foo(-3), Foo(4), Foo(-5)]);
+    /// ```
+    ///
+    /// [`sort_by`]: Slice::sort_by
+    pub fn sort_by_key<K, F>(&mut self, mut f: F)
+    where
+        F: FnMut(T::Ref<'_>) -> K,
+        K: Ord,
+    {
+        self.sort_by(|a, b| f(a).cmp(&f(b)));
+    }
+
+    /// Reorders the slice such that the element at `index` is at its final
+    /// sorted position according to the comparator function.
+    ///
+    /// Afterwards, every element before `index` is less than or equal to the
+    /// element at `index` and every element after it is greater than or equal
+    /// to it. Returns the elements before `index`, the element at `index`, and
+    /// the elements after it. This reordering is unstable (i.e., may reorder
+    /// equal elements) and in-place (i.e., does not allocate).
+    ///
+    /// This is a quickselect with median-of-three pivots and three-way
+    /// partitioning, moving elements with [`Slice::swap`]. It runs in O(n) time
+    /// on average. After too many unbalanced partitions, it falls back to a
+    /// heapsort of the remaining range, giving O(n * log(n)) worst-case.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use soa_rs::{Soa, Soars, soa};
+    /// # #[derive(Soars, Debug, PartialEq)]
+    /// # #[soa_derive(Debug, PartialEq)]
+    /// # struct Foo(i32);
+    /// let mut soa = soa![Foo(-5), Foo(4), Foo(2), Foo(-3), Foo(1)];
+    /// let (lesser, median, greater) = soa.select_nth_unstable_by(2, |a, b| a.0.cmp(b.0));
+    /// assert!(lesser.f0().iter().all(|&x| x <= 1));
+    /// assert_eq!(*median.0, 1);
+    /// assert!(greater.f0().iter().all(|&x| x >= 1));
+    /// ```
+    pub fn select_nth_unstable_by<F>(
+        &mut self,
+        index: usize,
+        mut compare: F,
+    ) -> (SliceMut<'_, T>, T::RefMut<'_>, SliceMut<'_, T>)
+    where
+        F: FnMut(T::Ref<'_>, T::Ref<'_>) -> Ordering,
+    {
+        let len = self.len();
+        assert!(index < len, "index out of bounds");
+
+        let raw = self.raw();
+        // SAFETY: Only called with indices in bounds, and the references do
+        // not outlive the comparison.
+        let mut less =
+            |a: usize, b: usize| unsafe { compare(raw.offset(a).get_ref(), raw.offset(b).get_ref()) }
+                == Ordering::Less;
+
+        let mut lo = 0;
+        let mut hi = len;
+        let mut limit = 2 * (usize::BITS - len.leading_zeros());
+        while hi - lo > 1 {
+            if limit == 0 {
+                self.heapsort_range(lo, hi, &mut less);
+                break;
+            }
+            limit -= 1;
+
+            // Move the median of the first, middle, and last elements to the
+            // front to use as the pivot.
+            let mid = lo + (hi - lo) / 2;
+            if less(mid, lo) {
+                self.swap(mid, lo);
+            }
+            if less(hi - 1, lo) {
+                self.swap(hi - 1, lo);
+            }
+            if less(hi - 1, mid) {
+                self.swap(hi - 1, mid);
+            }
+            self.swap(lo, mid);
+
+            // Partition into [lo, lt) < pivot, [lt, gt) == pivot, and
+            // [gt, hi) > pivot. The element at lt is always equal to the pivot.
+            let mut lt = lo;
+            let mut gt = hi;
+            let mut i = lo + 1;
+            while i < gt {
+                if less(i, lt) {
+                    self.swap(lt, i);
+                    lt += 1;
+                    i += 1;
+                } else if less(lt, i) {
+                    gt -= 1;
+                    self.swap(i, gt);
+                } else {
+                    i += 1;
+                }
+            }
+
+            if index < lt {
+                hi = lt;
+            } else if index >= gt {
+                lo = gt;
+            } else {
+                break;
+            }
+        }
+
+        unsafe {
+            let lesser = SliceMut::from_slice(Slice::with_raw(raw), index);
+            let nth = raw.offset(index).get_mut();
+            let greater =
+                SliceMut::from_slice(Slice::with_raw(raw.offset(index + 1)), len - index - 1);
+            (lesser, nth, greater)
+        }
+    }
+
+    /// Sorts the elements in `lo..hi` in-place with a heapsort.
+    fn heapsort_range<F>(&mut self, lo: usize, hi: usize, less: &mut F)
+    where
+        F: FnMut(usize, usize) -> bool,
+    {
+        let len = hi - lo;
+        let mut sift_down = |this: &mut Self, mut node: usize, end: usize| loop {
+            let mut child = 2 * node + 1;
+            if child >= end {
+                break;
+            }
+            if child + 1 < end && less(lo + child, lo + child + 1) {
+                child += 1;
+            }
+            if !less(lo + node, lo + child) {
+                break;
+            }
+            this.swap(lo + node, lo + child);
+            node = child;
+        };
+
+        for node in (0..len / 2).rev() {
+            sift_down(self, node, len);
+        }
+        for end in (1..len).rev() {
+            self.swap(lo, lo + end);
+            sift_down(self, 0, end);
+        }
+    }
+
+    /// Rotates the slice in-place such that the first `mid` elements of the
+    /// slice move to the end while the last `self.len() - mid` elements move
+    /// to the front.
+    ///
+    /// After calling `rotate_left`, the element previously at index `mid`
+    /// will become the first element in the slice.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if `mid` is greater than the length of the
+    /// slice. Note that `mid == self.len()` does not panic and is a no-op
+    /// rotation.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use soa_rs::{Soa, Soars, soa};
+    /// # #[derive(Soars, Debug, PartialEq)]
+    /// # #[soa_derive(Debug, PartialEq)]
+    /// # struct Foo(char);
+    /// let mut soa = soa![Foo('a'), Foo('b'), Foo('c'), Foo('d'), Foo('e')];
+    /// soa.rotate_left(2);
+    /// assert_eq!(soa, soa![Foo('c'), Foo('d'), Foo('e'), Foo('a'), Foo('b')]);
+    /// ```
+    pub fn rotate_left(&mut self, mid: usize) {
+        assert!(mid <= self.len(), "index out of bounds");
+        let len = self.len();
+        let raw = self.raw();
+        unsafe {
+            Self::reverse_raw(raw, mid);
+            Self::reverse_raw(raw.offset(mid), len - mid);
+            Self::reverse_raw(raw, len);
+        }
+    }
+
+    /// Rotates the slice in-place such that the first `self.len() - k`
+    /// elements of the slice move to the end while the last `k` elements move
+    /// to the front.
+    ///
+    /// After calling `rotate_right`, the element previously at index
+    /// `self.len() - k` will become the first element in the slice.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if `k` is greater than the length of the
+    /// slice. Note that `k == self.len()` does not panic and is a no-op
+    /// rotation.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use soa_rs::{Soa, Soars, soa};
+    /// # #[derive(Soars, Debug, PartialEq)]
+    /// # #[soa_derive(Debug, PartialEq)]
+    /// # struct Foo(char);
+    /// let mut soa = soa![Foo('a'), Foo('b'), Foo('c'), Foo('d'), Foo('e')];
+    /// soa.rotate_right(2);
+    /// assert_eq!(soa, soa![Foo('d'), Foo('e'), Foo('a'), Foo('b'), Foo('c')]);
+    /// ```
+    pub fn rotate_right(&mut self, k: usize) {
+        assert!(k <= self.len(), "index out of bounds");
+        self.rotate_left(self.len() - k);
+    }
+
+    /// Reverses the order of elements in the slice, in place.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use soa_rs::{Soa, Soars, soa};
+    /// # #[derive(Soars, Debug, PartialEq)]
+    /// # #[soa_derive(Debug, PartialEq)]
+    /// # struct Foo(char);
+    /// let mut soa = soa![Foo('a'), Foo('b'), Foo('c')];
+    /// soa.reverse();
+    /// assert_eq!(soa, soa![Foo('c'), Foo('b'), Foo('a')]);
+    /// ```
+    pub fn reverse(&mut self) {
+        unsafe { Self::reverse_raw(self.raw(), self.len()) };
+    }
+
+    /// Reverses the order of the `len` elements starting at `raw`.
+    ///
+    /// # Safety
+    ///
+    /// `raw` must be valid for `len` elements.
+    unsafe fn reverse_raw(raw: T::Raw, len: usize) {
+        for i in 0..len / 2 {
+            let a = raw.offset(i);
+            let b = raw.offset(len - 1 - i);
+            let tmp = a.get();
+            b.copy_to(a, 1);
+            b.set(tmp);
+        }
+    }
+
+    /// Moves the element at `indices[i]` to position `i` for every `i`,
+    /// following each cycle of the permutation so that every element is moved
+    /// once. On return, `indices[i] == i`.
+    ///
+    /// # Safety
+    ///
+    /// `indices` must be a permutation of `0..self.len()`.
+    unsafe fn permute(&mut self, indices: &mut [usize]) {
+        debug_assert_eq!(indices.len(), self.len());
+        let raw = self.raw();
+        for start in 0..indices.len() {
+            if indices[start] == start {
+                continue;
+            }
+
+            let tmp = raw.offset(start).get();
+            let mut dst = start;
+            loop {
+                let src = indices[dst];
+                indices[dst] = dst;
+                if src == start {
+                    raw.offset(dst).set(tmp);
+                    break;
+                }
+                raw.offset(src).copy_to(raw.offset(dst), 1);
+                dst = src;
+            }
+        }
+    }
+
+    /// Lexicographically compares the elements of this slice with another
+    /// using the given comparison function.
+    ///
+    /// Elements are compared pairwise until one comparison is not
+    /// [`Ordering::Equal`]. If one slice is a prefix of the other, the shorter
+    /// slice is less.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use soa_rs::{Soa, Soars, soa};
+    /// # use std::cmp::Ordering;
+    /// # #[derive(Soars, Debug, PartialEq)]
+    /// # #[soa_derive(Debug, PartialEq)]
+    /// # struct Foo(usize);
+    /// let a = soa![Foo(1), Foo(2)];
+    /// let b = soa![Foo(1), Foo(2), Foo(0)];
+    /// assert_eq!(a.cmp_by(&b, |a, b| a.0.cmp(b.0)), Ordering::Less);
+    /// assert_eq!(b.cmp_by(&a, |a, b| a.0.cmp(b.0)), Ordering::Greater);
+    /// assert_eq!(a.cmp_by(&b, |a, b| b.0.cmp(a.0)), Ordering::Less);
+    /// ```
+    pub fn cmp_by<F>(&self, other: &Self, mut f: F) -> Ordering
+    where
+        F: for<'a> FnMut(T::Ref<'a>, T::Ref<'a>) -> Ordering,
+    {
+        for (a, b) in self.iter().zip(other.iter()) {
+            match f(a, b) {
+                Ordering::Equal => {}
+                ord => return ord,
+            }
+        }
+        self.len().cmp(&other.len())
+    }
+
+    /// Lexicographically compares the elements of this slice with another
+    /// using the given partial comparison function.
+    ///
+    /// This is the same as [`Slice::cmp_by`], except that comparison stops
+    /// with [`None`] if two elements are not comparable.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use soa_rs::{Soa, Soars, soa};
+    /// # use std::cmp::Ordering;
+    /// # #[derive(Soars, Debug, PartialEq)]
+    /// # #[soa_derive(Debug, PartialEq)]
+    /// # struct Foo(f32);
+    /// let a = soa![Foo(1.0), Foo(f32::NAN)];
+    /// let b = soa![Foo(2.0), Foo(f32::NAN)];
+    /// assert_eq!(a.partial_cmp_by(&b, |a, b| a.0.partial_cmp(b.0)), Some(Ordering::Less));
+    /// assert_eq!(a.partial_cmp_by(&a, |a, b| a.0.partial_cmp(b.0)), None);
+    /// ```
+    pub fn partial_cmp_by<F>(&self, other: &Self, mut f: F) -> Option<Ordering>
+    where
+        F: for<'a> FnMut(T::Ref<'a>, T::Ref<'a>) -> Option<Ordering>,
+    {
+        for (a, b) in self.iter().zip(other.iter()) {
+            match f(a, b) {
+                Some(Ordering::Equal) => {}
+                ord => return ord,
+            }
+        }
+        Some(self.len().cmp(&other.len()))
+    }
+
+    /// Determines whether this slice is equal to another using the given
+    /// equality function.
+    ///
+    /// The slices are equal if they have the same length and each pair of
+    /// elements is equal.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use soa_rs::{Soa, Soars, soa};
+    /// # #[derive(Soars, Debug, PartialEq)]
+    /// # #[soa_derive(Debug, PartialEq)]
+    /// # struct Foo(usize);
+    /// let a = soa![Foo(1), Foo(2)];
+    /// let b = soa![Foo(11), Foo(12)];
+    /// assert!(a.eq_by(&b, |a, b| a.0 % 10 == b.0 % 10));
+    /// assert!(!a.eq_by(&b, |a, b| a.0 == b.0));
+    /// ```
+    pub fn eq_by<F>(&self, other: &Self, mut f: F) -> bool
+    where
+        F: for<'a> FnMut(T::Ref<'a>, T::Ref<'a>) -> bool,
+    {
+        self.len() == other.len() && self.iter().zip(other.iter()).all(|(a, b)| f(a, b))
+    }
+
+    /// Partitions the elements by key, copying each element into the [`Soa`]
+    /// for its key.
+    ///
+    /// Within each group, elements keep their relative order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use soa_rs::{Soa, Soars, soa};
+    /// # #[derive(Soars, Debug, PartialEq, Clone, Copy)]
+    /// # #[soa_derive(Debug, PartialEq)]
+    /// # struct Foo(usize);
+    /// let soa = soa![Foo(1), Foo(2), Foo(3), Foo(4), Foo(5)];
+    /// let groups = soa.group_by_key(|foo| *foo.0 % 2 == 0);
+    /// assert_eq!(groups[&true], soa![Foo(2), Foo(4)]);
+    /// assert_eq!(groups[&false], soa![Foo(1), Foo(3), Foo(5)]);
+    /// ```
+    pub fn group_by_key<K, F>(&self, mut f: F) -> HashMap<K, Soa<T>>
+    where
+        T: Copy,
+        K: Eq + Hash,
+        F: FnMut(T::Ref<'_>) -> K,
+    {
+        let mut out: HashMap<K, Soa<T>> = HashMap::new();
+        for i in 0..self.len() {
+            let raw = unsafe { self.raw().offset(i) };
+            let key = f(unsafe { raw.get_ref() });
+            // SAFETY: T is Copy, so a bitwise copy leaves the original intact.
+            out.entry(key).or_default().push(unsafe { raw.get() });
+        }
+        out
+    }
+
+    /// Returns the name and raw bytes of the array for each field, in
+    /// declaration order.
+    ///
+    /// This requires the `bytemuck` feature and the `#[soa_pod]` attribute. The
+    /// result can be turned back into a [`Soa`] with
+    /// [`Soa::from_column_bytes`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use soa_rs::{Soa, Soars, soa};
+    /// #[derive(Soars, Debug, PartialEq)]
+    /// #[soa_derive(Debug, PartialEq)]
+    /// #[soa_pod]
+    /// struct Foo {
+    ///     foo: u8,
+    ///     bar: u16,
+    /// }
+    ///
+    /// let soa = soa![Foo { foo: 1, bar: 2 }, Foo { foo: 3, bar: 4 }];
+    /// let columns = soa.as_column_bytes();
+    /// assert_eq!(columns[0], ("foo", [1, 3].as_slice()));
+    /// assert_eq!(columns[1].0, "bar");
+    /// assert_eq!(columns[1].1.len(), 4);
+    /// ```
+    #[cfg(feature = "bytemuck")]
+    pub fn as_column_bytes(&self) -> Vec<(&'static str, &[u8])>
+    where
+        T: crate::SoaPod,
+    {
+        T::pod_columns(self.raw())
+            .into_iter()
+            .map(|column| {
+                let bytes =
+                    unsafe { std::slice::from_raw_parts(column.ptr, column.size * self.len()) };
+                (column.name, bytes)
+            })
+            .collect()
+    }
+
+    /// Hashes the contents of the slice column by column.
+    ///
+    /// Unlike the [`Hash`] implementation, which hashes each element in turn,
+    /// this hashes the slice for each field in declaration order via
+    /// [`Soars::Slices`]. The result depends only on the elements, so it is the
+    /// same regardless of the capacity of a [`Soa`] or how it was built. It
+    /// requires [`Hash`] to be derived for `FooSlices`, for example with
+    /// `#[soa_derive(Hash)]`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use soa_rs::{Soa, Soars, soa};
+    /// # use std::hash::{DefaultHasher, Hasher};
+    /// # #[derive(Soars, Debug, PartialEq)]
+    /// # #[soa_derive(Debug, PartialEq, Hash)]
+    /// # struct Foo(usize);
+    /// let a = soa![Foo(1), Foo(2)];
+    /// let mut b = Soa::<Foo>::with_capacity(100);
+    /// b.extend([Foo(1), Foo(2)]);
+    ///
+    /// let mut hasher_a = DefaultHasher::new();
+    /// a.content_hash(&mut hasher_a);
+    /// let mut hasher_b = DefaultHasher::new();
+    /// b.content_hash(&mut hasher_b);
+    /// assert_eq!(hasher_a.finish(), hasher_b.finish());
+    /// ```
+    pub fn content_hash<H>(&self, state: &mut H)
+    where
+        H: Hasher,
+        for<'a> T::Slices<'a>: Hash,
+    {
+        self.slices().hash(state);
+    }
+
+    /// Returns an iterator that creates an owned copy of each element.
+    ///
+    /// This is equivalent to `iter().map(T::from_soa_ref)` and is useful for
+    /// passing the contents of a slice to APIs that expect values in the
+    /// ordinary array-of-structs layout.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use soa_rs::{Soa, Soars, soa};
+    /// # #[derive(Soars, Debug, PartialEq)]
+    /// # #[soa_derive(Debug, PartialEq)]
+    /// # struct Foo(String);
+    /// let soa = soa![Foo("a".into()), Foo("b".into())];
+    /// let vec: Vec<Foo> = soa.aos_iter().collect();
+    /// assert_eq!(vec, [Foo("a".into()), Foo("b".into())]);
+    /// assert_eq!(soa.aos_iter().next_back(), Some(Foo("b".into())));
+    /// ```
+    pub fn aos_iter(&self) -> impl DoubleEndedIterator<Item = T> + ExactSizeIterator + '_
+    where
+        T: FromSoaRef,
+    {
+        self.iter().map(T::from_soa_ref)
+    }
+
+    /// Copies the elements into a new [`Vec`].
+    ///
+    /// Unlike `From<Soa<T>> for Vec<T>`, this works on borrowed slices and
+    /// leaves the original elements in place.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use soa_rs::{Soa, Soars, soa};
+    /// # #[derive(Soars, Debug, PartialEq)]
+    /// # #[soa_derive(Debug, PartialEq)]
+    /// # struct Foo(String);
+    /// let soa = soa![Foo("a".into()), Foo("b".into()), Foo("c".into())];
+    /// assert_eq!(soa.to_vec(), [Foo("a".into()), Foo("b".into()), Foo("c".into())]);
+    /// assert_eq!(soa.idx(1..).to_vec(), [Foo("b".into()), Foo("c".into())]);
+    /// ```
+    pub fn to_vec(&self) -> Vec<T>
+    where
+        T: FromSoaRef,
+    {
+        self.aos_iter().collect()
+    }
+
+    /// Returns an iterator over the elements, each paired with the whole of
+    /// one field's slice.
+    ///
+    /// `field` selects the slice, usually with one of the generated getters.
+    /// This is useful when each element needs to refer to a whole column, such
+    /// as to normalize a field by its maximum.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use soa_rs::{Soa, Soars, soa};
+    /// # #[derive(Soars, Debug, PartialEq)]
+    /// # #[soa_derive(Debug, PartialEq)]
+    /// # struct Foo(u32);
+    /// let soa = soa![Foo(1), Foo(2), Foo(3)];
+    /// let shares: Vec<_> = soa
+    ///     .iter_with_field(|slice| slice.f0())
+    ///     .map(|(foo, all)| *foo.0 as f32 / all.iter().sum::<u32>() as f32)
+    ///     .collect();
+    /// assert_eq!(shares, [1.0 / 6.0, 2.0 / 6.0, 3.0 / 6.0]);
+    /// ```
+    pub fn iter_with_field<'a, U, F>(
+        &'a self,
+        field: F,
+    ) -> impl DoubleEndedIterator<Item = (T::Ref<'a>, &'a [U])> + ExactSizeIterator + 'a
+    where
+        F: FnOnce(&'a Self) -> &'a [U],
+        U: 'a,
+    {
+        let column = field(self);
+        self.iter().map(move |item| (item, column))
+    }
+
+    /// Returns an iterator over every `step`-th element, starting with the
+    /// first.
+    ///
+    /// This is equivalent to `iter().step_by(step)`, but each element is
+    /// reached by offsetting the field pointers directly instead of skipping
+    /// over the elements in between.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `step` is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use soa_rs::{Soa, Soars, soa};
+    /// # #[derive(Soars, Debug, PartialEq)]
+    /// # #[soa_derive(Debug, PartialEq)]
+    /// # struct Foo(u8);
+    /// let soa = soa![Foo(0), Foo(1), Foo(2), Foo(3), Foo(4)];
+    /// let mut iter = soa.stride(2);
+    /// assert_eq!(iter.next(), Some(FooRef(&0)));
+    /// assert_eq!(iter.next_back(), Some(FooRef(&4)));
+    /// assert_eq!(iter.next(), Some(FooRef(&2)));
+    /// assert_eq!(iter.next(), None);
+    /// ```
+    pub fn stride(
+        &self,
+        step: usize,
+    ) -> impl DoubleEndedIterator<Item = T::Ref<'_>> + ExactSizeIterator + '_ {
+        assert_ne!(step, 0, "step must be nonzero");
+        let raw = self.raw();
+        (0..self.len().div_ceil(step)).map(move |i| {
+            // SAFETY: i * step < len for every i in the range
+            unsafe { raw.offset(i * step).get_ref() }
+        })
+    }
+
+    /// Calls `f` with a reference to each element as an ordinary `T`.
+    ///
+    /// This is for code that needs `&T`, such as to call inherent methods of
+    /// `T`, rather than [`Soars::Ref`]. Since the fields of an element are not
+    /// stored together, each element is moved out of the slice onto the stack
+    /// for the duration of the call and moved back afterwards, even if `f`
+    /// panics. No reference into the slice itself is created, so the fields
+    /// are never retagged as part of a `T` they do not belong to.
+    ///
+    /// This takes `&mut self` because of that round trip. Through interior
+    /// mutability, `f` can change the temporary `T` even though it only has a
+    /// shared reference. With shared access to the slice, those changes could
+    /// not be written back, and something like taking a [`Box`] out of a
+    /// [`Cell`](std::cell::Cell) would leave the original to be dropped twice.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use soa_rs::{Soa, Soars, soa};
+    /// # #[derive(Soars, Debug, PartialEq)]
+    /// # #[soa_derive(Debug, PartialEq)]
+    /// struct Foo(u32, u32);
+    ///
+    /// impl Foo {
+    ///     fn sum(&self) -> u32 {
+    ///         self.0 + self.1
+    ///     }
+    /// }
+    ///
+    /// let mut soa = soa![Foo(1, 2), Foo(3, 4)];
+    /// let mut total = 0;
+    /// soa.for_each(|foo| total += foo.sum());
+    /// assert_eq!(total, 10);
+    /// ```
+    pub fn for_each<F>(&mut self, mut f: F)
+    where
+        F: FnMut(&T),
+    {
+        let Ok(()) = self.try_fold((), |(), item| {
+            f(item);
+            Ok::<_, Infallible>(())
+        });
+    }
+
+    /// Folds the elements with a fallible function, stopping at the first
+    /// error.
+    ///
+    /// Like [`Slice::for_each`], `f` receives each element as `&T`. See there
+    /// for why this takes `&mut self`.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first error returned by `f`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use soa_rs::{Soa, Soars, soa};
+    /// # #[derive(Soars, Debug, PartialEq)]
+    /// # #[soa_derive(Debug, PartialEq)]
+    /// # struct Foo(u8);
+    /// let mut soa = soa![Foo(100), Foo(100), Foo(100)];
+    /// let sum = soa.try_fold(0u8, |acc, foo| acc.checked_add(foo.0).ok_or(acc));
+    /// assert_eq!(sum, Err(200));
+    /// ```
+    pub fn try_fold<B, E, F>(&mut self, init: B, mut f: F) -> Result<B, E>
+    where
+        F: FnMut(B, &T) -> Result<B, E>,
+    {
+        /// Moves the element back into the slice when the call is done.
+        struct WriteBack<T>
+        where
+            T: Soars,
+        {
+            raw: T::Raw,
+            element: ManuallyDrop<T>,
+        }
+
+        impl<T> Drop for WriteBack<T>
+        where
+            T: Soars,
+        {
+            fn drop(&mut self) {
+                unsafe { self.raw.set(ManuallyDrop::take(&mut self.element)) };
+            }
+        }
+
+        let mut acc = init;
+        for i in 0..self.len() {
+            let raw = unsafe { self.raw().offset(i) };
+            let element = WriteBack::<T> {
+                raw,
+                element: ManuallyDrop::new(unsafe { raw.get() }),
+            };
+            acc = f(acc, &element.element)?;
+        }
+        Ok(acc)
+    }
+
+    /// Returns a collection of slices for each field of the slice.
+    ///
+    /// For convenience, slices can also be aquired using the getter methods for
+    /// individual fields.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use soa_rs::{Soa, Soars, soa};
+    /// # #[derive(Soars, Debug, PartialEq)]
+    /// # #[soa_derive(Debug, PartialEq)]
+    /// # struct Foo {
+    /// #     foo: u8,
+    /// #     bar: u8,
+    /// # }
+    /// let soa = soa![Foo { foo: 1, bar: 2 }, Foo { foo: 3, bar: 4 }];
+    /// let slices = soa.slices();
+    /// assert_eq!(slices.foo, soa.foo());
+    /// assert_eq!(slices.bar, soa.bar());
+    /// ```
+    pub fn slices(&self) -> T::Slices<'_> {
+        unsafe { self.raw.slices(self.len()) }
+    }
+
+    /// Returns a collection of mutable slices for each field of the slice.
+    ///
+    /// For convenience, individual mutable slices can also be aquired using the
+    /// getter methods for individual fields. This method is necessary to be
+    /// able to mutably borrow multiple SoA fields simultaneously.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use soa_rs::{Soa, Soars, soa};
+    /// # #[derive(Soars, Debug, PartialEq)]
+    /// # #[soa_derive(Debug, PartialEq)]
+    /// # struct Foo {
+    /// #     foo: u8,
+    /// #     bar: u8,
+    /// # }
+    /// let mut soa = soa![Foo { foo: 1, bar: 0 }, Foo { foo: 2, bar: 0 }];
+    /// let slices = soa.slices_mut();
+    /// for (foo, bar) in slices.foo.iter().zip(slices.bar) {
+    ///     *bar = foo * 2;
+    /// }
+    /// assert_eq!(soa.bar(), [2, 4]);
+    /// ```
+    pub fn slices_mut(&mut self) -> T::SlicesMut<'_> {
+        unsafe { self.raw.slices_mut(self.len()) }
+    }
+
+    /// Binary searches this slice with a comparator function.
+    ///
+    /// The comparator function should return an order code that indicates
+    /// whether its argument is `Less`, `Equal` or `Greater` the desired target.
+    /// If the slice is not sorted or if the comparator function does not
+    /// implement an order consistent with the sort order of the underlying
+    /// slice, the returned result is unspecified and meaningless.
+    ///
+    /// If the value is found then [`Result::Ok`] is returned, containing the
+    /// index of the matching element. If there are multiple matches, then any
+    /// one of the matches could be returned. If the value is not found then
+    /// [`Result::Err`] is returned, containing the index where a matching
+    /// element could be inserted while maintaining sorted order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use soa_rs::{Soa, Soars, soa};
+    /// # #[derive(Soars, Debug, PartialEq)]
+    /// # #[soa_derive(Debug, PartialEq)]
+    /// # struct Foo(usize);
+    /// let soa = soa![Foo(1), Foo(3), Foo(5), Foo(7)];
+    /// assert_eq!(soa.binary_search_by(|foo| foo.0.cmp(&5)), Ok(2));
+    /// assert_eq!(soa.binary_search_by(|foo| foo.0.cmp(&4)), Err(2));
+    /// assert_eq!(soa.binary_search_by(|foo| foo.0.cmp(&0)), Err(0));
+    /// assert_eq!(soa.binary_search_by(|foo| foo.0.cmp(&8)), Err(4));
+    /// ```
+    pub fn binary_search_by<F>(&self, mut f: F) -> Result<usize, usize>
+    where
+        F: FnMut(T::Ref<'_>) -> Ordering,
+    {
+        let mut left = 0;
+        let mut right = self.len();
+        while left < right {
+            let mid = left + (right - left) / 2;
+            match f(unsafe { self.raw().offset(mid).get_ref() }) {
+                Ordering::Less => left = mid + 1,
+                Ordering::Greater => right = mid,
+                Ordering::Equal => return Ok(mid),
+            }
+        }
+        Err(left)
+    }
+
+    /// Binary searches this slice with a key extraction function.
+    ///
+    /// Assumes that the slice is sorted by the key. See
+    /// [`binary_search_by`] for details about the return value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use soa_rs::{Soa, Soars, soa};
+    /// # #[derive(Soars, Debug, PartialEq)]
+    /// # #[soa_derive(Debug, PartialEq)]
+    /// # struct Foo {
+    /// #     key: u8,
+    /// #     value: char,
+    /// # }
+    /// let soa = soa![
+    ///     Foo { key: 1, value: 'a' },
+    ///     Foo { key: 2, value: 'b' },
+    ///     Foo { key: 4, value: 'c' },
+    /// ];
+    /// assert_eq!(soa.binary_search_by_key(&2, |foo| *foo.key), Ok(1));
+    /// assert_eq!(soa.binary_search_by_key(&3, |foo| *foo.key), Err(2));
+    /// ```
+    ///
+    /// [`binary_search_by`]: Slice::binary_search_by
+    pub fn binary_search_by_key<B, F>(&self, b: &B, mut f: F) -> Result<usize, usize>
+    where
+        F: FnMut(T::Ref<'_>) -> B,
+        B: Ord,
+    {
+        self.binary_search_by(|item| f(item).cmp(b))
+    }
+
+    /// Returns the index of the partition point according to the given
+    /// predicate, which is the index of the first element for which it returns
+    /// false.
+    ///
+    /// The slice is assumed to be partitioned according to the predicate,
+    /// meaning that all elements for which it returns true are at the start
+    /// and all elements for which it returns false are at the end. If this is
+    /// not the case, the result is unspecified and meaningless.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use soa_rs::{Soa, Soars, soa};
+    /// # #[derive(Soars, Debug, PartialEq)]
+    /// # #[soa_derive(Debug, PartialEq)]
+    /// # struct Foo(usize);
+    /// let soa = soa![Foo(1), Foo(2), Foo(3), Foo(5), Foo(6)];
+    /// assert_eq!(soa.partition_point(|foo| *foo.0 < 4), 3);
+    /// ```
+    pub fn partition_point<F>(&self, mut pred: F) -> usize
+    where
+        F: FnMut(T::Ref<'_>) -> bool,
+    {
+        self.binary_search_by(|item| {
+            if pred(item) {
+                Ordering::Less
+            } else {
+                Ordering::Greater
+            }
+        })
+        .unwrap_or_else(|i| i)
+    }
+
+    /// Checks if the elements of this slice are sorted using the given
+    /// comparator function.
+    ///
+    /// `compare` is called with each pair of adjacent elements and should
+    /// return `true` if they are in order. Iteration stops at the first pair
+    /// that is not. This is a cheap way to check the precondition of
+    /// [`binary_search_by`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use soa_rs::{Soa, Soars, soa};
+    /// # #[derive(Soars, Debug, PartialEq)]
+    /// # #[soa_derive(Debug, PartialEq)]
+    /// # struct Foo(usize);
+    /// let soa = soa![Foo(1), Foo(3), Foo(3), Foo(7)];
+    /// assert!(soa.is_sorted_by(|a, b| a.0 <= b.0));
+    /// assert!(!soa.is_sorted_by(|a, b| a.0 < b.0));
+    /// ```
+    ///
+    /// [`binary_search_by`]: Slice::binary_search_by
+    pub fn is_sorted_by<F>(&self, mut compare: F) -> bool
+    where
+        F: for<'a> FnMut(T::Ref<'a>, T::Ref<'a>) -> bool,
+    {
+        let mut iter = self.iter();
+        let Some(mut prev) = iter.next() else {
+            return true;
+        };
+        for next in iter {
+            if !compare(prev, next) {
+                return false;
+            }
+            prev = next;
+        }
+        true
+    }
+
+    /// Checks if the elements of this slice are sorted.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use soa_rs::{Soa, Soars, soa};
+    /// # #[derive(Soars, Debug, PartialEq)]
+    /// # #[soa_derive(Debug, PartialEq, PartialOrd)]
+    /// # struct Foo(usize);
+    /// assert!(soa![Foo(1), Foo(2), Foo(2)].is_sorted());
+    /// assert!(!soa![Foo(2), Foo(1)].is_sorted());
+    /// ```
+    pub fn is_sorted(&self) -> bool
+    where
+        for<'a> T::Ref<'a>: PartialOrd,
+    {
+        self.is_sorted_by(|a, b| a <= b)
+    }
+
+    /// Converts from an unsized variant to sized variant
+    ///
+    /// # Safety
+    ///
+    /// Since this returns an owned value, it implicitly extends the lifetime &
+    /// in an unbounded way. The caller must ensure proper lifetimes with, for
+    /// example, [`PhantomData`].
     ///
     /// [`PhantomData`]: std::marker::PhantomData
     pub(crate) const unsafe fn as_sized(&self) -> Slice<T, ()> {
@@ -597,8 +2255,7 @@ where
     for<'a> T::Ref<'a>: PartialEq,
 {
     fn eq(&self, other: &R) -> bool {
-        let other = other.as_slice();
-        self.len() == other.len() && self.iter().zip(other.iter()).all(|(me, them)| me == them)
+        self.eq_by(&other.as_slice(), |a, b| a == b)
     }
 }
 
@@ -609,6 +2266,36 @@ where
 {
 }
 
+impl<T> PartialEq<[T]> for Slice<T>
+where
+    T: Soars,
+    for<'a> T::Ref<'a>: PartialEq,
+{
+    fn eq(&self, other: &[T]) -> bool {
+        self.len() == other.len() && self.iter().zip(other).all(|(a, b)| a == b.as_soa_ref())
+    }
+}
+
+impl<T, const N: usize> PartialEq<[T; N]> for Slice<T>
+where
+    T: Soars,
+    for<'a> T::Ref<'a>: PartialEq,
+{
+    fn eq(&self, other: &[T; N]) -> bool {
+        *self == other[..]
+    }
+}
+
+impl<T> PartialEq<Vec<T>> for Slice<T>
+where
+    T: Soars,
+    for<'a> T::Ref<'a>: PartialEq,
+{
+    fn eq(&self, other: &Vec<T>) -> bool {
+        *self == other[..]
+    }
+}
+
 impl<T> Debug for Slice<T>
 where
     T: Soars,
@@ -629,16 +2316,7 @@ where
     for<'a> T::Ref<'a>: PartialOrd,
 {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        match self
-            .iter()
-            .zip(other.iter())
-            .try_fold(Ordering::Equal, |_, (a, b)| match a.partial_cmp(&b) {
-                ord @ (None | Some(Ordering::Less | Ordering::Greater)) => ControlFlow::Break(ord),
-                Some(Ordering::Equal) => ControlFlow::Continue(self.len().cmp(&other.len())),
-            }) {
-            ControlFlow::Continue(ord) => Some(ord),
-            ControlFlow::Break(ord) => ord,
-        }
+        self.partial_cmp_by(other, |a, b| a.partial_cmp(&b))
     }
 }
 
@@ -648,15 +2326,43 @@ where
     for<'a> T::Ref<'a>: Ord,
 {
     fn cmp(&self, other: &Self) -> Ordering {
-        match self
-            .iter()
-            .zip(other.iter())
-            .try_fold(Ordering::Equal, |_, (a, b)| match a.cmp(&b) {
-                ord @ (Ordering::Greater | Ordering::Less) => ControlFlow::Break(ord),
-                Ordering::Equal => ControlFlow::Continue(self.len().cmp(&other.len())),
-            }) {
-            ControlFlow::Continue(ord) | ControlFlow::Break(ord) => ord,
+        self.cmp_by(other, |a, b| a.cmp(&b))
+    }
+}
+
+impl<T> PartialOrd<[T]> for Slice<T>
+where
+    T: Soars,
+    for<'a> T::Ref<'a>: PartialOrd,
+{
+    fn partial_cmp(&self, other: &[T]) -> Option<Ordering> {
+        for (a, b) in self.iter().zip(other) {
+            match a.partial_cmp(&b.as_soa_ref()) {
+                Some(Ordering::Equal) => {}
+                ord => return ord,
+            }
         }
+        Some(self.len().cmp(&other.len()))
+    }
+}
+
+impl<T, const N: usize> PartialOrd<[T; N]> for Slice<T>
+where
+    T: Soars,
+    for<'a> T::Ref<'a>: PartialOrd,
+{
+    fn partial_cmp(&self, other: &[T; N]) -> Option<Ordering> {
+        self.partial_cmp(&other[..])
+    }
+}
+
+impl<T> PartialOrd<Vec<T>> for Slice<T>
+where
+    T: Soars,
+    for<'a> T::Ref<'a>: PartialOrd,
+{
+    fn partial_cmp(&self, other: &Vec<T>) -> Option<Ordering> {
+        self.partial_cmp(&other[..])
     }
 }
 
@@ -3,8 +3,9 @@ use std::{
     cmp::Ordering,
     fmt::{self, Debug, Formatter},
     hash::{Hash, Hasher},
+    iter::Zip,
     marker::PhantomData,
-    ops::Deref,
+    ops::{Deref, Range},
 };
 
 /// An immutably borrowed [`Slice`].
@@ -41,6 +42,31 @@ where
             marker: PhantomData,
         }
     }
+
+    /// Returns an iterator over the elements paired with their index, where
+    /// indices count up from `base`.
+    ///
+    /// A [`SliceRef`] does not remember where it was sliced from, so
+    /// [`Iterator::enumerate`] yields indices relative to the start of the
+    /// subslice. When the subslice was taken at some offset into a larger
+    /// slice, pass that offset as `base` to get indices into the original.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use soa_rs::{Soa, Soars, soa};
+    /// # #[derive(Soars, Debug, PartialEq)]
+    /// # #[soa_derive(Debug, PartialEq)]
+    /// # struct Foo(usize);
+    /// let soa = soa![Foo(10), Foo(20), Foo(30), Foo(40)];
+    /// let mut iter = soa.idx(2..).enumerate_from(2);
+    /// assert_eq!(iter.next(), Some((2, FooRef(&30))));
+    /// assert_eq!(iter.next(), Some((3, FooRef(&40))));
+    /// assert_eq!(iter.next(), None);
+    /// ```
+    pub fn enumerate_from(self, base: usize) -> Zip<Range<usize>, Iter<'a, T>> {
+        (base..base + self.len).zip(self)
+    }
 }
 
 impl<'a, T> Clone for SliceRef<'a, T>
@@ -0,0 +1,421 @@
+use crate::{AsMutSlice, AsSlice, Iter, IterMut, Slice, SliceMut, SliceRef, Soa, SoaRaw, Soars};
+use std::{
+    cell::UnsafeCell,
+    fmt::{self, Debug, Formatter},
+    ops::{Deref, DerefMut},
+};
+
+/// Fixed-capacity storage for the fields of up to `N` elements, kept inline
+/// rather than on the heap.
+///
+/// This is an implementation detail of [`SmallSoa`] and is implemented by the
+/// [`Soars`] derive macro.
+///
+/// # Safety
+///
+/// The [`SoaRaw`] returned from [`raw`] and [`raw_mut`] must point to arrays
+/// with room for `N` elements of each field, stored within `self`.
+///
+/// [`raw`]: SoaInline::raw
+/// [`raw_mut`]: SoaInline::raw_mut
+/// [`Soars`]: crate::Soars
+#[doc(hidden)]
+pub unsafe trait SoaInline {
+    /// The type of element the storage will contain.
+    type Item: Soars;
+
+    /// Creates storage with all elements uninitialized.
+    fn uninit() -> Self;
+
+    /// Gets a [`SoaRaw`] for the storage, valid for reads.
+    fn raw(&self) -> <Self::Item as Soars>::Raw;
+
+    /// Gets a [`SoaRaw`] for the storage, valid for reads and writes.
+    fn raw_mut(&mut self) -> <Self::Item as Soars>::Raw;
+}
+
+/// A [`Soa`] that stores up to `N` elements inline before spilling to the
+/// heap.
+///
+/// Each field gets its own fixed-size array inside the `SmallSoa`, so no
+/// allocation happens until more than `N` elements are pushed. At that point
+/// the elements are moved to a heap-allocated [`Soa`], where they stay even
+/// if the length later drops back below `N`. This is useful when there are
+/// many small SoAs, where the cost of an allocation per container would
+/// dominate.
+///
+/// Like [`Soa`], `SmallSoa` dereferences to [`Slice`]. While the elements are
+/// inline, the field pointers behind the [`Slice`] are kept inside the
+/// container and brought up to date after it has been moved. Because a shared
+/// dereference may have to update them, `SmallSoa` is not [`Sync`].
+///
+/// The inline arrays use the natural alignment of each field. The `align`
+/// attributes only take effect once the elements have spilled to the heap, so
+/// a [`Slice`] borrowed from a `SmallSoa` that has not spilled may be less
+/// aligned than one borrowed from a [`Soa`].
+///
+/// # Examples
+///
+/// ```
+/// # use soa_rs::{Soars, SmallSoa, AsSlice, AsMutSlice, soa};
+/// # #[derive(Soars, Debug, PartialEq)]
+/// # #[soa_derive(Debug, PartialEq)]
+/// # struct Foo(u8);
+/// let mut small = SmallSoa::<Foo, 2>::new();
+/// small.push(Foo(1));
+/// small.push(Foo(2));
+/// assert!(!small.spilled());
+/// small.push(Foo(3));
+/// assert!(small.spilled());
+/// small.f0_mut()[0] = 10;
+/// assert_eq!(small.f0(), [10, 2, 3]);
+/// ```
+pub struct SmallSoa<T, const N: usize>
+where
+    T: Soars,
+{
+    data: Data<T, N>,
+}
+
+enum Data<T, const N: usize>
+where
+    T: Soars,
+{
+    Inline {
+        storage: T::Inline<N>,
+        len: usize,
+        /// The slice to dereference to, along with the address of `storage`
+        /// that it was made for. A zero address means it needs refreshing.
+        /// Mutably borrowing `storage` invalidates the pointers in the slice,
+        /// so every mutable access has to reset the address.
+        slice: UnsafeCell<(usize, Slice<T, ()>)>,
+    },
+    Heap(Soa<T>),
+}
+
+impl<T, const N: usize> SmallSoa<T, N>
+where
+    T: Soars,
+{
+    /// Constructs a new, empty `SmallSoa<T, N>` using inline storage.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use soa_rs::{Soars, SmallSoa};
+    /// # #[derive(Soars)]
+    /// # #[soa_derive(Debug, PartialEq)]
+    /// # struct Foo(u8);
+    /// let small = SmallSoa::<Foo, 4>::new();
+    /// assert_eq!(small.capacity(), 4);
+    /// ```
+    pub fn new() -> Self {
+        let storage = <T::Inline<N> as SoaInline>::uninit();
+        let slice = UnsafeCell::new((0, Slice::with_raw(storage.raw())));
+        Self {
+            data: Data::Inline {
+                storage,
+                len: 0,
+                slice,
+            },
+        }
+    }
+
+    /// Returns the number of elements in the container.
+    pub fn len(&self) -> usize {
+        match &self.data {
+            Data::Inline { len, .. } => *len,
+            Data::Heap(soa) => soa.len(),
+        }
+    }
+
+    /// Returns true if the container contains no elements.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the number of elements the container can hold without
+    /// reallocating. This is `N` until the elements have been spilled to the
+    /// heap.
+    pub fn capacity(&self) -> usize {
+        match &self.data {
+            Data::Inline { .. } => N,
+            Data::Heap(soa) => soa.capacity(),
+        }
+    }
+
+    /// Returns true if the elements have been moved to a heap allocation.
+    pub fn spilled(&self) -> bool {
+        matches!(self.data, Data::Heap(_))
+    }
+
+    /// Appends an element to the back of the collection, spilling to the heap
+    /// if the inline capacity is exceeded.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use soa_rs::{Soars, SmallSoa, AsSlice, soa};
+    /// # #[derive(Soars, Debug, PartialEq)]
+    /// # #[soa_derive(Debug, PartialEq)]
+    /// # struct Foo(u8);
+    /// let mut small = SmallSoa::<Foo, 1>::new();
+    /// small.push(Foo(1));
+    /// small.push(Foo(2));
+    /// assert_eq!(small, soa![Foo(1), Foo(2)]);
+    /// ```
+    pub fn push(&mut self, element: T) {
+        if let Data::Inline {
+            storage,
+            len,
+            slice,
+        } = &mut self.data
+        {
+            slice.get_mut().0 = 0;
+            if *len < N {
+                unsafe { storage.raw_mut().offset(*len).set(element) };
+                *len += 1;
+                return;
+            }
+
+            let mut soa = Soa::<T>::with_capacity(N * 2);
+            let raw = storage.raw_mut();
+            for i in 0..*len {
+                soa.push(unsafe { raw.offset(i).get() });
+            }
+            *len = 0;
+            self.data = Data::Heap(soa);
+        }
+
+        if let Data::Heap(soa) = &mut self.data {
+            soa.push(element);
+        }
+    }
+
+    /// Removes the last element and returns it, or [`None`] if empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use soa_rs::{Soars, SmallSoa};
+    /// # #[derive(Soars, Debug, PartialEq)]
+    /// # #[soa_derive(Debug, PartialEq)]
+    /// # struct Foo(u8);
+    /// let mut small = SmallSoa::<Foo, 2>::new();
+    /// small.push(Foo(1));
+    /// assert_eq!(small.pop(), Some(Foo(1)));
+    /// assert_eq!(small.pop(), None);
+    /// ```
+    pub fn pop(&mut self) -> Option<T> {
+        match &mut self.data {
+            Data::Inline {
+                storage,
+                len,
+                slice,
+            } => {
+                slice.get_mut().0 = 0;
+                if *len == 0 {
+                    None
+                } else {
+                    *len -= 1;
+                    Some(unsafe { storage.raw_mut().offset(*len).get() })
+                }
+            }
+            Data::Heap(soa) => soa.pop(),
+        }
+    }
+
+    /// Clears the container, removing all values.
+    ///
+    /// This does not move spilled elements back into inline storage.
+    pub fn clear(&mut self) {
+        while self.pop().is_some() {}
+    }
+
+    /// Returns an iterator over the elements.
+    pub fn iter(&self) -> Iter<'_, T> {
+        self.as_slice().into_iter()
+    }
+
+    /// Returns an iterator over the elements that allows modifying each value.
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+        self.as_mut_slice().into_iter()
+    }
+}
+
+impl<T, const N: usize> Drop for SmallSoa<T, N>
+where
+    T: Soars,
+{
+    fn drop(&mut self) {
+        if let Data::Inline { .. } = self.data {
+            self.clear();
+        }
+    }
+}
+
+impl<T, const N: usize> Default for SmallSoa<T, N>
+where
+    T: Soars,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, const N: usize> Deref for SmallSoa<T, N>
+where
+    T: Soars,
+{
+    type Target = Slice<T>;
+
+    fn deref(&self) -> &Self::Target {
+        match &self.data {
+            Data::Inline {
+                storage,
+                len,
+                slice,
+            } => unsafe {
+                let slice = slice.get();
+                let address = storage as *const T::Inline<N> as usize;
+                // The slice only goes stale when the container has been moved
+                // or mutably borrowed since it was last refreshed, so no
+                // references to it can still be alive
+                if (*slice).0 != address {
+                    slice.write((address, Slice::with_raw(storage.raw())));
+                }
+                (*slice).1.as_unsized(*len)
+            },
+            Data::Heap(soa) => soa,
+        }
+    }
+}
+
+impl<T, const N: usize> DerefMut for SmallSoa<T, N>
+where
+    T: Soars,
+{
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        match &mut self.data {
+            Data::Inline {
+                storage,
+                len,
+                slice,
+            } => {
+                let slice = slice.get_mut();
+                *slice = (0, Slice::with_raw(storage.raw_mut()));
+                unsafe { slice.1.as_unsized_mut(*len) }
+            }
+            Data::Heap(soa) => soa,
+        }
+    }
+}
+
+impl<T, const N: usize> AsSlice for SmallSoa<T, N>
+where
+    T: Soars,
+{
+    type Item = T;
+
+    fn as_slice(&self) -> SliceRef<'_, Self::Item> {
+        match &self.data {
+            Data::Inline { storage, len, .. } => unsafe {
+                SliceRef::from_slice(Slice::with_raw(storage.raw()), *len)
+            },
+            Data::Heap(soa) => soa.as_slice(),
+        }
+    }
+}
+
+impl<T, const N: usize> AsMutSlice for SmallSoa<T, N>
+where
+    T: Soars,
+{
+    fn as_mut_slice(&mut self) -> SliceMut<'_, Self::Item> {
+        match &mut self.data {
+            Data::Inline {
+                storage,
+                len,
+                slice,
+            } => unsafe {
+                slice.get_mut().0 = 0;
+                SliceMut::from_slice(Slice::with_raw(storage.raw_mut()), *len)
+            },
+            Data::Heap(soa) => soa.as_mut_slice(),
+        }
+    }
+}
+
+impl<'a, T, const N: usize> IntoIterator for &'a SmallSoa<T, N>
+where
+    T: Soars,
+{
+    type Item = T::Ref<'a>;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, T, const N: usize> IntoIterator for &'a mut SmallSoa<T, N>
+where
+    T: Soars,
+{
+    type Item = T::RefMut<'a>;
+    type IntoIter = IterMut<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+impl<T, const N: usize> Extend<T> for SmallSoa<T, N>
+where
+    T: Soars,
+{
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for item in iter {
+            self.push(item);
+        }
+    }
+}
+
+impl<T, const N: usize> FromIterator<T> for SmallSoa<T, N>
+where
+    T: Soars,
+{
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut out = Self::new();
+        out.extend(iter);
+        out
+    }
+}
+
+impl<T, const N: usize> Debug for SmallSoa<T, N>
+where
+    T: Soars,
+    for<'a> T::Ref<'a>: Debug,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        self.as_slice().fmt(f)
+    }
+}
+
+impl<T, R, const N: usize> PartialEq<R> for SmallSoa<T, N>
+where
+    T: Soars,
+    R: AsSlice<Item = T> + ?Sized,
+    for<'a> T::Ref<'a>: PartialEq,
+{
+    fn eq(&self, other: &R) -> bool {
+        self.as_slice() == other.as_slice()
+    }
+}
+
+impl<T, const N: usize> Eq for SmallSoa<T, N>
+where
+    T: Soars,
+    for<'a> T::Ref<'a>: Eq,
+{
+}
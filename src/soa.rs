@@ -1,6 +1,7 @@
 use crate::{
-    iter_raw::IterRaw, AsMutSlice, AsSlice, IntoIter, Iter, IterMut, Slice, SliceMut, SliceRef,
-    SoaRaw, Soars,
+    index, iter_raw::IterRaw, AsMutSlice, AsSlice, AsSoaRef, CapacityReport, DrainAll, ExtractIf,
+    FromSoaRef, IntoIter, Iter, IterMut, Slice, SliceMut, SliceRef, SoaColumns, SoaRaw, Soars,
+    TryReserveError,
 };
 use std::{
     borrow::{Borrow, BorrowMut},
@@ -9,9 +10,12 @@ use std::{
     hash::{Hash, Hasher},
     marker::PhantomData,
     mem::{needs_drop, size_of, ManuallyDrop},
-    ops::{Deref, DerefMut},
+    ops::{Deref, DerefMut, Range, RangeBounds},
 };
 
+#[cfg(feature = "nightly")]
+use std::alloc::{Allocator, Global};
+
 /// A growable array type that stores the values for each field of `T`
 /// contiguously.
 ///
@@ -21,16 +25,32 @@ use std::{
 /// - Never deallocates memory unless explicitly requested
 /// - Uses `usize::MAX` as the capacity for zero-sized types
 ///
+/// Unlike [`Vec`], elements are dropped last-to-first when the container is
+/// dropped, cleared, or truncated. Add `#[soa(drop_order = "forward")]` to the
+/// struct to drop them first-to-last instead. Within each element, fields are
+/// dropped in declaration order as usual.
+///
+/// With the `nightly` feature, `Soa` takes a second parameter for the
+/// [`Allocator`] to use, which defaults to [`Global`]. Construct one with
+/// `Soa::new_in` or `Soa::with_capacity_in`. Other allocators support
+/// everything available on [`Slice`] and deallocate when the container is
+/// dropped. Every other method, including `push`, `reserve`, `clear`, and
+/// `into_iter`, is only available with `Global`.
+///
 /// See the top-level [`soa_rs`] docs for usage examples.
 ///
 /// [`soa_rs`]: crate
-pub struct Soa<T>
+/// [`Allocator`]: https://doc.rust-lang.org/std/alloc/trait.Allocator.html
+/// [`Global`]: https://doc.rust-lang.org/std/alloc/struct.Global.html
+pub struct Soa<T, #[cfg(feature = "nightly")] A: Allocator = Global>
 where
     T: Soars,
 {
     pub(crate) cap: usize,
     pub(crate) slice: Slice<T, ()>,
     pub(crate) len: usize,
+    #[cfg(feature = "nightly")]
+    pub(crate) alloc: A,
 }
 
 impl<T> Soa<T>
@@ -58,6 +78,52 @@ where
             cap: if size_of::<T>() == 0 { usize::MAX } else { 0 },
             slice: Slice::empty(),
             len: 0,
+            #[cfg(feature = "nightly")]
+            alloc: Global,
+        }
+    }
+
+    /// Appends an element to the back of a collection.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use soa_rs::{Soa, Soars, soa};
+    /// # #[derive(Soars, Debug, PartialEq)]
+    /// # #[soa_derive(Debug, PartialEq)]
+    /// # struct Foo(usize);
+    /// let mut soa = soa![Foo(1), Foo(2)];
+    /// soa.push(Foo(3));
+    /// assert_eq!(soa, soa![Foo(1), Foo(2), Foo(3)]);
+    /// ```
+    pub fn push(&mut self, element: T) {
+        self.maybe_grow();
+        unsafe {
+            self.raw().offset(self.len).set(element);
+        }
+        self.len += 1;
+    }
+
+    /// Removes the last element from a vector and returns it, or [`None`] if it
+    /// is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use soa_rs::{Soa, Soars, soa};
+    /// # #[derive(Soars, Debug, PartialEq)]
+    /// # #[soa_derive(Debug, PartialEq)]
+    /// # struct Foo(usize);
+    /// let mut soa = soa![Foo(1), Foo(2), Foo(3)];
+    /// assert_eq!(soa.pop(), Some(Foo(3)));
+    /// assert_eq!(soa, soa![Foo(1), Foo(2)]);
+    /// ```
+    pub fn pop(&mut self) -> Option<T> {
+        if self.len == 0 {
+            None
+        } else {
+            self.len -= 1;
+            Some(unsafe { self.raw().offset(self.len).get() })
         }
     }
 
@@ -110,18 +176,208 @@ where
                         cap: usize::MAX,
                         slice: Slice::empty(),
                         len: 0,
+                        #[cfg(feature = "nightly")]
+                        alloc: Global,
                     }
                 } else {
                     Self {
                         cap: capacity,
                         slice: Slice::with_raw(unsafe { T::Raw::alloc(capacity) }),
                         len: 0,
+                        #[cfg(feature = "nightly")]
+                        alloc: Global,
                     }
                 }
             }
         }
     }
 
+    /// Construct a new, empty `Soa<T>` with at least the specified capacity,
+    /// returning an error rather than panicking or aborting if the allocation
+    /// fails.
+    ///
+    /// Otherwise, this behaves the same as [`Soa::with_capacity`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`TryReserveError`] if the capacity would exceed the maximum
+    /// allocation size or the allocation fails.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use soa_rs::{Soa, Soars, TryReserveError};
+    /// # #[derive(Soars)]
+    /// # #[soa_derive(Debug, PartialEq)]
+    /// # struct Foo(u8, u64);
+    /// let soa = Soa::<Foo>::try_with_capacity(10)?;
+    /// assert_eq!(soa.capacity(), 10);
+    /// assert!(Soa::<Foo>::try_with_capacity(usize::MAX / 4).is_err());
+    /// # Ok::<(), TryReserveError>(())
+    /// ```
+    pub fn try_with_capacity(capacity: usize) -> Result<Self, TryReserveError> {
+        let mut out = Self::new();
+        if capacity > 0 && size_of::<T>() > 0 {
+            out.try_grow(capacity)?;
+        }
+        Ok(out)
+    }
+
+    /// Construct a new, empty `Soa<T>` with capacity for at least `capacity`
+    /// elements, rounded up to a multiple of `LANES`.
+    ///
+    /// This is intended for SIMD kernels that process a fixed number of lanes
+    /// at a time. Together with [`Soa::padded_slices_mut`], it lets the
+    /// remainder be handled as a full set of lanes rather than with scalar
+    /// code.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `LANES` is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use soa_rs::{Soa, Soars};
+    /// # #[derive(Soars)]
+    /// # #[soa_derive(Debug, PartialEq)]
+    /// # struct Foo(u32);
+    /// let soa = Soa::<Foo>::with_simd_padding::<8>(10);
+    /// assert_eq!(soa.len(), 0);
+    /// assert_eq!(soa.capacity(), 16);
+    /// ```
+    pub fn with_simd_padding<const LANES: usize>(capacity: usize) -> Self {
+        assert_ne!(LANES, 0, "lane count must be nonzero");
+        Self::with_capacity(capacity.next_multiple_of(LANES))
+    }
+
+    /// Returns the field slices with the length rounded up to a multiple of
+    /// `LANES`.
+    ///
+    /// The elements past [`Slice::len`] are set to [`Default::default`] and
+    /// are not part of the container. Their values are not preserved between
+    /// calls and may be overwritten by kernels freely. Capacity is grown if
+    /// needed, rounded up to a multiple of `LANES`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `LANES` is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use soa_rs::{Soa, Soars};
+    /// # #[derive(Soars, Clone, Copy, Default)]
+    /// # #[soa_derive(Debug, PartialEq)]
+    /// # struct Foo(u32);
+    /// let mut soa = Soa::<Foo>::with_simd_padding::<4>(6);
+    /// soa.extend([Foo(1), Foo(2), Foo(3), Foo(4), Foo(5)]);
+    /// let slices = soa.padded_slices_mut::<4>();
+    /// assert_eq!(slices.0, [1, 2, 3, 4, 5, 0, 0, 0]);
+    /// for chunk in slices.0.chunks_exact_mut(4) {
+    ///     chunk.iter_mut().for_each(|x| *x *= 2);
+    /// }
+    /// assert_eq!(soa.len(), 5);
+    /// assert_eq!(soa.f0(), [2, 4, 6, 8, 10]);
+    /// ```
+    pub fn padded_slices_mut<const LANES: usize>(&mut self) -> T::SlicesMut<'_>
+    where
+        T: Copy + Default,
+    {
+        assert_ne!(LANES, 0, "lane count must be nonzero");
+        let padded = self.len.next_multiple_of(LANES);
+        if padded > self.cap {
+            self.grow(padded);
+        }
+        for i in self.len..padded {
+            // SAFETY: i < padded <= self.cap, and T: Copy so the padding does
+            // not need to be dropped.
+            unsafe { self.raw().offset(i).set(T::default()) };
+        }
+        unsafe { self.raw().slices_mut(padded) }
+    }
+
+    /// Constructs a new `Soa<T>` from the bytes of each field's array, as
+    /// returned by [`Slice::as_column_bytes`]. This requires the `bytemuck`
+    /// feature.
+    ///
+    /// Columns are matched to fields by name and may be given in any order.
+    /// The bytes are copied into a new allocation, so they need not be aligned
+    /// for the field type.
+    ///
+    /// # Safety
+    ///
+    /// Each column must consist of valid values of the corresponding field's
+    /// type, such as the bytes returned by [`Slice::as_column_bytes`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the columns do not match the fields one-to-one, or if they
+    /// do not all hold the same number of elements.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use soa_rs::{Soa, Soars, soa};
+    /// # #[derive(Soars, Debug, PartialEq)]
+    /// # #[soa_derive(Debug, PartialEq)]
+    /// # #[soa_pod]
+    /// # struct Foo(u8, u16);
+    /// let bar = [2u16, 4].map(u16::to_ne_bytes).concat();
+    /// let columns = [("1", bar.as_slice()), ("0", [1, 3].as_slice())];
+    /// let soa = unsafe { Soa::<Foo>::from_column_bytes(&columns) };
+    /// assert_eq!(soa, soa![Foo(1, 2), Foo(3, 4)]);
+    /// ```
+    #[cfg(feature = "bytemuck")]
+    pub unsafe fn from_column_bytes(columns: &[(&str, &[u8])]) -> Self
+    where
+        T: crate::SoaPod,
+    {
+        let fields = T::pod_columns(T::Raw::dangling());
+        assert_eq!(
+            columns.len(),
+            fields.len(),
+            "number of columns does not match number of fields"
+        );
+        let bytes: Vec<_> = fields
+            .iter()
+            .map(|field| {
+                let mut matches = columns.iter().filter(|(name, _)| *name == field.name);
+                let Some((_, bytes)) = matches.next() else {
+                    panic!("missing column `{}`", field.name);
+                };
+                assert!(
+                    matches.next().is_none(),
+                    "duplicate column `{}`",
+                    field.name
+                );
+                *bytes
+            })
+            .collect();
+
+        let len = fields
+            .iter()
+            .zip(&bytes)
+            .find(|(field, _)| field.size > 0)
+            .map_or(0, |(field, bytes)| bytes.len() / field.size);
+        for (field, bytes) in fields.iter().zip(&bytes) {
+            assert_eq!(
+                bytes.len(),
+                field.size * len,
+                "column `{}` has the wrong length",
+                field.name
+            );
+        }
+
+        let mut out = Self::with_capacity(len);
+        for (field, bytes) in T::pod_columns(out.raw()).into_iter().zip(bytes) {
+            // SAFETY: The allocation has room for len elements of each field
+            unsafe { std::ptr::copy_nonoverlapping(bytes.as_ptr(), field.ptr, bytes.len()) };
+        }
+        out.len = len;
+        out
+    }
+
     /// Constructs a new `Soa<T>` with the given first element.
     ///
     /// This is mainly useful to get around type inference limitations in some
@@ -146,6 +402,35 @@ where
         out
     }
 
+    /// Collects the elements of `iter` into a new `Soa<T>` sorted by the
+    /// given key.
+    ///
+    /// This is equivalent to collecting and then calling
+    /// [`Slice::sort_by_key`], so the sort is stable.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use soa_rs::{Soa, Soars, soa};
+    /// # use std::collections::HashMap;
+    /// # #[derive(Soars, Debug, PartialEq)]
+    /// # #[soa_derive(Debug, PartialEq)]
+    /// # struct Foo(&'static str, usize);
+    /// let map = HashMap::from([("b", 2), ("c", 3), ("a", 1)]);
+    /// let soa = Soa::from_sorted_by_key(map.into_iter().map(|(k, v)| Foo(k, v)), |foo| *foo.0);
+    /// assert_eq!(soa, soa![Foo("a", 1), Foo("b", 2), Foo("c", 3)]);
+    /// ```
+    pub fn from_sorted_by_key<I, K, F>(iter: I, key: F) -> Self
+    where
+        I: IntoIterator<Item = T>,
+        F: FnMut(T::Ref<'_>) -> K,
+        K: Ord,
+    {
+        let mut out: Self = iter.into_iter().collect();
+        out.sort_by_key(key);
+        out
+    }
+
     /// Returns the total number of elements the container can hold without
     /// reallocating.
     ///
@@ -166,6 +451,167 @@ where
         self.cap
     }
 
+    /// Returns the remaining spare capacity of the vector as slices of
+    /// [`MaybeUninit`], one per field.
+    ///
+    /// The returned slices can be used to fill the vector with data before
+    /// marking it as initialized with [`Soa::set_len`]. Every field of an
+    /// element must be written before the element is included in the length.
+    ///
+    /// [`MaybeUninit`]: std::mem::MaybeUninit
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use soa_rs::{Soa, Soars, soa};
+    /// # use std::mem::MaybeUninit;
+    /// #[derive(Soars, Debug, PartialEq)]
+    /// #[soa_derive(Debug, PartialEq)]
+    /// struct Foo {
+    ///     a: u8,
+    ///     b: u16,
+    /// }
+    ///
+    /// let mut soa = Soa::<Foo>::with_capacity(4);
+    /// let spare = soa.spare_capacity_mut();
+    /// for i in 0..2 {
+    ///     spare.a[i] = MaybeUninit::new(i as u8);
+    ///     spare.b[i] = MaybeUninit::new(i as u16 * 10);
+    /// }
+    /// unsafe { soa.set_len(2) };
+    /// assert_eq!(soa, soa![Foo { a: 0, b: 0 }, Foo { a: 1, b: 10 }]);
+    /// ```
+    pub fn spare_capacity_mut(&mut self) -> T::SlicesUninit<'_> {
+        unsafe {
+            self.raw()
+                .offset(self.len)
+                .slices_uninit(self.cap - self.len)
+        }
+    }
+
+    /// Forces the length of the vector to `new_len`.
+    ///
+    /// This is a low-level operation that maintains none of the normal
+    /// invariants of the type. Normally, changing the length is done using
+    /// safe operations such as [`Soa::truncate`], [`Soa::extend`], or
+    /// [`Soa::clear`].
+    ///
+    /// When growing the length, the new elements are usually written first
+    /// through [`Soa::spare_capacity_mut`]. When shrinking the length, the
+    /// elements past `new_len` are not dropped. They are leaked unless the
+    /// caller moves them out or drops them in place beforehand.
+    ///
+    /// # Safety
+    ///
+    /// - `new_len` must be less than or equal to [`Soa::capacity`].
+    /// - Every field of the elements at `old_len..new_len` must be
+    ///   initialized.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use soa_rs::{Soa, Soars, soa};
+    /// # use std::mem::MaybeUninit;
+    /// # #[derive(Soars, Debug, PartialEq)]
+    /// # #[soa_derive(Debug, PartialEq)]
+    /// # struct Foo(usize);
+    /// let mut soa = Soa::<Foo>::with_capacity(3);
+    /// for (i, slot) in soa.spare_capacity_mut().0[..3].iter_mut().enumerate() {
+    ///     *slot = MaybeUninit::new(i);
+    /// }
+    /// // SAFETY: The capacity is at least 3 and the only field of the first
+    /// // three elements was initialized
+    /// unsafe { soa.set_len(3) };
+    /// assert_eq!(soa, soa![Foo(0), Foo(1), Foo(2)]);
+    /// ```
+    pub unsafe fn set_len(&mut self, new_len: usize) {
+        debug_assert!(new_len <= self.cap);
+        self.len = new_len;
+    }
+
+    /// Describes how the field arrays are laid out in the current allocation.
+    ///
+    /// The report lists the offset, size, and preceding padding of each field
+    /// array, along with the total allocation size. This is useful for seeing
+    /// how much space `#[align]` attributes cost compared to a packed layout.
+    /// Fields tagged with `#[soa_skip]` share a single entry named
+    /// `soa_skipped`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use soa_rs::{Soa, Soars};
+    /// #[derive(Soars)]
+    /// #[soa_derive(Debug, PartialEq)]
+    /// struct Foo {
+    ///     a: u8,
+    ///     #[align(64)]
+    ///     b: u8,
+    /// }
+    ///
+    /// let soa = Soa::<Foo>::with_capacity(4);
+    /// let report = soa.capacity_report();
+    /// assert_eq!(report.fields[1].name, "b");
+    /// assert_eq!(report.fields[1].offset, 64);
+    /// assert_eq!(report.fields[1].padding, 60);
+    /// assert_eq!(report.packed_size, 8);
+    /// ```
+    pub fn capacity_report(&self) -> CapacityReport {
+        T::Raw::capacity_report(self.cap).expect("capacity overflow")
+    }
+
+    /// Consumes and leaks the `Soa`, returning a mutable slice of its
+    /// contents with a lifetime of the caller's choosing.
+    ///
+    /// Like [`Vec::leak`], the allocation and the elements are never freed or
+    /// dropped. This is mainly useful for data that lives for the remainder
+    /// of the program. Any unused capacity is leaked as well, so consider
+    /// calling [`Soa::shrink_to_fit`] first.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use soa_rs::{Soa, Soars, soa, SliceMut};
+    /// # #[derive(Soars, Debug, PartialEq)]
+    /// # #[soa_derive(Debug, PartialEq)]
+    /// # struct Foo(usize);
+    /// let soa = soa![Foo(1), Foo(2), Foo(3)];
+    /// let mut leaked: SliceMut<'static, Foo> = soa.leak();
+    /// *leaked.idx_mut(0).0 = 10;
+    /// assert_eq!(leaked, soa![Foo(10), Foo(2), Foo(3)]);
+    /// ```
+    pub fn leak<'a>(self) -> SliceMut<'a, T> {
+        let me = ManuallyDrop::new(self);
+        // SAFETY: The allocation is never freed, so it outlives any lifetime
+        unsafe { SliceMut::from_slice(me.slice, me.len) }
+    }
+
+    /// Splits the `Soa` into a [`Vec`] for each field.
+    ///
+    /// This is the inverse of [`SoaColumns::from_columns`]. Each column owns
+    /// its elements independently, so convert them with
+    /// [`Vec::into_boxed_slice`] if boxed field arrays are needed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use soa_rs::{Soa, Soars, soa};
+    /// # #[derive(Soars, Debug, PartialEq)]
+    /// # #[soa_derive(Debug, PartialEq)]
+    /// # struct Foo { bar: u8, baz: u16 }
+    /// let soa = soa![Foo { bar: 1, baz: 2 }, Foo { bar: 3, baz: 4 }];
+    /// let columns = soa.into_columns();
+    /// let bar: Box<[u8]> = columns.bar.into_boxed_slice();
+    /// assert_eq!(*bar, [1, 3]);
+    /// assert_eq!(columns.baz, [2, 4]);
+    /// ```
+    pub fn into_columns(self) -> T::Columns
+    where
+        T: SoaColumns,
+    {
+        T::into_columns(self)
+    }
+
     /// Decomposes a `Soa<T>` into its raw components.
     ///
     /// Returns the raw pointer to the underlying data, the length of the vector (in
@@ -209,32 +655,16 @@ where
             cap: capacity,
             slice: Slice::with_raw(unsafe { T::Raw::from_parts(ptr, capacity) }),
             len: length,
+            #[cfg(feature = "nightly")]
+            alloc: Global,
         }
     }
 
-    /// Appends an element to the back of a collection.
-    ///
-    /// # Examples
+    /// Removes the first element from a vector and returns it, or [`None`] if
+    /// it is empty.
     ///
-    /// ```
-    /// # use soa_rs::{Soa, Soars, soa};
-    /// # #[derive(Soars, Debug, PartialEq)]
-    /// # #[soa_derive(Debug, PartialEq)]
-    /// # struct Foo(usize);
-    /// let mut soa = soa![Foo(1), Foo(2)];
-    /// soa.push(Foo(3));
-    /// assert_eq!(soa, soa![Foo(1), Foo(2), Foo(3)]);
-    /// ```
-    pub fn push(&mut self, element: T) {
-        self.maybe_grow();
-        unsafe {
-            self.raw().offset(self.len).set(element);
-        }
-        self.len += 1;
-    }
-
-    /// Removes the last element from a vector and returns it, or [`None`] if it
-    /// is empty.
+    /// This shifts all remaining elements to the left, so it is O(n). Use
+    /// [`Soa::swap_remove_front`] if ordering does not need to be preserved.
     ///
     /// # Examples
     ///
@@ -244,15 +674,14 @@ where
     /// # #[soa_derive(Debug, PartialEq)]
     /// # struct Foo(usize);
     /// let mut soa = soa![Foo(1), Foo(2), Foo(3)];
-    /// assert_eq!(soa.pop(), Some(Foo(3)));
-    /// assert_eq!(soa, soa![Foo(1), Foo(2)]);
+    /// assert_eq!(soa.pop_front(), Some(Foo(1)));
+    /// assert_eq!(soa, soa![Foo(2), Foo(3)]);
     /// ```
-    pub fn pop(&mut self) -> Option<T> {
+    pub fn pop_front(&mut self) -> Option<T> {
         if self.len == 0 {
             None
         } else {
-            self.len -= 1;
-            Some(unsafe { self.raw().offset(self.len).get() })
+            Some(self.remove(0))
         }
     }
 
@@ -287,6 +716,73 @@ where
         self.len += 1;
     }
 
+    /// Inserts all elements of `iter` at position `index` within the vector,
+    /// shifting all elements after them to the right.
+    ///
+    /// If the iterator reports an exact size, the tail is shifted once to open
+    /// a gap for the new elements. Otherwise, the elements are inserted one at
+    /// a time.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index > len`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use soa_rs::{Soa, Soars, soa};
+    /// # #[derive(Soars, Debug, PartialEq)]
+    /// # #[soa_derive(Debug, PartialEq)]
+    /// # struct Foo(usize);
+    /// let mut soa = soa![Foo(1), Foo(2)];
+    /// soa.insert_from_iter(1, [Foo(3), Foo(4), Foo(5)]);
+    /// assert_eq!(soa, soa![Foo(1), Foo(3), Foo(4), Foo(5), Foo(2)]);
+    /// ```
+    pub fn insert_from_iter<I>(&mut self, mut index: usize, iter: I)
+    where
+        I: IntoIterator<Item = T>,
+    {
+        assert!(index <= self.len, "index out of bounds");
+        let mut iter = iter.into_iter();
+        let (lower, upper) = iter.size_hint();
+        if lower > 0 && upper == Some(lower) {
+            let gap = lower;
+            self.reserve(gap);
+            let tail = self.len - index;
+            // Hide the tail while the gap is open so that a panicking iterator
+            // leaks it rather than exposing uninitialized elements
+            self.len = index;
+            unsafe {
+                let ith = self.raw().offset(index);
+                ith.copy_to(ith.offset(gap), tail);
+            }
+
+            let mut written = 0;
+            for element in iter.by_ref().take(gap) {
+                unsafe {
+                    self.raw().offset(index + written).set(element);
+                }
+                written += 1;
+            }
+
+            // The size hint is untrusted, so close whatever part of the gap
+            // went unfilled
+            if written < gap {
+                unsafe {
+                    let src = self.raw().offset(index + gap);
+                    src.copy_to(self.raw().offset(index + written), tail);
+                }
+            }
+            self.len = index + written + tail;
+            index += written;
+        }
+
+        for element in iter {
+            self.insert(index, element);
+            index += 1;
+        }
+    }
+
     /// Removes and returns the element at position index within the vector,
     /// shifting all elements after it to the left.
     ///
@@ -312,11 +808,52 @@ where
         out
     }
 
-    /// Reserves capacity for at least additional more elements to be inserted
-    /// in the given `Soa<T>`. The collection may reserve more space to
-    /// speculatively avoid frequent reallocations. After calling reserve,
-    /// capacity will be greater than or equal to `self.len() + additional`.
-    /// Does nothing if capacity is already sufficient.
+    /// Tries to reserve capacity for at least `additional` more elements to be
+    /// inserted in the given `Soa<T>`.
+    ///
+    /// This grows the capacity the same way as [`Soa::reserve`], but returns
+    /// an error rather than panicking or aborting if the capacity overflows or
+    /// the allocator reports a failure. The container is unchanged in that
+    /// case.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`TryReserveError`] if the new capacity would exceed the
+    /// maximum allocation size or the allocation fails.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use soa_rs::{Soa, Soars, soa, TryReserveError};
+    /// # #[derive(Soars, Debug, PartialEq)]
+    /// # #[soa_derive(Debug, PartialEq)]
+    /// # struct Foo(usize);
+    /// let mut soa = soa![Foo(1)];
+    /// soa.try_reserve(10)?;
+    /// assert!(soa.capacity() >= 11);
+    /// assert_eq!(soa.try_reserve(usize::MAX), Err(TryReserveError::CapacityOverflow));
+    /// # Ok::<(), TryReserveError>(())
+    /// ```
+    pub fn try_reserve(&mut self, additional: usize) -> Result<(), TryReserveError> {
+        let new_len = self
+            .len
+            .checked_add(additional)
+            .ok_or(TryReserveError::CapacityOverflow)?;
+        if new_len > self.cap {
+            let new_cap = new_len
+                // Ensure exponential growth
+                .max(self.cap.saturating_mul(2))
+                .max(Self::SMALL_CAPACITY);
+            self.try_grow(new_cap)?;
+        }
+        Ok(())
+    }
+
+    /// Reserves capacity for at least additional more elements to be inserted
+    /// in the given `Soa<T>`. The collection may reserve more space to
+    /// speculatively avoid frequent reallocations. After calling reserve,
+    /// capacity will be greater than or equal to `self.len() + additional`.
+    /// Does nothing if capacity is already sufficient.
     ///
     /// # Examples
     ///
@@ -367,6 +904,9 @@ where
 
     /// Shrinks the capacity of the container as much as possible.
     ///
+    /// If the container is empty, its allocation is released and the capacity
+    /// becomes zero.
+    ///
     /// # Examples
     ///
     /// ```
@@ -384,11 +924,518 @@ where
         self.shrink(self.len);
     }
 
-    /// Shrinks the capacity of the vector with a lower bound.
+    /// Shrinks the capacity of the vector with a lower bound.
+    ///
+    /// The capacity will remain at least as large as both the length and the
+    /// supplied value. If the current capacity is less than the lower limit,
+    /// this is a no-op.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use soa_rs::{Soa, Soars, soa};
+    /// # #[derive(Soars, Debug, PartialEq)]
+    /// # #[soa_derive(Debug, PartialEq)]
+    /// # struct Foo(usize);
+    /// let mut soa = Soa::<Foo>::with_capacity(10);
+    /// soa.extend([Foo(1), Foo(2), Foo(3)]);
+    /// assert_eq!(soa.capacity(), 10);
+    /// soa.shrink_to(4);
+    /// assert_eq!(soa.capacity(), 4);
+    /// soa.shrink_to(0);
+    /// assert_eq!(soa.capacity(), 3);
+    pub fn shrink_to(&mut self, min_capacity: usize) {
+        let new_cap = self.len.max(min_capacity);
+        if new_cap < self.cap {
+            self.shrink(new_cap);
+        }
+    }
+
+    /// Shortens the vector, keeping the first len elements and dropping the rest.
+    ///
+    /// If len is greater or equal to the vector’s current length, this has no
+    /// effect. Note that this method has no effect on the allocated capacity of
+    /// the vector.
+    ///
+    /// # Examples
+    ///
+    /// Truncating a five-element SOA to two elements:
+    /// ```
+    /// # use soa_rs::{Soa, Soars, soa};
+    /// # #[derive(Soars, Debug, PartialEq)]
+    /// # #[soa_derive(Debug, PartialEq)]
+    /// # struct Foo(usize);
+    /// let mut soa = soa![Foo(1), Foo(2), Foo(3), Foo(4), Foo(5)];
+    /// soa.truncate(2);
+    /// assert_eq!(soa, soa![Foo(1), Foo(2)]);
+    /// ```
+    ///
+    /// No truncation occurs when `len` is greater than the SOA's current
+    /// length:
+    /// ```
+    /// # use soa_rs::{Soa, Soars, soa};
+    /// # #[derive(Soars, Debug, PartialEq)]
+    /// # #[soa_derive(Debug, PartialEq)]
+    /// # struct Foo(usize);
+    /// let mut soa = soa![Foo(1), Foo(2), Foo(3)];
+    /// soa.truncate(8);
+    /// assert_eq!(soa, soa![Foo(1), Foo(2), Foo(3)]);
+    /// ```
+    ///
+    /// Truncating with `len == 0` is equivalent to [`Soa::clear`].
+    /// The removed elements are dropped in the order described in the [`Soa`]
+    /// docs.
+    /// ```
+    /// # use soa_rs::{Soa, Soars, soa};
+    /// # #[derive(Soars, Debug, PartialEq)]
+    /// # #[soa_derive(Debug, PartialEq)]
+    /// # struct Foo(usize);
+    /// let mut soa = soa![Foo(1), Foo(2), Foo(3)];
+    /// soa.truncate(0);
+    /// assert_eq!(soa, soa![]);
+    /// ```
+    pub fn truncate(&mut self, len: usize) {
+        if len < self.len {
+            unsafe { drop_elements::<T>(self.raw(), &mut self.len, len) };
+        }
+    }
+
+    /// Resizes the container in place so that its length is equal to
+    /// `new_len`.
+    ///
+    /// If `new_len` is greater than the current length, the container is
+    /// extended by the difference, with each additional slot filled with the
+    /// result of calling `f`. If `new_len` is less than the current length,
+    /// the container is truncated.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use soa_rs::{Soa, Soars, soa};
+    /// # #[derive(Soars, Debug, PartialEq)]
+    /// # #[soa_derive(Debug, PartialEq)]
+    /// # struct Foo(usize);
+    /// let mut soa = soa![Foo(1), Foo(2)];
+    /// let mut next = 2;
+    /// soa.resize_with(4, || {
+    ///     next += 1;
+    ///     Foo(next)
+    /// });
+    /// assert_eq!(soa, soa![Foo(1), Foo(2), Foo(3), Foo(4)]);
+    /// soa.resize_with(1, || unreachable!());
+    /// assert_eq!(soa, soa![Foo(1)]);
+    /// ```
+    pub fn resize_with<F>(&mut self, new_len: usize, mut f: F)
+    where
+        F: FnMut() -> T,
+    {
+        if new_len > self.len {
+            self.reserve_exact(new_len - self.len);
+            while self.len < new_len {
+                self.push(f());
+            }
+        } else {
+            self.truncate(new_len);
+        }
+    }
+
+    /// Resizes the container in place so that its length is equal to
+    /// `new_len`, filling any additional slots with [`Default::default`].
+    ///
+    /// This is equivalent to `resize_with(new_len, T::default)`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use soa_rs::{Soa, Soars, soa};
+    /// # #[derive(Soars, Debug, PartialEq, Default)]
+    /// # #[soa_derive(Debug, PartialEq)]
+    /// # struct Foo(usize);
+    /// let mut soa = soa![Foo(1), Foo(2)];
+    /// soa.resize_default(3);
+    /// assert_eq!(soa, soa![Foo(1), Foo(2), Foo(0)]);
+    /// ```
+    pub fn resize_default(&mut self, new_len: usize)
+    where
+        T: Default,
+    {
+        self.resize_with(new_len, T::default);
+    }
+
+    /// Shortens the vector to exclude the first element matching `pred` and
+    /// everything after it.
+    ///
+    /// If no element matches, the vector is unchanged. See [`Soa::truncate`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use soa_rs::{Soa, Soars, soa};
+    /// # #[derive(Soars, Debug, PartialEq)]
+    /// # #[soa_derive(Debug, PartialEq)]
+    /// # struct Foo(usize);
+    /// let mut soa = soa![Foo(1), Foo(2), Foo(0), Foo(3), Foo(0)];
+    /// soa.truncate_at(|foo| *foo.0 == 0);
+    /// assert_eq!(soa, soa![Foo(1), Foo(2)]);
+    /// ```
+    pub fn truncate_at<P>(&mut self, pred: P)
+    where
+        P: FnMut(T::Ref<'_>) -> bool,
+    {
+        if let Some(index) = self.position(pred) {
+            self.truncate(index);
+        }
+    }
+
+    /// Removes an element from the vector and returns it.
+    ///
+    /// The removed element is replaced by the last element of the vector. This
+    /// does not preserve ordering, but is O(1). If you need to preserve the
+    /// element order, use remove instead.
+    ///
+    /// # Panics
+    ///
+    /// Panics if index is out of bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use soa_rs::{Soa, Soars, soa};
+    /// # #[derive(Soars, Debug, PartialEq)]
+    /// # #[soa_derive(Debug, PartialEq)]
+    /// # struct Foo(usize);
+    /// let mut soa = soa![Foo(0), Foo(1), Foo(2), Foo(3)];
+    ///
+    /// assert_eq!(soa.swap_remove(1), Foo(1));
+    /// assert_eq!(soa, soa![Foo(0), Foo(3), Foo(2)]);
+    ///
+    /// assert_eq!(soa.swap_remove(0), Foo(0));
+    /// assert_eq!(soa, soa![Foo(2), Foo(3)])
+    /// ```
+    pub fn swap_remove(&mut self, index: usize) -> T {
+        if index >= self.len {
+            panic!("index out of bounds")
+        }
+        self.len -= 1;
+        let to_remove = unsafe { self.raw().offset(index) };
+        let last = unsafe { self.raw().offset(self.len) };
+        let out = unsafe { to_remove.get() };
+        unsafe {
+            last.copy_to(to_remove, 1);
+        }
+        out
+    }
+
+    /// Removes the first element from a vector and returns it, or [`None`] if
+    /// it is empty.
+    ///
+    /// The removed element is replaced by the last element of the vector. This
+    /// does not preserve ordering, but is O(1). If you need to preserve the
+    /// element order, use [`Soa::pop_front`] instead.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use soa_rs::{Soa, Soars, soa};
+    /// # #[derive(Soars, Debug, PartialEq)]
+    /// # #[soa_derive(Debug, PartialEq)]
+    /// # struct Foo(usize);
+    /// let mut soa = soa![Foo(0), Foo(1), Foo(2), Foo(3)];
+    /// assert_eq!(soa.swap_remove_front(), Some(Foo(0)));
+    /// assert_eq!(soa, soa![Foo(3), Foo(1), Foo(2)]);
+    /// ```
+    pub fn swap_remove_front(&mut self) -> Option<T> {
+        if self.len == 0 {
+            None
+        } else {
+            Some(self.swap_remove(0))
+        }
+    }
+
+    /// Removes the elements in the given range and returns them as a new
+    /// [`Soa`].
+    ///
+    /// The hole is filled with elements from the end of the vector. This does
+    /// not preserve ordering, but is O(n) in the number of removed elements
+    /// rather than the length of the vector. This is the range equivalent of
+    /// [`Soa::swap_remove`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the start of the range is greater than the end or if the end
+    /// is out of bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use soa_rs::{Soa, Soars, soa};
+    /// # #[derive(Soars, Debug, PartialEq)]
+    /// # #[soa_derive(Debug, PartialEq)]
+    /// # struct Foo(usize);
+    /// let mut soa = soa![Foo(0), Foo(1), Foo(2), Foo(3), Foo(4), Foo(5)];
+    /// let removed = soa.swap_remove_range(1..3);
+    /// assert_eq!(removed, soa![Foo(1), Foo(2)]);
+    /// assert_eq!(soa, soa![Foo(0), Foo(4), Foo(5), Foo(3)]);
+    /// ```
+    pub fn swap_remove_range<R>(&mut self, range: R) -> Self
+    where
+        R: RangeBounds<usize>,
+    {
+        let Some(Range { start, end }) = index::bounds(range, self.len) else {
+            panic!("index out of bounds")
+        };
+
+        let count = end - start;
+        let mut out = Self::with_capacity(count);
+        for i in start..end {
+            out.push(unsafe { self.raw().offset(i).get() });
+        }
+
+        let fill = count.min(self.len - end);
+        unsafe {
+            let src = self.raw().offset(self.len - fill);
+            let dst = self.raw().offset(start);
+            src.copy_to(dst, fill);
+        }
+        self.len -= count;
+        out
+    }
+
+    /// Moves all the elements of other into self, leaving other empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use soa_rs::{Soa, Soars, soa};
+    /// # #[derive(Soars, Debug, PartialEq)]
+    /// # #[soa_derive(Debug, PartialEq)]
+    /// # struct Foo(usize);
+    /// let mut soa1  = soa![Foo(1), Foo(2), Foo(3)];
+    /// let mut soa2 = soa![Foo(4), Foo(5), Foo(6)];
+    /// soa1.append(&mut soa2);
+    /// assert_eq!(soa1, soa![Foo(1), Foo(2), Foo(3), Foo(4), Foo(5), Foo(6)]);
+    /// assert_eq!(soa2, soa![]);
+    /// ```
+    pub fn append(&mut self, other: &mut Self) {
+        self.reserve(other.len);
+        unsafe {
+            other.raw().copy_to(self.raw().offset(self.len), other.len);
+        }
+        self.len += other.len;
+        // The elements were moved out, so they must not be dropped again
+        other.len = 0;
+    }
+
+    /// Appends clones of all elements of `other` to the end of `self`.
+    ///
+    /// Unlike [`Soa::append`], this only needs to borrow the source, so it
+    /// works with any [`AsSlice`] such as a [`SliceRef`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use soa_rs::{Soa, Soars, soa};
+    /// # #[derive(Soars, Debug, PartialEq, Clone)]
+    /// # #[soa_derive(Debug, PartialEq)]
+    /// # struct Foo(usize);
+    /// let mut soa = soa![Foo(1)];
+    /// let other = soa![Foo(2), Foo(3), Foo(4)];
+    /// soa.append_slice(&other.idx(1..));
+    /// assert_eq!(soa, soa![Foo(1), Foo(3), Foo(4)]);
+    /// assert_eq!(other, soa![Foo(2), Foo(3), Foo(4)]);
+    /// ```
+    pub fn append_slice<S>(&mut self, other: &S)
+    where
+        S: AsSlice<Item = T>,
+        T: FromSoaRef,
+    {
+        let other = other.as_slice();
+        self.reserve(other.len());
+        for item in other.iter() {
+            self.push(T::from_soa_ref(item));
+        }
+    }
+
+    /// Concatenates the given slices into a new [`Soa`], cloning each element
+    /// with [`FromSoaRef`].
+    ///
+    /// The total length is computed up front so that only a single allocation
+    /// is made.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use soa_rs::{Soa, Soars, soa};
+    /// # #[derive(Soars, Debug, PartialEq, Clone)]
+    /// # #[soa_derive(Debug, PartialEq)]
+    /// # struct Foo(usize);
+    /// let soa = Soa::concat([soa![Foo(1), Foo(2)], soa![], soa![Foo(3)]]);
+    /// assert_eq!(soa, soa![Foo(1), Foo(2), Foo(3)]);
+    /// assert_eq!(soa.capacity(), 3);
+    /// ```
+    pub fn concat<I, S>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsSlice<Item = T>,
+        T: FromSoaRef,
+    {
+        let parts: Vec<S> = iter.into_iter().collect();
+        let len = parts.iter().map(|part| part.as_slice().len()).sum();
+        let mut out = Self::with_capacity(len);
+        for part in &parts {
+            out.extend(part.as_slice().iter().map(|item| T::from_soa_ref(item)));
+        }
+        out
+    }
+
+    /// Returns a copy of the container, cloning each element with
+    /// [`FromSoaRef`].
+    ///
+    /// The [`Clone`] implementation for [`Soa`] requires `T: Copy`. This is
+    /// the equivalent for element types that are only [`Clone`]. Each field is
+    /// cloned from a reference to it in place, so no owned `T` is ever read out
+    /// of the container.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use soa_rs::{Soa, Soars, soa};
+    /// # #[derive(Soars, Debug, PartialEq, Clone)]
+    /// # #[soa_derive(Debug, PartialEq)]
+    /// # struct Foo(String);
+    /// let soa = soa![Foo("a".into()), Foo("b".into())];
+    /// let copy = soa.clone_soa();
+    /// assert_eq!(soa, copy);
+    /// assert_eq!(copy.capacity(), 2);
+    /// ```
+    pub fn clone_soa(&self) -> Self
+    where
+        T: FromSoaRef,
+    {
+        let mut out = Self::with_capacity(self.len);
+        out.extend(self.iter().map(|item| T::from_soa_ref(item)));
+        out
+    }
+
+    /// Allocates a `Soa<T>` and fills it by copying the elements of `slice`.
+    ///
+    /// This produces the same result as `Soa::from(slice)`, but since `T` is
+    /// [`Copy`], it skips the per-element capacity checks and writes each
+    /// element straight into place after a single allocation.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use soa_rs::{Soa, Soars, soa};
+    /// # #[derive(Soars, Debug, PartialEq, Clone, Copy)]
+    /// # #[soa_derive(Debug, PartialEq)]
+    /// # struct Foo(u8, u16);
+    /// let soa = Soa::from_slice_copy(&[Foo(1, 2), Foo(3, 4)]);
+    /// assert_eq!(soa, soa![Foo(1, 2), Foo(3, 4)]);
+    /// assert_eq!(soa.capacity(), 2);
+    /// ```
+    pub fn from_slice_copy(slice: &[T]) -> Self
+    where
+        T: Copy,
+    {
+        let mut out = Self::with_capacity(slice.len());
+        let raw = out.raw();
+        for (i, &element) in slice.iter().enumerate() {
+            // SAFETY: The capacity is at least slice.len(), and T: Copy means
+            // nothing can panic partway through
+            unsafe { raw.offset(i).set(element) };
+        }
+        out.len = slice.len();
+        out
+    }
+
+    /// Creates an iterator which uses a closure to determine if an element
+    /// should be removed.
+    ///
+    /// If the closure returns `true`, the element is removed and yielded. If
+    /// the closure returns `false`, the element remains in the vector. The
+    /// closure can mutate every element it visits, whether or not it is
+    /// removed.
+    ///
+    /// Elements are removed lazily, so only the elements visited before the
+    /// iterator is dropped are considered. The remaining elements are shifted
+    /// into place when the iterator is dropped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use soa_rs::{Soa, Soars, soa};
+    /// # #[derive(Soars, Debug, PartialEq)]
+    /// # #[soa_derive(Debug, PartialEq)]
+    /// # struct Foo(usize);
+    /// let mut soa = soa![Foo(1), Foo(2), Foo(3), Foo(4), Foo(5)];
+    /// let evens: Vec<_> = soa.extract_if(|foo| *foo.0 % 2 == 0).collect();
+    /// assert_eq!(evens, [Foo(2), Foo(4)]);
+    /// assert_eq!(soa, soa![Foo(1), Foo(3), Foo(5)]);
+    /// ```
+    pub fn extract_if<F>(&mut self, f: F) -> ExtractIf<'_, T, F>
+    where
+        F: FnMut(T::RefMut<'_>) -> bool,
+    {
+        let old_len = self.len;
+        // Elements are moved out as the iterator advances, so hide them until
+        // the iterator is dropped in case it is leaked
+        self.len = 0;
+        ExtractIf {
+            soa: self,
+            idx: 0,
+            del: 0,
+            old_len,
+            pred: f,
+        }
+    }
+
+    /// Removes every element and returns them in an iterator, keeping the
+    /// allocation for reuse.
+    ///
+    /// Unlike [`into_iter`], this leaves the container in place with its
+    /// capacity intact. Any elements that are not yielded are dropped along
+    /// with the iterator.
+    ///
+    /// [`into_iter`]: IntoIterator::into_iter
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use soa_rs::{Soa, Soars, soa};
+    /// # #[derive(Soars, Debug, PartialEq)]
+    /// # #[soa_derive(Debug, PartialEq)]
+    /// # struct Foo(usize);
+    /// let mut soa = soa![Foo(1), Foo(2), Foo(3)];
+    /// let capacity = soa.capacity();
+    /// let drained: Vec<_> = soa.drain_all().collect();
+    /// assert_eq!(drained, [Foo(1), Foo(2), Foo(3)]);
+    /// assert!(soa.is_empty());
+    /// assert_eq!(soa.capacity(), capacity);
+    /// ```
+    pub fn drain_all(&mut self) -> DrainAll<'_, T> {
+        let len = self.len;
+        // The elements are moved out as the iterator advances, so hide them
+        // right away in case it is leaked
+        self.len = 0;
+        DrainAll {
+            iter_raw: IterRaw {
+                slice: self.slice,
+                len,
+                adapter: PhantomData,
+            },
+            _marker: PhantomData,
+        }
+    }
+
+    /// Retains only the elements specified by the predicate, passing each
+    /// element's original index alongside it.
     ///
-    /// The capacity will remain at least as large as both the length and the
-    /// supplied value. If the current capacity is less than the lower limit,
-    /// this is a no-op.
+    /// Removes all elements for which `f(index, element)` returns `false`. The
+    /// index is the position the element had before any elements were removed.
+    /// Elements are visited exactly once, in order, and the order of the
+    /// retained elements is preserved. If the predicate or a destructor panics,
+    /// the vector is left in a valid state with the unvisited elements intact.
     ///
     /// # Examples
     ///
@@ -397,76 +1444,114 @@ where
     /// # #[derive(Soars, Debug, PartialEq)]
     /// # #[soa_derive(Debug, PartialEq)]
     /// # struct Foo(usize);
-    /// let mut soa = Soa::<Foo>::with_capacity(10);
-    /// soa.extend([Foo(1), Foo(2), Foo(3)]);
-    /// assert_eq!(soa.capacity(), 10);
-    /// soa.shrink_to(4);
-    /// assert_eq!(soa.capacity(), 4);
-    /// soa.shrink_to(0);
-    /// assert_eq!(soa.capacity(), 3);
-    pub fn shrink_to(&mut self, min_capacity: usize) {
-        let new_cap = self.len.max(min_capacity);
-        if new_cap < self.cap {
-            self.shrink(new_cap);
-        }
+    /// let mut soa = soa![Foo(10), Foo(20), Foo(30), Foo(40)];
+    /// let mut removed = vec![];
+    /// soa.retain_indexed(|i, foo| {
+    ///     let keep = *foo.0 != 20 && i != 3;
+    ///     if !keep {
+    ///         removed.push(i);
+    ///     }
+    ///     keep
+    /// });
+    /// assert_eq!(soa, soa![Foo(10), Foo(30)]);
+    /// assert_eq!(removed, [1, 3]);
+    /// ```
+    pub fn retain_indexed<F>(&mut self, mut f: F)
+    where
+        F: FnMut(usize, T::Ref<'_>) -> bool,
+    {
+        let mut index = 0;
+        self.extract_if(|el| {
+            let keep = f(index, el.as_soa_ref());
+            index += 1;
+            !keep
+        })
+        .for_each(drop);
     }
 
-    /// Shortens the vector, keeping the first len elements and dropping the rest.
+    /// Removes consecutive repeated elements in the vector according to the
+    /// [`PartialEq`] implementation of [`Soars::Ref`].
     ///
-    /// If len is greater or equal to the vector’s current length, this has no
-    /// effect. Note that this method has no effect on the allocated capacity of
-    /// the vector.
+    /// If the vector is sorted, this removes all duplicates.
     ///
     /// # Examples
     ///
-    /// Truncating a five-element SOA to two elements:
     /// ```
     /// # use soa_rs::{Soa, Soars, soa};
     /// # #[derive(Soars, Debug, PartialEq)]
     /// # #[soa_derive(Debug, PartialEq)]
     /// # struct Foo(usize);
-    /// let mut soa = soa![Foo(1), Foo(2), Foo(3), Foo(4), Foo(5)];
-    /// soa.truncate(2);
-    /// assert_eq!(soa, soa![Foo(1), Foo(2)]);
+    /// let mut soa = soa![Foo(1), Foo(2), Foo(2), Foo(3), Foo(2)];
+    /// soa.dedup();
+    /// assert_eq!(soa, soa![Foo(1), Foo(2), Foo(3), Foo(2)]);
     /// ```
+    pub fn dedup(&mut self)
+    where
+        for<'a> T::Ref<'a>: PartialEq,
+    {
+        self.dedup_by_internal(|a, b| a == b);
+    }
+
+    /// Removes all but the first of consecutive elements in the vector that
+    /// resolve to the same key.
+    ///
+    /// If the vector is sorted by the key, this removes all duplicates.
+    ///
+    /// # Examples
     ///
-    /// No truncation occurs when `len` is greater than the SOA's current
-    /// length:
     /// ```
     /// # use soa_rs::{Soa, Soars, soa};
     /// # #[derive(Soars, Debug, PartialEq)]
     /// # #[soa_derive(Debug, PartialEq)]
     /// # struct Foo(usize);
-    /// let mut soa = soa![Foo(1), Foo(2), Foo(3)];
-    /// soa.truncate(8);
-    /// assert_eq!(soa, soa![Foo(1), Foo(2), Foo(3)]);
+    /// let mut soa = soa![Foo(10), Foo(20), Foo(21), Foo(30), Foo(20)];
+    /// soa.dedup_by_key(|foo| foo.0 / 10);
+    /// assert_eq!(soa, soa![Foo(10), Foo(20), Foo(30), Foo(20)]);
     /// ```
+    pub fn dedup_by_key<K, F>(&mut self, mut key: F)
+    where
+        F: FnMut(T::Ref<'_>) -> K,
+        K: PartialEq,
+    {
+        self.dedup_by_internal(|a, b| key(a) == key(b));
+    }
+
+    /// Removes all but the first of consecutive elements in the vector
+    /// satisfying a given equality relation, returning the number of elements
+    /// removed.
+    ///
+    /// The `same_bucket` function is passed references to two elements from
+    /// the vector. The first is the element being considered and the second
+    /// is the last element that was kept. If `same_bucket` returns `true`, the
+    /// first element is removed. This matches [`Vec::dedup_by`].
+    ///
+    /// # Examples
     ///
-    /// Truncating with `len == 0` is equivalent to [`Soa::clear`].
     /// ```
     /// # use soa_rs::{Soa, Soars, soa};
     /// # #[derive(Soars, Debug, PartialEq)]
     /// # #[soa_derive(Debug, PartialEq)]
-    /// # struct Foo(usize);
-    /// let mut soa = soa![Foo(1), Foo(2), Foo(3)];
-    /// soa.truncate(0);
-    /// assert_eq!(soa, soa![]);
+    /// # struct Foo(usize, char);
+    /// let mut soa = soa![Foo(1, 'a'), Foo(1, 'b'), Foo(2, 'c'), Foo(2, 'd')];
+    /// let removed = soa.dedup_by(|a, b| a.0 == b.0);
+    /// assert_eq!(removed, 2);
+    /// assert_eq!(soa, soa![Foo(1, 'a'), Foo(2, 'c')]);
     /// ```
-    pub fn truncate(&mut self, len: usize) {
-        while len < self.len {
-            self.pop();
-        }
+    pub fn dedup_by<F>(&mut self, mut same_bucket: F) -> usize
+    where
+        F: FnMut(T::Ref<'_>, T::Ref<'_>) -> bool,
+    {
+        let old_len = self.len;
+        self.dedup_by_internal(|a, b| same_bucket(a, b));
+        old_len - self.len
     }
 
-    /// Removes an element from the vector and returns it.
-    ///
-    /// The removed element is replaced by the last element of the vector. This
-    /// does not preserve ordering, but is O(1). If you need to preserve the
-    /// element order, use remove instead.
-    ///
-    /// # Panics
+    /// Removes consecutive elements whose projected values differ by less than
+    /// `epsilon`.
     ///
-    /// Panics if index is out of bounds.
+    /// Each element is compared with the last element that was kept, so a run
+    /// of small steps only collapses while it stays within `epsilon` of the
+    /// start of the run. Elements projecting to NaN are always kept.
     ///
     /// # Examples
     ///
@@ -474,30 +1559,23 @@ where
     /// # use soa_rs::{Soa, Soars, soa};
     /// # #[derive(Soars, Debug, PartialEq)]
     /// # #[soa_derive(Debug, PartialEq)]
-    /// # struct Foo(usize);
-    /// let mut soa = soa![Foo(0), Foo(1), Foo(2), Foo(3)];
-    ///
-    /// assert_eq!(soa.swap_remove(1), Foo(1));
-    /// assert_eq!(soa, soa![Foo(0), Foo(3), Foo(2)]);
-    ///
-    /// assert_eq!(soa.swap_remove(0), Foo(0));
-    /// assert_eq!(soa, soa![Foo(2), Foo(3)])
+    /// # struct Foo(f64);
+    /// let mut soa = soa![Foo(1.0), Foo(1.01), Foo(2.0), Foo(2.05), Foo(1.0)];
+    /// soa.dedup_by_approx(0.1, |foo| *foo.0);
+    /// assert_eq!(soa, soa![Foo(1.0), Foo(2.0), Foo(1.0)]);
     /// ```
-    pub fn swap_remove(&mut self, index: usize) -> T {
-        if index >= self.len {
-            panic!("index out of bounds")
-        }
-        self.len -= 1;
-        let to_remove = unsafe { self.raw().offset(index) };
-        let last = unsafe { self.raw().offset(self.len) };
-        let out = unsafe { to_remove.get() };
-        unsafe {
-            last.copy_to(to_remove, 1);
-        }
-        out
+    pub fn dedup_by_approx<F>(&mut self, epsilon: f64, mut f: F)
+    where
+        F: FnMut(T::Ref<'_>) -> f64,
+    {
+        self.dedup_by_internal(|a, b| (f(a) - f(b)).abs() < epsilon);
     }
 
-    /// Moves all the elements of other into self, leaving other empty.
+    /// Clears the vector, removing all values.
+    ///
+    /// Note that this method has no effect on the allocated capacity of the
+    /// vector. Elements are dropped in the order described in the [`Soa`]
+    /// docs.
     ///
     /// # Examples
     ///
@@ -506,25 +1584,19 @@ where
     /// # #[derive(Soars, Debug, PartialEq)]
     /// # #[soa_derive(Debug, PartialEq)]
     /// # struct Foo(usize);
-    /// let mut soa1  = soa![Foo(1), Foo(2), Foo(3)];
-    /// let mut soa2 = soa![Foo(4), Foo(5), Foo(6)];
-    /// soa1.append(&mut soa2);
-    /// assert_eq!(soa1, soa![Foo(1), Foo(2), Foo(3), Foo(4), Foo(5), Foo(6)]);
-    /// assert_eq!(soa2, soa![]);
+    /// let mut soa = soa![Foo(1), Foo(2)];
+    /// soa.clear();
+    /// assert!(soa.is_empty());
     /// ```
-    pub fn append(&mut self, other: &mut Self) {
-        self.reserve(other.len);
-        for i in 0..other.len {
-            let element = unsafe { other.raw().offset(i).get() };
-            self.push(element);
-        }
-        other.clear();
+    pub fn clear(&mut self) {
+        unsafe { drop_elements::<T>(self.raw(), &mut self.len, 0) };
     }
 
-    /// Clears the vector, removing all values.
+    /// Clears the vector, removing all values, and releases its allocation.
     ///
-    /// Note that this method has no effect on the allocated capacity of the
-    /// vector.
+    /// This is equivalent to calling [`Soa::clear`] followed by
+    /// [`Soa::shrink_to_fit`], but the allocation is freed directly rather
+    /// than reallocated.
     ///
     /// # Examples
     ///
@@ -534,11 +1606,71 @@ where
     /// # #[soa_derive(Debug, PartialEq)]
     /// # struct Foo(usize);
     /// let mut soa = soa![Foo(1), Foo(2)];
-    /// soa.clear();
+    /// soa.clear_and_shrink();
     /// assert!(soa.is_empty());
+    /// assert_eq!(soa.capacity(), 0);
     /// ```
-    pub fn clear(&mut self) {
-        while self.pop().is_some() {}
+    pub fn clear_and_shrink(&mut self) {
+        unsafe { drop_elements::<T>(self.raw(), &mut self.len, 0) };
+        self.shrink(0);
+    }
+
+    /// Removes consecutive elements for which `same_bucket(current, previous)`
+    /// returns true, keeping the first element of each run.
+    fn dedup_by_internal<F>(&mut self, mut same_bucket: F)
+    where
+        F: for<'a> FnMut(T::Ref<'a>, T::Ref<'a>) -> bool,
+    {
+        /// Closes the gap between the kept elements and the unvisited tail,
+        /// even if `same_bucket` or a destructor panics.
+        struct FillGapOnDrop<'a, T>
+        where
+            T: Soars,
+        {
+            read: usize,
+            write: usize,
+            soa: &'a mut Soa<T>,
+        }
+
+        impl<T> Drop for FillGapOnDrop<'_, T>
+        where
+            T: Soars,
+        {
+            fn drop(&mut self) {
+                let tail = self.soa.len - self.read;
+                unsafe {
+                    let src = self.soa.raw().offset(self.read);
+                    let dst = self.soa.raw().offset(self.write);
+                    src.copy_to(dst, tail);
+                }
+                self.soa.len = self.write + tail;
+            }
+        }
+
+        if self.len < 2 {
+            return;
+        }
+
+        let mut gap = FillGapOnDrop {
+            read: 1,
+            write: 1,
+            soa: self,
+        };
+
+        while gap.read < gap.soa.len {
+            let current = unsafe { gap.soa.raw().offset(gap.read) };
+            let previous = unsafe { gap.soa.raw().offset(gap.write - 1) };
+            if same_bucket(unsafe { current.get_ref() }, unsafe { previous.get_ref() }) {
+                gap.read += 1;
+                drop(unsafe { current.get() });
+            } else {
+                unsafe {
+                    current.copy_to(gap.soa.raw().offset(gap.write), 1);
+                }
+                gap.read += 1;
+                gap.write += 1;
+            }
+        }
     }
 
     /// Grows the allocated capacity if `len == cap`.
@@ -594,16 +1726,122 @@ where
 
         self.cap = new_cap;
     }
+
+    /// Grows the allocated capacity, returning an error rather than panicking
+    /// or aborting on failure.
+    fn try_grow(&mut self, new_cap: usize) -> Result<(), TryReserveError> {
+        debug_assert!(size_of::<T>() > 0);
+        debug_assert!(new_cap > self.cap);
+
+        self.raw = if self.cap == 0 {
+            unsafe { T::Raw::try_alloc(new_cap)? }
+        } else {
+            debug_assert!(self.len <= self.cap);
+            unsafe { self.raw().try_realloc_grow(self.cap, new_cap, self.len)? }
+        };
+        self.cap = new_cap;
+        Ok(())
+    }
+}
+
+#[cfg(feature = "nightly")]
+impl<T, A> Soa<T, A>
+where
+    T: Soars,
+    A: Allocator,
+{
+    /// Constructs a new, empty `Soa<T, A>` that allocates with the given
+    /// allocator.
+    ///
+    /// The container does not allocate. See the [`Soa`] docs for which
+    /// methods support allocators other than `Global`.
+    ///
+    /// # Examples
+    /// ```
+    /// #![feature(allocator_api)]
+    /// # use soa_rs::{Soa, Soars};
+    /// # #[derive(Soars, Debug, PartialEq)]
+    /// # #[soa_derive(Debug, PartialEq)]
+    /// # struct Foo(usize);
+    /// use std::alloc::System;
+    ///
+    /// let soa = Soa::<Foo, _>::new_in(System);
+    /// assert!(soa.is_empty());
+    /// ```
+    pub fn new_in(alloc: A) -> Self {
+        Self {
+            cap: if size_of::<T>() == 0 { usize::MAX } else { 0 },
+            slice: Slice::empty(),
+            len: 0,
+            alloc,
+        }
+    }
+
+    /// Constructs a new, empty `Soa<T, A>` with the specified capacity that
+    /// allocates with the given allocator.
+    ///
+    /// See [`Soa::with_capacity`] for how the capacity is chosen.
+    ///
+    /// # Examples
+    /// ```
+    /// #![feature(allocator_api)]
+    /// # use soa_rs::{Soa, Soars};
+    /// # #[derive(Soars, Debug, PartialEq)]
+    /// # #[soa_derive(Debug, PartialEq)]
+    /// # struct Foo(usize);
+    /// use std::alloc::System;
+    ///
+    /// let soa = Soa::<Foo, _>::with_capacity_in(10, System);
+    /// assert!(soa.is_empty());
+    /// ```
+    pub fn with_capacity_in(capacity: usize, alloc: A) -> Self {
+        let mut soa = Self::new_in(alloc);
+        if capacity > 0 && size_of::<T>() > 0 {
+            soa.raw = unsafe { T::Raw::alloc_in(capacity, &soa.alloc) };
+            soa.cap = capacity;
+        }
+        soa
+    }
+}
+
+/// Drops the elements of `raw` from index `start` up to `len` in the order
+/// selected by `#[soa(drop_order)]`, then sets `len` to `start`.
+///
+/// # Safety
+///
+/// The first `len` elements of `raw` must be initialized, and the dropped
+/// elements must not be read again.
+unsafe fn drop_elements<T>(raw: T::Raw, len: &mut usize, start: usize)
+where
+    T: Soars,
+{
+    debug_assert!(start <= *len);
+    if !needs_drop::<T>() {
+        *len = start;
+    } else if T::Raw::DROP_FORWARD {
+        let end = *len;
+        // If a drop panics, the remaining elements are leaked
+        *len = start;
+        for i in start..end {
+            // SAFETY: Each element is read exactly once and is no longer
+            // reachable because the length was already reset
+            drop(unsafe { raw.offset(i).get() });
+        }
+    } else {
+        while *len > start {
+            *len -= 1;
+            drop(unsafe { raw.offset(*len).get() });
+        }
+    }
 }
 
+#[cfg(not(feature = "nightly"))]
 impl<T> Drop for Soa<T>
 where
     T: Soars,
 {
     fn drop(&mut self) {
-        if needs_drop::<T>() {
-            while self.pop().is_some() {}
-        }
+        unsafe { drop_elements::<T>(self.raw(), &mut self.len, 0) };
 
         if size_of::<T>() > 0 && self.cap > 0 {
             unsafe {
@@ -613,6 +1851,23 @@ where
     }
 }
 
+#[cfg(feature = "nightly")]
+impl<T, A> Drop for Soa<T, A>
+where
+    T: Soars,
+    A: Allocator,
+{
+    fn drop(&mut self) {
+        unsafe { drop_elements::<T>(self.raw(), &mut self.len, 0) };
+
+        if size_of::<T>() > 0 && self.cap > 0 {
+            unsafe {
+                self.raw().dealloc_in(self.cap, &self.alloc);
+            }
+        }
+    }
+}
+
 impl<T> IntoIterator for Soa<T>
 where
     T: Soars,
@@ -664,6 +1919,7 @@ where
 // NOTE: Copy is the required bound because calling Clone::clone on a
 // stack-allocated element is unsound in the presence of interior mutability
 // unless the fields are written back, which we also can't do because of &self.
+// Element types that are only Clone can use Soa::clone_soa instead.
 impl<T> Clone for Soa<T>
 where
     T: Soars + Copy,
@@ -764,6 +2020,26 @@ where
     }
 }
 
+impl<T> From<Vec<T>> for Soa<T>
+where
+    T: Soars,
+{
+    /// Allocate a `Soa<T>` and move `value`'s items into it.
+    fn from(value: Vec<T>) -> Self {
+        value.into_iter().collect()
+    }
+}
+
+impl<T> From<Soa<T>> for Vec<T>
+where
+    T: Soars,
+{
+    /// Allocate a `Vec<T>` and move `value`'s items into it.
+    fn from(value: Soa<T>) -> Self {
+        value.into_iter().collect()
+    }
+}
+
 impl<T> Debug for Soa<T>
 where
     T: Soars,
@@ -794,6 +2070,36 @@ where
     }
 }
 
+impl<T> PartialOrd<[T]> for Soa<T>
+where
+    T: Soars,
+    for<'a> T::Ref<'a>: PartialOrd,
+{
+    fn partial_cmp(&self, other: &[T]) -> Option<Ordering> {
+        (**self).partial_cmp(other)
+    }
+}
+
+impl<T, const N: usize> PartialOrd<[T; N]> for Soa<T>
+where
+    T: Soars,
+    for<'a> T::Ref<'a>: PartialOrd,
+{
+    fn partial_cmp(&self, other: &[T; N]) -> Option<Ordering> {
+        (**self).partial_cmp(other)
+    }
+}
+
+impl<T> PartialOrd<Vec<T>> for Soa<T>
+where
+    T: Soars,
+    for<'a> T::Ref<'a>: PartialOrd,
+{
+    fn partial_cmp(&self, other: &Vec<T>) -> Option<Ordering> {
+        (**self).partial_cmp(other)
+    }
+}
+
 impl<T> Hash for Soa<T>
 where
     T: Soars,
@@ -849,6 +2155,7 @@ where
     }
 }
 
+#[cfg(not(feature = "nightly"))]
 impl<T> Deref for Soa<T>
 where
     T: Soars,
@@ -860,6 +2167,7 @@ where
     }
 }
 
+#[cfg(not(feature = "nightly"))]
 impl<T> DerefMut for Soa<T>
 where
     T: Soars,
@@ -869,6 +2177,30 @@ where
     }
 }
 
+#[cfg(feature = "nightly")]
+impl<T, A> Deref for Soa<T, A>
+where
+    T: Soars,
+    A: Allocator,
+{
+    type Target = Slice<T>;
+
+    fn deref(&self) -> &Self::Target {
+        unsafe { self.slice.as_unsized(self.len) }
+    }
+}
+
+#[cfg(feature = "nightly")]
+impl<T, A> DerefMut for Soa<T, A>
+where
+    T: Soars,
+    A: Allocator,
+{
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        unsafe { self.slice.as_unsized_mut(self.len) }
+    }
+}
+
 impl<T> Borrow<Slice<T>> for Soa<T>
 where
     T: Soars,
@@ -905,6 +2237,36 @@ where
 {
 }
 
+impl<T> PartialEq<[T]> for Soa<T>
+where
+    T: Soars,
+    for<'a> T::Ref<'a>: PartialEq,
+{
+    fn eq(&self, other: &[T]) -> bool {
+        **self == *other
+    }
+}
+
+impl<T, const N: usize> PartialEq<[T; N]> for Soa<T>
+where
+    T: Soars,
+    for<'a> T::Ref<'a>: PartialEq,
+{
+    fn eq(&self, other: &[T; N]) -> bool {
+        **self == *other
+    }
+}
+
+impl<T> PartialEq<Vec<T>> for Soa<T>
+where
+    T: Soars,
+    for<'a> T::Ref<'a>: PartialEq,
+{
+    fn eq(&self, other: &Vec<T>) -> bool {
+        **self == *other
+    }
+}
+
 impl<T> AsSlice for Soa<T>
 where
     T: Soars,
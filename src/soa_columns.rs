@@ -0,0 +1,45 @@
+use crate::{Soa, Soars};
+use std::{
+    error::Error,
+    fmt::{self, Display, Formatter},
+};
+
+/// Conversion between a [`Soa`] and a struct holding a [`Vec`] for each field.
+///
+/// This is implemented by the derive macro. The generated `FooColumns` type has
+/// the same fields as `Foo`, but each field of type `T` has type `Vec<T>`.
+/// Fields tagged with `#[soa_flatten]` instead use the columns type of the
+/// nested struct.
+pub trait SoaColumns: Soars + Sized {
+    /// The generated `FooColumns` type.
+    type Columns;
+
+    /// Builds a [`Soa`] from the given columns.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ColumnLengthError`] if the columns do not all have the same
+    /// length.
+    fn from_columns(columns: Self::Columns) -> Result<Soa<Self>, ColumnLengthError>;
+
+    /// Splits a [`Soa`] into its columns.
+    ///
+    /// Each field array is moved into its own [`Vec`], so the columns own
+    /// their elements independently of one another. Use
+    /// [`Vec::into_boxed_slice`] where a boxed slice is needed instead. This
+    /// is the inverse of [`SoaColumns::from_columns`].
+    fn into_columns(soa: Soa<Self>) -> Self::Columns;
+}
+
+/// The error returned by [`SoaColumns::from_columns`] when the columns do not
+/// all have the same length.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ColumnLengthError;
+
+impl Display for ColumnLengthError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "columns have different lengths")
+    }
+}
+
+impl Error for ColumnLengthError {}
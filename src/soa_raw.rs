@@ -1,4 +1,10 @@
-use crate::Soars;
+use crate::{CapacityReport, Soars, TryReserveError};
+use std::alloc::{Layout, LayoutError};
+#[cfg(feature = "nightly")]
+use std::{
+    alloc::{handle_alloc_error, Allocator},
+    ptr::NonNull,
+};
 
 /// A low-level utility providing fundamental operations needed by [`Soa`].
 ///
@@ -30,10 +36,26 @@ pub unsafe trait SoaRaw: Copy + Clone {
     /// using the derive macro.
     type Item: Soars;
 
+    /// Whether elements are dropped first-to-last when a [`Soa`] is dropped,
+    /// cleared, or truncated. This is set with `#[soa(drop_order = "forward")]`.
+    ///
+    /// [`Soa`]: crate::Soa
+    const DROP_FORWARD: bool = false;
+
     /// Creates a [`SoaRaw`] with dangling pointers for all its fields and without
     /// allocating memory.
     fn dangling() -> Self;
 
+    /// Returns the layout of an allocation with room for `capacity` elements.
+    ///
+    /// This is used to embed the arrays of one [`SoaRaw`] within the
+    /// allocation of another, as is done for `#[soa_flatten]` fields.
+    fn layout(capacity: usize) -> Result<Layout, LayoutError>;
+
+    /// Describes where each column is placed in an allocation with room for
+    /// `capacity` elements.
+    fn capacity_report(capacity: usize) -> Result<CapacityReport, LayoutError>;
+
     /// Construct a new [`SoaRaw`] with the given pointer and capacity.
     ///
     /// # Safety
@@ -64,6 +86,14 @@ pub unsafe trait SoaRaw: Copy + Clone {
     #[must_use]
     unsafe fn alloc(capacity: usize) -> Self;
 
+    /// Like [`SoaRaw::alloc`], but returns an error rather than panicking or
+    /// aborting if the allocation fails.
+    ///
+    /// # Safety
+    ///
+    /// The same requirements as [`SoaRaw::alloc`] apply.
+    unsafe fn try_alloc(capacity: usize) -> Result<Self, TryReserveError>;
+
     /// Grows the allocation with room for `old_capacity` elements to fit
     /// `new_capacity` elements and moves `length` number of array elements to
     /// their new locations.
@@ -84,6 +114,20 @@ pub unsafe trait SoaRaw: Copy + Clone {
         length: usize,
     ) -> Self;
 
+    /// Like [`SoaRaw::realloc_grow`], but returns an error rather than
+    /// panicking or aborting if the allocation fails. The original allocation
+    /// is left untouched in that case.
+    ///
+    /// # Safety
+    ///
+    /// The same requirements as [`SoaRaw::realloc_grow`] apply.
+    unsafe fn try_realloc_grow(
+        &mut self,
+        old_capacity: usize,
+        new_capacity: usize,
+        length: usize,
+    ) -> Result<Self, TryReserveError>;
+
     /// Shrinks the allocation with room for `old_capacity` elements to fit
     /// `new_capacity` elements and moves `length` number of array elements to
     /// their new locations.
@@ -115,8 +159,36 @@ pub unsafe trait SoaRaw: Copy + Clone {
     /// - `old_capacity > 0`
     unsafe fn dealloc(self, old_capacity: usize);
 
+    /// Like [`SoaRaw::alloc`], but allocates with the given allocator.
+    ///
+    /// # Safety
+    ///
+    /// The same requirements as [`SoaRaw::alloc`] apply.
+    #[cfg(feature = "nightly")]
+    #[must_use]
+    unsafe fn alloc_in<A: Allocator>(capacity: usize, alloc: &A) -> Self {
+        let layout = Self::layout(capacity).expect("capacity overflow");
+        match alloc.allocate(layout) {
+            Ok(ptr) => Self::from_parts(ptr.as_ptr().cast(), capacity),
+            Err(_) => handle_alloc_error(layout),
+        }
+    }
+
+    /// Like [`SoaRaw::dealloc`], but deallocates with the given allocator.
+    ///
+    /// # Safety
+    ///
+    /// The same requirements as [`SoaRaw::dealloc`] apply. Additionally, the
+    /// allocation must have come from `alloc`.
+    #[cfg(feature = "nightly")]
+    unsafe fn dealloc_in<A: Allocator>(self, old_capacity: usize, alloc: &A) {
+        // SAFETY: We already constructed this layout for a previous allocation
+        let layout = Self::layout(old_capacity).unwrap_unchecked();
+        alloc.deallocate(NonNull::new_unchecked(self.into_parts()), layout);
+    }
+
     /// Copies `count` elements from `src` index to `dst` index in each of the
-    /// arrays.
+    /// arrays, starting with the first array.
     ///
     /// # Safety
     ///
@@ -128,6 +200,17 @@ pub unsafe trait SoaRaw: Copy + Clone {
     /// - `dst + count <= PREV_CAP`
     unsafe fn copy_to(self, dst: Self, count: usize);
 
+    /// Like [`SoaRaw::copy_to`], but starting with the last array.
+    ///
+    /// When the arrays of `dst` are at higher addresses within the same
+    /// allocation as those of `self`, copying in this order avoids overwriting
+    /// data that has not been copied yet.
+    ///
+    /// # Safety
+    ///
+    /// The same requirements as [`SoaRaw::copy_to`] apply.
+    unsafe fn copy_to_reversed(self, dst: Self, count: usize);
+
     /// Sets the element at `index` to `element`.
     ///
     /// # Safety
@@ -184,4 +267,13 @@ pub unsafe trait SoaRaw: Copy + Clone {
     unsafe fn slices<'a>(self, len: usize) -> <Self::Item as Soars>::Slices<'a>;
 
     unsafe fn slices_mut<'a>(self, len: usize) -> <Self::Item as Soars>::SlicesMut<'a>;
+
+    /// Returns slices over the `len` possibly uninitialized elements starting
+    /// at this position.
+    ///
+    /// # Safety
+    ///
+    /// The range must lie within the allocated capacity and must not be
+    /// aliased for the lifetime of the slices.
+    unsafe fn slices_uninit<'a>(self, len: usize) -> <Self::Item as Soars>::SlicesUninit<'a>;
 }
@@ -1,4 +1,4 @@
-use crate::{AsSoaRef, SoaDeref, SoaRaw};
+use crate::{AsSoaRef, SoaDeref, SoaInline, SoaRaw};
 
 #[diagnostic::on_unimplemented(
     label = "SOA type",
@@ -60,4 +60,23 @@ pub unsafe trait Soars: AsSoaRef<Item = Self> {
     type SlicesMut<'a>
     where
         Self: 'a;
+
+    /// The uninitialized spare capacity of a [`Soa`].
+    ///
+    /// For each field with type `T`, this type has a field with type
+    /// `&mut [MaybeUninit<T>]`. Fields tagged with `#[soa_skip]` are grouped
+    /// into a single `soa_skipped` field.
+    ///
+    /// [`Soa`]: crate::Soa
+    type SlicesUninit<'a>
+    where
+        Self: 'a;
+
+    /// Inline storage for up to `N` elements, used by [`SmallSoa`].
+    ///
+    /// For each field with type `T`, this type has a field with type
+    /// `[MaybeUninit<T>; N]`.
+    ///
+    /// [`SmallSoa`]: crate::SmallSoa
+    type Inline<const N: usize>: SoaInline<Item = Self>;
 }
@@ -0,0 +1,37 @@
+use std::{
+    alloc::Layout,
+    error::Error,
+    fmt::{self, Display, Formatter},
+};
+
+/// The error returned by [`Soa::try_reserve`] when the requested capacity
+/// cannot be allocated.
+///
+/// [`Soa::try_reserve`]: crate::Soa::try_reserve
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TryReserveError {
+    /// The computed capacity exceeded the maximum allocation size.
+    CapacityOverflow,
+    /// The memory allocator returned an error.
+    AllocError {
+        /// The layout of the allocation request that failed.
+        layout: Layout,
+    },
+}
+
+impl Display for TryReserveError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::CapacityOverflow => write!(
+                f,
+                "memory allocation failed because the computed capacity exceeded the maximum"
+            ),
+            Self::AllocError { .. } => write!(
+                f,
+                "memory allocation failed because the memory allocator returned an error"
+            ),
+        }
+    }
+}
+
+impl Error for TryReserveError {}
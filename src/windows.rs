@@ -0,0 +1,59 @@
+use crate::{Slice, SliceRef, SoaRaw, Soars};
+use std::marker::PhantomData;
+
+/// An iterator over overlapping subslices of length `size`.
+///
+/// This struct is created by the [`windows`] method.
+///
+/// [`windows`]: Slice::windows
+pub struct Windows<'a, T>
+where
+    T: 'a + Soars,
+{
+    slice: Slice<T, ()>,
+    parts_remaining: usize,
+    size: usize,
+    marker: PhantomData<&'a T>,
+}
+
+impl<'a, T> Windows<'a, T>
+where
+    T: Soars,
+{
+    pub(crate) fn new(slice: &'a Slice<T>, size: usize) -> Self {
+        let parts_remaining = (slice.len() + 1).saturating_sub(size);
+        let slice = unsafe { slice.as_sized() };
+        Self {
+            slice,
+            parts_remaining,
+            size,
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<'a, T> Iterator for Windows<'a, T>
+where
+    T: Soars,
+{
+    type Item = SliceRef<'a, T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.parts_remaining == 0 {
+            None
+        } else {
+            let out = SliceRef {
+                slice: self.slice,
+                len: self.size,
+                marker: PhantomData,
+            };
+            self.parts_remaining -= 1;
+            self.slice.raw = unsafe { self.slice.raw().offset(1) };
+            Some(out)
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.parts_remaining, Some(self.parts_remaining))
+    }
+}